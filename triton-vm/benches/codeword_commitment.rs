@@ -0,0 +1,48 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use itertools::Itertools;
+use rand::prelude::StdRng;
+use rand::Rng;
+use rand_core::SeedableRng;
+use triton_vm::fri::commit_codeword;
+use twenty_first::prelude::*;
+
+criterion_main!(benches);
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = codeword_commitment,
+);
+
+/// Committing to a FRI codeword, either by converting the leafs and hashing the tree's layers
+/// sequentially or by doing both in parallel.
+fn codeword_commitment(c: &mut Criterion) {
+    let mut group = c.benchmark_group("codeword_commitment");
+    let mut rng = StdRng::seed_from_u64(0);
+    let log_2_codeword_len = 20;
+    let codeword_len = 1 << log_2_codeword_len;
+    let codeword = (0..codeword_len).map(|_| rng.gen()).collect_vec();
+
+    let id = BenchmarkId::new("sequential", codeword_len);
+    group.bench_with_input(id, &codeword, |b, codeword| {
+        b.iter(|| sequential_merkle_root(codeword))
+    });
+    let id = BenchmarkId::new("parallel", codeword_len);
+    group.bench_with_input(id, &codeword, |b, codeword| {
+        b.iter(|| commit_codeword::<Tip5>(codeword).unwrap().0.root())
+    });
+    group.finish();
+}
+
+fn sequential_merkle_root(codeword: &[XFieldElement]) -> Digest {
+    let mut layer = codeword.iter().map(|&xfe| Digest::from(xfe)).collect_vec();
+    while layer.len() > 1 {
+        layer = layer
+            .chunks_exact(2)
+            .map(|pair| Tip5::hash_pair(pair[0], pair[1]))
+            .collect();
+    }
+    layer[0]
+}
@@ -0,0 +1,37 @@
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+use triton_vm::instruction::Instruction;
+use triton_vm::instruction::ALL_INSTRUCTIONS;
+use triton_vm::instruction::OPCODE_TO_INSTRUCTION_MAP;
+
+criterion_main!(benches);
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = decode_instruction
+}
+
+/// cargo criterion --bench decode_instruction
+fn decode_instruction(c: &mut Criterion) {
+    let opcodes = ALL_INSTRUCTIONS.map(|instruction| instruction.opcode());
+
+    let mut group = c.benchmark_group("decode_instruction");
+    group.bench_function("lookup table", |b| {
+        b.iter(|| {
+            for &opcode in &opcodes {
+                let _ = black_box(Instruction::try_from(black_box(opcode)));
+            }
+        })
+    });
+    group.bench_function("hash map", |b| {
+        b.iter(|| {
+            for opcode in &opcodes {
+                let _ = black_box(OPCODE_TO_INSTRUCTION_MAP.get(black_box(opcode)));
+            }
+        })
+    });
+    group.finish();
+}
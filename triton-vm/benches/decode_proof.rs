@@ -0,0 +1,41 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+
+use triton_vm::example_programs::FIBONACCI_SEQUENCE;
+use triton_vm::prelude::*;
+use triton_vm::proof_stream::ProofStream;
+
+const FIBONACCI_INDEX: u32 = 1000;
+
+criterion_main!(benches);
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = decode_proof
+}
+
+/// cargo criterion --bench decode_proof
+fn decode_proof(criterion: &mut Criterion) {
+    let program = FIBONACCI_SEQUENCE.clone();
+    let public_input = PublicInput::new(bfe_vec![FIBONACCI_INDEX]);
+    let (aet, output) = program
+        .trace_execution(public_input.clone(), NonDeterminism::default())
+        .unwrap();
+    let claim = Claim::about_program(&program)
+        .with_input(public_input.individual_tokens)
+        .with_output(output);
+    let proof = Stark::default().prove(&claim, &aet).unwrap();
+
+    let mut group = criterion.benchmark_group("decode_proof");
+    group.throughput(Throughput::Elements(proof.0.len() as u64));
+    group.bench_function(BenchmarkId::new("derived", proof.0.len()), |bencher| {
+        bencher.iter(|| ProofStream::try_from(&proof).unwrap())
+    });
+    group.bench_function(BenchmarkId::new("fast", proof.0.len()), |bencher| {
+        bencher.iter(|| ProofStream::try_from_proof_fast(&proof).unwrap())
+    });
+    group.finish();
+}
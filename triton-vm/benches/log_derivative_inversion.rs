@@ -0,0 +1,61 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use itertools::Itertools;
+use num_traits::ConstZero;
+use rand::prelude::StdRng;
+use rand::Rng;
+use rand_core::SeedableRng;
+use twenty_first::math::traits::FiniteField;
+use twenty_first::prelude::*;
+
+criterion_main!(benches);
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = log_derivative_inversion,
+);
+
+/// Accumulating a log derivative, like the Processor Table's lookup arguments do, either by
+/// inverting every row's term individually or by inverting all terms in one batch.
+fn log_derivative_inversion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("log_derivative_inversion");
+    let mut rng = StdRng::seed_from_u64(0);
+    for log_2_num_rows in [10, 12, 14, 16] {
+        let num_rows = 1 << log_2_num_rows;
+        let to_invert = (0..num_rows).map(|_| rng.gen()).collect_vec();
+
+        let id = BenchmarkId::new("individual", num_rows);
+        group.bench_with_input(id, &to_invert, |b, to_invert| {
+            b.iter(|| accumulate_individually_inverted(to_invert))
+        });
+        let id = BenchmarkId::new("batch", num_rows);
+        group.bench_with_input(id, &to_invert, |b, to_invert| {
+            b.iter(|| accumulate_batch_inverted(to_invert))
+        });
+    }
+    group.finish();
+}
+
+fn accumulate_individually_inverted(to_invert: &[XFieldElement]) -> Vec<XFieldElement> {
+    let mut log_derivative = XFieldElement::ZERO;
+    to_invert
+        .iter()
+        .map(|element| {
+            log_derivative += element.inverse();
+            log_derivative
+        })
+        .collect()
+}
+
+fn accumulate_batch_inverted(to_invert: &[XFieldElement]) -> Vec<XFieldElement> {
+    let mut log_derivative = XFieldElement::ZERO;
+    XFieldElement::batch_inversion(to_invert.to_vec())
+        .into_iter()
+        .map(|inverse| {
+            log_derivative += inverse;
+            log_derivative
+        })
+        .collect()
+}
@@ -0,0 +1,52 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+
+use triton_vm::example_programs::program_with_target_cycle_count;
+use triton_vm::prelude::*;
+
+const LOG_2_PADDED_HEIGHTS: std::ops::RangeInclusive<u32> = 10..=18;
+
+criterion_main!(benches);
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = proving_throughput
+}
+
+/// cargo criterion --bench proving_throughput
+fn proving_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("proving_throughput");
+    for log_2_padded_height in LOG_2_PADDED_HEIGHTS {
+        let padded_height = 1 << log_2_padded_height;
+
+        // stay clear of the next smaller power of two, leaving room for the `halt` instruction
+        let cycle_count = padded_height * 3 / 4;
+        let program = program_with_target_cycle_count(cycle_count);
+        let (aet, output) = program
+            .trace_execution(PublicInput::default(), NonDeterminism::default())
+            .unwrap();
+        assert_eq!(padded_height, aet.padded_height());
+
+        let stark = Stark::default();
+        let claim = Claim::about_program(&program).with_output(output);
+
+        // the proof size only depends on the padded height: record it from any benchmarked proof
+        let mut proof_size = None;
+        group.throughput(Throughput::Elements(cycle_count as u64));
+        let bench_id = BenchmarkId::new("log_2_padded_height", log_2_padded_height);
+        group.bench_function(bench_id, |b| {
+            b.iter(|| {
+                let proof = stark.prove(&claim, &aet).unwrap();
+                proof_size.get_or_insert_with(|| proof.encode().len());
+                proof
+            })
+        });
+        if let Some(proof_size) = proof_size {
+            println!("padded height 2^{log_2_padded_height}: proof size {proof_size} bfe");
+        }
+    }
+    group.finish();
+}
@@ -0,0 +1,143 @@
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::measurement::Measurement;
+use criterion::measurement::ValueFormatter;
+use criterion::Criterion;
+use criterion::Throughput;
+
+use triton_vm::prelude::*;
+
+/// Counts all (re-)allocations, allowing to compare tracing with and without pre-allocated
+/// capacity.
+struct CountingAllocator;
+
+static NUM_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The measurement unit for Criterion: the number of (re-)allocations.
+#[derive(Debug, Copy, Clone)]
+struct NumAllocations;
+
+impl Measurement for NumAllocations {
+    type Intermediate = usize;
+    type Value = usize;
+
+    fn start(&self) -> Self::Intermediate {
+        NUM_ALLOCATIONS.load(Ordering::Relaxed)
+    }
+
+    fn end(&self, num_allocations_at_start: Self::Intermediate) -> Self::Value {
+        NUM_ALLOCATIONS.load(Ordering::Relaxed) - num_allocations_at_start
+    }
+
+    fn add(&self, v1: &Self::Value, v2: &Self::Value) -> Self::Value {
+        v1 + v2
+    }
+
+    fn zero(&self) -> Self::Value {
+        0
+    }
+
+    fn to_f64(&self, value: &Self::Value) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &NumAllocationsFormatter
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct NumAllocationsFormatter;
+
+impl ValueFormatter for NumAllocationsFormatter {
+    fn scale_values(&self, _typical_value: f64, _values: &mut [f64]) -> &'static str {
+        "allocations"
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical_value: f64,
+        _throughput: &Throughput,
+        _values: &mut [f64],
+    ) -> &'static str {
+        "allocations/element"
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "allocations"
+    }
+}
+
+criterion_main!(benches, allocation_benches);
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = trace_with_capacity
+}
+
+criterion_group! {
+    name = allocation_benches;
+    config = Criterion::default().sample_size(10).with_measurement(NumAllocations);
+    targets = trace_with_capacity_allocations
+}
+
+/// cargo criterion --bench trace_with_capacity
+fn trace_with_capacity(c: &mut Criterion) {
+    bench_tracing(c, "trace_with_capacity");
+}
+
+fn trace_with_capacity_allocations(c: &mut Criterion<NumAllocations>) {
+    bench_tracing(c, "trace_with_capacity_allocations");
+}
+
+fn bench_tracing<M: Measurement>(c: &mut Criterion<M>, group_name: &str) {
+    let num_iterations = 1_000;
+    let program = triton_program!(
+        push {num_iterations} sponge_init call hash_loop pop 1 halt
+        hash_loop:
+            dup 0 push 0 eq skiz return
+            sponge_squeeze hash pop 5
+            addi -1 recurse
+    );
+    let table_heights = program
+        .estimate_table_heights([].into(), [].into())
+        .unwrap();
+
+    let trace = || program.trace_execution([].into(), [].into()).unwrap();
+    let trace_with_capacity = || {
+        program
+            .trace_execution_with_capacity([].into(), [].into(), table_heights)
+            .unwrap()
+    };
+
+    let mut group = c.benchmark_group(group_name);
+    group.bench_function("without capacity", |b| b.iter(trace));
+    group.bench_function("with capacity", |b| b.iter(trace_with_capacity));
+    group.finish();
+}
@@ -1,5 +1,6 @@
 use std::collections::hash_map::Entry::Occupied;
 use std::collections::hash_map::Entry::Vacant;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::ops::AddAssign;
 
@@ -8,6 +9,7 @@ use itertools::Itertools;
 use ndarray::s;
 use ndarray::Array2;
 use ndarray::Axis;
+use num_traits::One;
 use strum::IntoEnumIterator;
 use twenty_first::prelude::*;
 
@@ -15,6 +17,7 @@ use crate::error::InstructionError;
 use crate::error::InstructionError::InstructionPointerOverflow;
 use crate::instruction::Instruction;
 use crate::program::Program;
+use crate::program::VMTableHeights;
 use crate::table::hash_table::HashTable;
 use crate::table::hash_table::PermutationTrace;
 use crate::table::master_table::TableId;
@@ -22,6 +25,8 @@ use crate::table::op_stack_table::OpStackTableEntry;
 use crate::table::ram_table::RamTableCall;
 use crate::table::table_column::HashBaseTableColumn::CI;
 use crate::table::table_column::MasterBaseTableColumn;
+use crate::table::table_column::OpStackBaseTableColumn;
+use crate::table::table_column::ProcessorBaseTableColumn;
 use crate::table::u32_table::U32TableEntry;
 use crate::table::*;
 use crate::vm::CoProcessorCall;
@@ -107,6 +112,38 @@ impl AlgebraicExecutionTrace {
         aet
     }
 
+    /// Like [`new`](Self::new), but allocates the traces for the given table heights up front,
+    /// avoiding repeated reallocation during execution. The table heights are only a hint: if
+    /// any trace ends up larger than anticipated, it grows as required. Likewise, if the memory
+    /// for a trace cannot be reserved, that trace starts out without pre-allocated capacity.
+    ///
+    /// Since it is unknown how the [Hash Table](TableId::Hash)'s height is split between the
+    /// `hash` instruction and the Sponge instructions, the corresponding traces each get half of
+    /// the capacity remaining after hashing the program.
+    pub fn with_capacity(program: Program, table_heights: VMTableHeights) -> Self {
+        let rows_with_capacity = |height: u32, width: usize| {
+            let capacity = usize::try_from(height).unwrap_or(usize::MAX);
+            let mut data = vec![];
+            let _ = data.try_reserve_exact(capacity.saturating_mul(width));
+            Array2::from_shape_vec([0, width], data).unwrap()
+        };
+
+        let mut aet = Self::new(program);
+        let num_program_hash_rows = u32::try_from(aet.program_hash_trace.nrows()).unwrap();
+        let remaining_hash_height = table_heights.hash.saturating_sub(num_program_hash_rows);
+        let sponge_height = remaining_hash_height / 2;
+        let hash_height = remaining_hash_height - sponge_height;
+
+        aet.processor_trace =
+            rows_with_capacity(table_heights.processor, processor_table::BASE_WIDTH);
+        aet.op_stack_underflow_trace =
+            rows_with_capacity(table_heights.op_stack, op_stack_table::BASE_WIDTH);
+        aet.ram_trace = rows_with_capacity(table_heights.ram, ram_table::BASE_WIDTH);
+        aet.hash_trace = rows_with_capacity(hash_height, hash_table::BASE_WIDTH);
+        aet.sponge_trace = rows_with_capacity(sponge_height, hash_table::BASE_WIDTH);
+        aet
+    }
+
     /// The height of the [AET](AlgebraicExecutionTrace) after [padding][pad].
     ///
     /// Guaranteed to be a power of two.
@@ -145,6 +182,49 @@ impl AlgebraicExecutionTrace {
         }
     }
 
+    /// The number of times each instruction was executed, _i.e._, the number of cycles spent on
+    /// each instruction. Instructions are counted irrespective of their argument: for example,
+    /// `push 1` and `push 2` both count towards the same entry, which is keyed on the instruction
+    /// with its default argument.
+    pub fn instruction_histogram(&self) -> BTreeMap<Instruction, usize> {
+        let ci_column = self
+            .processor_trace
+            .column(ProcessorBaseTableColumn::CI.base_table_index());
+        let is_padding_column = self
+            .processor_trace
+            .column(ProcessorBaseTableColumn::IsPadding.base_table_index());
+
+        let mut histogram = BTreeMap::new();
+        for (&opcode, is_padding) in ci_column.iter().zip(is_padding_column) {
+            if is_padding.is_one() {
+                continue;
+            }
+            let instruction = Instruction::try_from(opcode).expect("opcode in trace is valid");
+            *histogram.entry(instruction).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    /// The Op Stack Table's rows as recorded during execution, _i.e._, every read from and write
+    /// to the op-stack underflow memory. Each entry is of the form `(clk, pointer, value)`, where
+    /// `clk` is the processor's cycle count at the time of access, `pointer` is the op stack
+    /// pointer, and `value` is the element moved to or from the underflow memory.
+    ///
+    /// Useful for manually cross-checking the permutation argument between the Processor Table
+    /// and the Op Stack Table.
+    pub fn op_stack_underflow_rows(&self) -> Vec<(u64, BFieldElement, BFieldElement)> {
+        self.op_stack_underflow_trace
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let clk = row[OpStackBaseTableColumn::CLK.base_table_index()];
+                let pointer = row[OpStackBaseTableColumn::StackPointer.base_table_index()];
+                let value = row[OpStackBaseTableColumn::FirstUnderflowElement.base_table_index()];
+                (clk.value(), pointer, value)
+            })
+            .collect()
+    }
+
     /// # Panics
     ///
     /// - if the table height exceeds [`u32::MAX`]
@@ -358,6 +438,10 @@ impl Ord for TableHeight {
 mod tests {
     use assert2::assert;
 
+    use strum::EnumCount;
+
+    use crate::op_stack::NumberOfWords;
+    use crate::op_stack::OpStackElement;
     use crate::prelude::*;
 
     use super::*;
@@ -372,6 +456,59 @@ mod tests {
         assert!(expected == padded_program);
     }
 
+    #[test]
+    fn excessive_capacity_hint_does_not_abort() {
+        let table_heights = VMTableHeights {
+            processor: u32::MAX,
+            op_stack: u32::MAX,
+            ram: u32::MAX,
+            hash: u32::MAX,
+            u32: u32::MAX,
+        };
+        let program = triton_program!(halt);
+        let aet = AlgebraicExecutionTrace::with_capacity(program.clone(), table_heights);
+        let reference_aet = AlgebraicExecutionTrace::new(program);
+        assert!(reference_aet.program_hash_trace == aet.program_hash_trace);
+        assert!(0 == aet.processor_trace.nrows());
+    }
+
+    #[test]
+    fn capacity_of_hash_table_is_split_between_hash_and_sponge_traces() {
+        let program = triton_program!(halt);
+        let num_program_hash_rows = AlgebraicExecutionTrace::new(program.clone())
+            .program_hash_trace
+            .nrows();
+        let table_heights = VMTableHeights {
+            hash: (num_program_hash_rows + 20).try_into().unwrap(),
+            ..VMTableHeights::default()
+        };
+        let aet = AlgebraicExecutionTrace::with_capacity(program, table_heights);
+
+        let capacity_in_rows =
+            |trace: Array2<BFieldElement>| trace.into_raw_vec().capacity() / hash_table::BASE_WIDTH;
+        assert!(10 == capacity_in_rows(aet.hash_trace));
+        assert!(10 == capacity_in_rows(aet.sponge_trace));
+    }
+
+    #[test]
+    fn instruction_histogram_counts_executed_instructions() {
+        let program = triton_program!(push 1 push 2 add pop 1 halt);
+        let (aet, _) = program
+            .trace_execution(PublicInput::default(), NonDeterminism::default())
+            .unwrap();
+
+        let push = Instruction::Push(bfe!(0));
+        let pop = Instruction::Pop(NumberOfWords::N1);
+        let expected = [
+            (push, 2),
+            (Instruction::Add, 1),
+            (pop, 1),
+            (Instruction::Halt, 1),
+        ];
+        let expected = expected.into_iter().collect::<BTreeMap<_, _>>();
+        assert!(expected == aet.instruction_histogram());
+    }
+
     #[test]
     fn height_of_any_table_can_be_computed() {
         let program = triton_program!(halt);
@@ -384,4 +521,27 @@ mod tests {
             let _ = aet.height_of_table(table);
         }
     }
+
+    #[test]
+    fn pushing_beyond_op_stack_capacity_records_underflow_writes() {
+        let program = triton_program!(
+            push  1 push  2 push  3 push  4 push  5 push  6 push  7 push  8 push  9
+            push 10 push 11 push 12 push 13 push 14 push 15 push 16 push 17 halt
+        );
+        let (aet, _) = program
+            .trace_execution(PublicInput::default(), NonDeterminism::default())
+            .unwrap();
+
+        let underflow_rows = aet.op_stack_underflow_rows();
+        assert!(17 == underflow_rows.len());
+        for (i, &(clk, pointer, _)) in underflow_rows.iter().enumerate() {
+            assert!(i as u64 == clk);
+            assert!(bfe!((OpStackElement::COUNT + i) as u64) == pointer);
+        }
+
+        // The first 16 pushes move the initial op stack into the underflow memory, the last push
+        // moves the first pushed element there.
+        let (_, _, last_value) = underflow_rows.last().unwrap();
+        assert!(bfe!(1) == *last_value);
+    }
 }
@@ -11,6 +11,7 @@ use crate::instruction::Instruction;
 use crate::proof_item::ProofItem;
 use crate::proof_item::ProofItemVariant;
 use crate::proof_stream::ProofStream;
+use crate::table::challenges::ChallengeId;
 use crate::vm::VMState;
 use crate::BFieldElement;
 
@@ -29,11 +30,36 @@ impl VMError {
         let vm_state = Box::new(vm_state);
         Self { source, vm_state }
     }
+
+    /// The clock cycle in which Triton VM crashed.
+    pub fn cycle_count(&self) -> u32 {
+        self.vm_state.cycle_count
+    }
+
+    /// The instruction pointer at the time of the crash.
+    pub fn instruction_pointer(&self) -> usize {
+        self.vm_state.instruction_pointer
+    }
+
+    /// The instruction that caused the crash, if the instruction pointer points into the program.
+    pub fn instruction(&self) -> Option<Instruction> {
+        self.vm_state.current_instruction().ok()
+    }
 }
 
 impl Display for VMError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        writeln!(f, "VM error: {}", self.source)?;
+        let cycle_count = self.cycle_count();
+        let ip = self.instruction_pointer();
+        write!(
+            f,
+            "VM error: {} at cycle {cycle_count}, ip {ip}",
+            self.source
+        )?;
+        match self.instruction() {
+            Some(instruction) => writeln!(f, ", instruction `{instruction}`")?,
+            None => writeln!(f)?,
+        }
         writeln!(f, "VM state:")?;
         writeln!(f, "{}", self.vm_state)
     }
@@ -60,11 +86,14 @@ pub enum InstructionError {
     #[error("jump stack is empty")]
     JumpStackIsEmpty,
 
-    #[error("assertion failed: st0 must be 1")]
-    AssertionFailed,
+    #[error("assertion failed with error code {code}: st0 must be 1")]
+    AssertionFailed { code: u32 },
 
-    #[error("vector assertion failed: stack[{0}] != stack[{}]", .0 + Digest::LEN)]
-    VectorAssertionFailed(usize),
+    #[error(
+        "vector assertion failed with error code {code}: stack[{index}] != stack[{}]",
+        index + Digest::LEN
+    )]
+    VectorAssertionFailed { index: usize, code: u32 },
 
     #[error("0 does not have a multiplicative inverse")]
     InverseOfZero,
@@ -124,6 +153,77 @@ pub enum ProofStreamError {
 
     #[error(transparent)]
     DecodingError(#[from] <ProofStream as BFieldCodec>::Error),
+
+    #[error("proof is truncated")]
+    Truncated,
+
+    #[error("proof header is malformed or of an unsupported format version")]
+    BadHeader,
+
+    #[error("proof's length indicator does not match its actual length")]
+    LengthMismatch,
+
+    #[error("proof item of {size} bytes exceeds the limit of {max_size} bytes")]
+    ItemTooLarge { size: usize, max_size: usize },
+
+    #[error(transparent)]
+    ItemDecodingError(#[from] <ProofItem as BFieldCodec>::Error),
+
+    #[error("compressed authentication structure is malformed")]
+    MalformedAuthenticationStructure,
+
+    #[error("proof contains non-canonical field element {0}")]
+    NonCanonicalWord(u64),
+
+    #[error("failed to read proof")]
+    ReadError(#[from] std::io::Error),
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum StarkParameterError {
+    #[error("the FRI expansion factor must be greater than 1")]
+    FriExpansionFactorTooSmall,
+
+    #[error("the FRI expansion factor must be a power of 2")]
+    FriExpansionFactorUnsupported,
+
+    #[error(
+        "{num_collinearity_checks} collinearity checks with FRI expansion factor \
+        {fri_expansion_factor} do not achieve security level {security_level}"
+    )]
+    InsufficientSecurity {
+        security_level: usize,
+        fri_expansion_factor: usize,
+        num_collinearity_checks: usize,
+    },
+}
+
+/// Indicates that the domains of a [`MasterBaseTable`] are too small for the requested
+/// [number of trace randomizers](MasterBaseTable::with_num_randomizers).
+///
+/// [`MasterBaseTable`]: crate::table::master_table::MasterBaseTable
+/// [MasterBaseTable::with_num_randomizers]: crate::table::master_table::MasterBaseTable::with_num_randomizers
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum NumRandomizersError {
+    #[error(
+        "quotient domain of length {domain_length} is too small \
+        for {num_trace_randomizers} trace randomizers"
+    )]
+    QuotientDomainTooSmall {
+        num_trace_randomizers: usize,
+        domain_length: usize,
+    },
+
+    #[error(
+        "FRI domain of length {domain_length} is too small \
+        for {num_trace_randomizers} trace randomizers"
+    )]
+    FriDomainTooSmall {
+        num_trace_randomizers: usize,
+        domain_length: usize,
+    },
 }
 
 #[non_exhaustive]
@@ -203,6 +303,40 @@ pub enum ProgramDecodingError {
 
     #[error("missing argument for instruction {1} at index {0}")]
     MissingArgument(usize, Instruction),
+
+    #[error("malformed argument for instruction {1} at index {0}")]
+    MalformedArgument(usize, Instruction),
+
+    #[error("word at index {0} is not a canonical representation of a field element")]
+    NonCanonicalWord(usize),
+}
+
+/// Indicates that [resuming](crate::program::Program::resume_from) a program from a
+/// [`VMState`] failed.
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum ResumptionError {
+    #[error("the VM state belongs to a different program")]
+    ProgramMismatch,
+
+    #[error(transparent)]
+    VMError(#[from] VMError),
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ProgramLoadingError {
+    #[error("failed to read file")]
+    ReadError(#[from] std::io::Error),
+
+    #[error("failed to parse program: {0}")]
+    ParseError(String),
+
+    #[error("failed to parse public input")]
+    PublicInputParseError(#[from] std::num::ParseIntError),
+
+    #[error("failed to parse non-determinism")]
+    NonDeterminismParseError(#[from] serde_json::Error),
 }
 
 #[non_exhaustive]
@@ -211,6 +345,9 @@ pub enum ProvingError {
     #[error("claimed program digest does not match actual program digest")]
     ProgramDigestMismatch,
 
+    #[error("claimed public input does not match actual public input")]
+    PublicInputMismatch,
+
     #[error("claimed public output does not match actual public output")]
     PublicOutputMismatch,
 
@@ -234,6 +371,22 @@ pub enum ProvingError {
 
     #[error(transparent)]
     VMError(#[from] VMError),
+
+    /// Writing the proof failed. Keeps the kind and the message of the underlying
+    /// [`io::Error`](std::io::Error), which itself can be neither cloned nor compared.
+    #[error("failed to write proof: {message}")]
+    WriteError {
+        kind: std::io::ErrorKind,
+        message: String,
+    },
+}
+
+impl From<std::io::Error> for ProvingError {
+    fn from(err: std::io::Error) -> Self {
+        let kind = err.kind();
+        let message = err.to_string();
+        Self::WriteError { kind, message }
+    }
 }
 
 #[non_exhaustive]
@@ -269,6 +422,15 @@ pub enum VerificationError {
     #[error("the number of received extension table rows does not match the parameters")]
     IncorrectNumberOfExtTableRows,
 
+    #[error("the claim embedded in the proof does not match the given claim")]
+    ClaimMismatch,
+
+    #[error("log2 of the padded height ({0}) is out of range")]
+    Log2PaddedHeightOutOfRange(u32),
+
+    #[error("expected {expected} proof items but got {actual}")]
+    IncorrectNumberOfProofItems { expected: usize, actual: usize },
+
     #[error(transparent)]
     ProofStreamError(#[from] ProofStreamError),
 
@@ -282,6 +444,29 @@ pub enum VerificationError {
     FriValidationError(#[from] FriValidationError),
 }
 
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum ChallengeError {
+    #[error("challenge {0} is missing")]
+    MissingChallenge(ChallengeId),
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum TraceError {
+    #[error("current instruction does not match its instruction bits in row {row}")]
+    InconsistentInstructionBits { row: usize },
+
+    #[error("clock jump difference lookup multiplicity is inconsistent in row {row}")]
+    InconsistentClockJumpDifferenceMultiplicity { row: usize },
+
+    #[error("clock jump difference {clock_jump_difference} exceeds the table's {num_rows} rows")]
+    ClockJumpDifferenceOutOfRange {
+        clock_jump_difference: u64,
+        num_rows: usize,
+    },
+}
+
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
 pub enum OpStackElementError {
@@ -346,7 +531,25 @@ mod tests {
     fn assert_false() {
         let program = triton_program!(push 0 assert halt);
         let_assert!(Err(err) = program.run([].into(), [].into()));
-        let_assert!(InstructionError::AssertionFailed = err.source);
+        let_assert!(InstructionError::AssertionFailed { code: 0 } = err.source);
+    }
+
+    #[test]
+    fn failing_assert_reports_error_code() {
+        let program = triton_program!(push 1 assert 3 push 0 assert 7 halt);
+        let_assert!(Err(err) = program.run([].into(), [].into()));
+        let_assert!(InstructionError::AssertionFailed { code: 7 } = err.source);
+    }
+
+    #[test]
+    fn failing_vector_assert_reports_error_code() {
+        let program = triton_program! {
+            push 0 push 0 push 0 push 0 push 0
+            push 0 push 0 push 0 push 0 push 1
+            assert_vector 42 halt
+        };
+        let_assert!(Err(err) = program.run([].into(), [].into()));
+        let_assert!(InstructionError::VectorAssertionFailed { index: 0, code: 42 } = err.source);
     }
 
     #[test]
@@ -357,7 +560,7 @@ mod tests {
             assert_vector halt
         };
         let_assert!(Err(err) = program.run([].into(), [].into()));
-        let_assert!(InstructionError::VectorAssertionFailed(index) = err.source);
+        let_assert!(InstructionError::VectorAssertionFailed { index, .. } = err.source);
         assert!(1 == index);
     }
 
@@ -390,7 +593,7 @@ mod tests {
         };
 
         let_assert!(Err(err) = program.run([].into(), [].into()));
-        let_assert!(InstructionError::VectorAssertionFailed(index) = err.source);
+        let_assert!(InstructionError::VectorAssertionFailed { index, .. } = err.source);
         prop_assert_eq!(disturbance_index, index);
     }
 
@@ -415,6 +618,35 @@ mod tests {
         let_assert!(InstructionError::DivisionByZero = err.source);
     }
 
+    #[test]
+    fn vm_error_reports_cycle_instruction_pointer_and_instruction() {
+        let program = triton_program!(push 0 push 5 div_mod halt);
+        let_assert!(Err(err) = program.run([].into(), [].into()));
+        let_assert!(InstructionError::DivisionByZero = err.source);
+        assert!(2 == err.cycle_count());
+        assert!(4 == err.instruction_pointer());
+        assert!(Some(Instruction::DivMod) == err.instruction());
+
+        let err_string = err.to_string();
+        let_assert!(Some(first_line) = err_string.lines().next());
+        let expected =
+            "VM error: division by 0 is impossible at cycle 2, ip 4, instruction `div_mod`";
+        assert!(expected == first_line);
+    }
+
+    #[test]
+    fn vm_error_without_current_instruction_reports_cycle_and_instruction_pointer() {
+        let program = triton_program!(push 1 pop 1);
+        let_assert!(Err(err) = program.run([].into(), [].into()));
+        let_assert!(InstructionError::InstructionPointerOverflow = err.source);
+        assert!(None == err.instruction());
+
+        let err_string = err.to_string();
+        let_assert!(Some(first_line) = err_string.lines().next());
+        let expected = "VM error: instruction pointer points outside of program at cycle 2, ip 4";
+        assert!(expected == first_line);
+    }
+
     #[test]
     fn log_of_zero() {
         let program = triton_program!(push 0 log_2_floor halt);
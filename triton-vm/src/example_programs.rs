@@ -1,6 +1,7 @@
 use lazy_static::lazy_static;
 
 use crate::program::Program;
+use crate::triton_asm;
 use crate::triton_program;
 
 lazy_static! {
@@ -15,6 +16,41 @@ lazy_static! {
         merkle_tree_authentication_path_verify();
 }
 
+/// A program that executes exactly `cycle_count` many instructions, including the final `halt`,
+/// or a single `halt` if `cycle_count` is 0. Since the program requires neither input nor
+/// non-determinism, and the Processor Table is its tallest table, it is well suited for measuring
+/// proving throughput, _i.e._, the proving time per cycle.
+pub fn program_with_target_cycle_count(cycle_count: usize) -> Program {
+    // push n call countdown pop 1 halt
+    const NUM_CYCLES_OUTSIDE_LOOP: usize = 4;
+    // push -1 add dup 0 skiz recurse / return
+    const NUM_CYCLES_PER_ITERATION: usize = 5;
+
+    let Some(num_cycles_in_loop) = cycle_count.checked_sub(NUM_CYCLES_OUTSIDE_LOOP) else {
+        let nops = triton_asm![nop; cycle_count.saturating_sub(1)];
+        return triton_program!({&nops} halt);
+    };
+    let num_iterations = num_cycles_in_loop / NUM_CYCLES_PER_ITERATION;
+    let nops = triton_asm![nop; num_cycles_in_loop % NUM_CYCLES_PER_ITERATION];
+    if num_iterations == 0 {
+        return triton_program!({&nops} nop nop nop halt);
+    }
+
+    triton_program!(
+        {&nops}
+        push {num_iterations}
+        call countdown
+        pop 1
+        halt
+
+        countdown:
+            push -1 add
+            dup 0 skiz
+                recurse
+            return
+    )
+}
+
 fn fibonacci_sequence() -> Program {
     triton_program!(
         // initialize stack: ⊥ 0 1 i
@@ -716,3 +752,42 @@ pub(crate) fn calculate_new_mmr_peaks_from_append_with_safe_lists() -> Program {
             return
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use assert2::assert;
+    use assert2::let_assert;
+    use test_strategy::proptest;
+
+    use crate::prelude::*;
+    use crate::program::ProgramAndInput;
+    use crate::shared_tests::prove_and_verify;
+
+    use super::*;
+
+    #[proptest(cases = 50)]
+    fn program_with_target_cycle_count_executes_target_number_of_cycles(
+        #[strategy(0_usize..1_000)] cycle_count: usize,
+    ) {
+        let program = program_with_target_cycle_count(cycle_count);
+        let_assert!(
+            Ok((aet, _)) =
+                program.trace_execution(PublicInput::default(), NonDeterminism::default())
+        );
+        assert!(cycle_count.max(1) == aet.processor_trace.nrows());
+    }
+
+    #[test]
+    fn program_with_target_cycle_count_for_smallest_benchmarked_padded_height_can_be_proven() {
+        let padded_height = 1 << 10;
+        let program = program_with_target_cycle_count(padded_height * 3 / 4);
+        let_assert!(
+            Ok((aet, _)) =
+                program.trace_execution(PublicInput::default(), NonDeterminism::default())
+        );
+        assert!(padded_height == aet.padded_height());
+        assert!(aet.processor_trace.nrows() == aet.height().height);
+
+        prove_and_verify(ProgramAndInput::new(program), 1);
+    }
+}
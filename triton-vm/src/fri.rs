@@ -170,8 +170,8 @@ impl<H: AlgebraicHasher> ProverRound<H> {
     }
 
     fn merkle_tree_from_codeword(codeword: &[XFieldElement]) -> ProverResult<MerkleTree<H>> {
-        let digests = codeword_as_digests(codeword);
-        CpuParallel::from_digests(&digests).map_err(FriProvingError::MerkleTreeError)
+        let (merkle_tree, _) = commit_codeword(codeword)?;
+        Ok(merkle_tree)
     }
 
     fn split_and_fold(&self, folding_challenge: XFieldElement) -> Vec<XFieldElement> {
@@ -623,6 +623,23 @@ fn codeword_as_digests(codeword: &[XFieldElement]) -> Vec<Digest> {
     codeword.par_iter().map(|&xfe| xfe.into()).collect()
 }
 
+/// Commit to a codeword by building a [`MerkleTree`] over its elements. Both the conversion of
+/// the codeword's elements into leafs and the construction of the tree happen in parallel.
+///
+/// Returns the Merkle tree as well as its leafs, _i.e._, the codeword's elements as
+/// [`Digest`]s. The Merkle root is identical to that of a sequentially built tree.
+///
+/// # Errors
+///
+/// Errors if the codeword is empty or its length is not a power of two.
+pub fn commit_codeword<H: AlgebraicHasher>(
+    codeword: &[XFieldElement],
+) -> Result<(MerkleTree<H>, Vec<Digest>), FriProvingError> {
+    let leafs = codeword_as_digests(codeword);
+    let merkle_tree = CpuParallel::from_digests(&leafs)?;
+    Ok((merkle_tree, leafs))
+}
+
 /// Use the barycentric Lagrange evaluation formula to extrapolate the codeword
 /// to an out-of-domain location.
 ///
@@ -1078,6 +1095,32 @@ mod tests {
         let _verdict = fri.verify(&mut proof_stream);
     }
 
+    #[proptest]
+    fn parallel_codeword_commitment_matches_sequential_merkle_root(
+        #[strategy(0_usize..12)] _log_codeword_len: usize,
+        #[strategy(vec(arb(), 1 << #_log_codeword_len))] codeword: Vec<XFieldElement>,
+    ) {
+        let (merkle_tree, leafs) = commit_codeword::<Tip5>(&codeword).unwrap();
+        let sequential_leafs = codeword.iter().map(|&xfe| Digest::from(xfe)).collect_vec();
+        prop_assert_eq!(&sequential_leafs, &leafs);
+
+        let mut layer = sequential_leafs;
+        while layer.len() > 1 {
+            layer = layer
+                .chunks_exact(2)
+                .map(|pair| Tip5::hash_pair(pair[0], pair[1]))
+                .collect();
+        }
+        prop_assert_eq!(layer[0], merkle_tree.root());
+    }
+
+    #[test]
+    fn committing_to_codeword_of_length_not_power_of_two_fails() {
+        let codeword = xfe_vec![0; 3];
+        let_assert!(Err(err) = commit_codeword::<Tip5>(&codeword));
+        assert!(let FriProvingError::MerkleTreeError(_) = err);
+    }
+
     #[proptest]
     fn polynomial_evaluation_and_barycentric_evaluation_are_equivalent(
         #[strategy(1_usize..13)] _log_num_coefficients: usize,
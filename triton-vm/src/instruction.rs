@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -10,6 +11,7 @@ use get_size::GetSize;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use num_traits::ConstZero;
+use num_traits::One;
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 use strum::EnumCount;
@@ -24,6 +26,7 @@ use crate::instruction::InstructionBit::*;
 use crate::op_stack::NumberOfWords::*;
 use crate::op_stack::OpStackElement::*;
 use crate::op_stack::*;
+use crate::table::master_table::TableId;
 
 type Result<T> = result::Result<T, InstructionError>;
 
@@ -34,6 +37,13 @@ pub const ALL_INSTRUCTIONS: [Instruction; Instruction::COUNT] =
     all_instructions_with_default_args();
 pub const ALL_INSTRUCTION_NAMES: [&str; Instruction::COUNT] = all_instruction_names();
 
+/// The number of distinct opcodes that can be expressed using all [instruction bits](InstructionBit).
+const NUM_OPCODES: usize = 1 << InstructionBit::COUNT;
+
+/// The instruction (with default arguments, if any) for every opcode, or `None` if the opcode
+/// is invalid. Allows decoding opcodes through a simple lookup.
+const OPCODE_TO_INSTRUCTION: [Option<Instruction>; NUM_OPCODES] = opcode_to_instruction();
+
 lazy_static! {
     pub static ref OPCODE_TO_INSTRUCTION_MAP: HashMap<u32, Instruction> = {
         let mut opcode_to_instruction_map = HashMap::new();
@@ -174,6 +184,7 @@ pub enum AnInstruction<Dest: PartialEq + Default> {
     Recurse,
     RecurseOrReturn,
     Assert,
+    AssertWithCode(u32),
 
     // Memory access
     ReadMem(NumberOfWords),
@@ -182,6 +193,7 @@ pub enum AnInstruction<Dest: PartialEq + Default> {
     // Hashing-related
     Hash,
     AssertVector,
+    AssertVectorWithCode(u32),
     SpongeInit,
     SpongeAbsorb,
     SpongeAbsorbMem,
@@ -218,6 +230,10 @@ pub enum AnInstruction<Dest: PartialEq + Default> {
     MerkleStep,
     XxDotStep,
     XbDotStep,
+
+    // OpStack rearrangement
+    Pick(OpStackElement),
+    Place(OpStackElement),
 }
 
 impl<Dest: PartialEq + Default> AnInstruction<Dest> {
@@ -237,10 +253,12 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
             Recurse => 24,
             RecurseOrReturn => 32,
             Assert => 10,
+            AssertWithCode(_) => 7,
             ReadMem(_) => 41,
             WriteMem(_) => 11,
             Hash => 18,
             AssertVector => 26,
+            AssertVectorWithCode(_) => 15,
             SpongeInit => 40,
             SpongeAbsorb => 34,
             SpongeAbsorbMem => 48,
@@ -267,6 +285,8 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
             MerkleStep => 36,
             XxDotStep => 80,
             XbDotStep => 88,
+            Pick(_) => 65,
+            Place(_) => 73,
         }
     }
 
@@ -284,11 +304,11 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
             Return => "return",
             Recurse => "recurse",
             RecurseOrReturn => "recurse_or_return",
-            Assert => "assert",
+            Assert | AssertWithCode(_) => "assert",
             ReadMem(_) => "read_mem",
             WriteMem(_) => "write_mem",
             Hash => "hash",
-            AssertVector => "assert_vector",
+            AssertVector | AssertVectorWithCode(_) => "assert_vector",
             SpongeInit => "sponge_init",
             SpongeAbsorb => "sponge_absorb",
             SpongeAbsorbMem => "sponge_absorb_mem",
@@ -315,6 +335,8 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
             MerkleStep => "merkle_step",
             XxDotStep => "xx_dot_step",
             XbDotStep => "xb_dot_step",
+            Pick(_) => "pick",
+            Place(_) => "place",
         }
     }
 
@@ -327,8 +349,10 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
         match self {
             Pop(_) | Push(_) => 2,
             Divine(_) => 2,
+            Pick(_) | Place(_) => 2,
             Dup(_) | Swap(_) => 2,
             Call(_) => 2,
+            AssertWithCode(_) | AssertVectorWithCode(_) => 2,
             ReadMem(_) | WriteMem(_) => 2,
             AddI(_) => 2,
             ReadIo(_) | WriteIo(_) => 2,
@@ -353,6 +377,8 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
             Pop(x) => Pop(*x),
             Push(x) => Push(*x),
             Divine(x) => Divine(*x),
+            Pick(x) => Pick(*x),
+            Place(x) => Place(*x),
             Dup(x) => Dup(*x),
             Swap(x) => Swap(*x),
             Halt => Halt,
@@ -363,10 +389,12 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
             Recurse => Recurse,
             RecurseOrReturn => RecurseOrReturn,
             Assert => Assert,
+            AssertWithCode(x) => AssertWithCode(*x),
             ReadMem(x) => ReadMem(*x),
             WriteMem(x) => WriteMem(*x),
             Hash => Hash,
             AssertVector => AssertVector,
+            AssertVectorWithCode(x) => AssertVectorWithCode(*x),
             SpongeInit => SpongeInit,
             SpongeAbsorb => SpongeAbsorb,
             SpongeAbsorbMem => SpongeAbsorbMem,
@@ -401,6 +429,8 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
             Pop(n) => -(n.num_words() as i32),
             Push(_) => 1,
             Divine(n) => n.num_words() as i32,
+            Pick(_) => 0,
+            Place(_) => 0,
             Dup(_) => 1,
             Swap(_) => 0,
             Halt => 0,
@@ -410,11 +440,11 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
             Return => 0,
             Recurse => 0,
             RecurseOrReturn => 0,
-            Assert => -1,
+            Assert | AssertWithCode(_) => -1,
             ReadMem(n) => n.num_words() as i32,
             WriteMem(n) => -(n.num_words() as i32),
             Hash => -5,
-            AssertVector => -5,
+            AssertVector | AssertVectorWithCode(_) => -5,
             SpongeInit => 0,
             SpongeAbsorb => -10,
             SpongeAbsorbMem => 0,
@@ -444,13 +474,68 @@ impl<Dest: PartialEq + Default> AnInstruction<Dest> {
         }
     }
 
+    /// Whether executing the instruction increases the size of the op stack.
+    pub const fn grows_op_stack(&self) -> bool {
+        self.op_stack_size_influence() > 0
+    }
+
+    /// Whether executing the instruction decreases the size of the op stack.
+    pub const fn shrinks_op_stack(&self) -> bool {
+        self.op_stack_size_influence() < 0
+    }
+
+    /// How executing the instruction changes the size of the op stack.
+    pub const fn stack_effect(&self) -> StackEffect {
+        let influence = self.op_stack_size_influence();
+        match influence {
+            0 => StackEffect::Keep,
+            1.. => StackEffect::Grow(influence.unsigned_abs() as usize),
+            _ => StackEffect::Shrink(influence.unsigned_abs() as usize),
+        }
+    }
+
+    /// The number of clock cycles it takes to execute the instruction. Every instruction is
+    /// executed in exactly one clock cycle, corresponding to exactly one row in the Processor
+    /// Table. Work delegated to co-processors, like the Hash Table or the U32 Table, is not
+    /// accounted for.
+    pub const fn cycle_cost(&self) -> usize {
+        1
+    }
+
     /// Indicates whether the instruction operates on base field elements that are also u32s.
     pub fn is_u32_instruction(&self) -> bool {
         matches!(
             self,
-            Split | Lt | And | Xor | Log2Floor | Pow | DivMod | PopCount | MerkleStep
+            Split
+                | Lt
+                | And
+                | Xor
+                | Log2Floor
+                | Pow
+                | DivMod
+                | PopCount
+                | MerkleStep
+                | AssertWithCode(_)
+                | AssertVectorWithCode(_)
         )
     }
+
+    /// The co-processor tables the instruction links the Processor Table to, _i.e._, the
+    /// tables for which executing the instruction contributes to some cross-table argument.
+    ///
+    /// The Program Table, the Op Stack Table, and the Jump Stack Table are omitted: they are
+    /// linked to by (almost) every instruction.
+    pub fn coprocessor_links(&self) -> &'static [TableId] {
+        match self {
+            Hash | SpongeInit | SpongeAbsorb | SpongeSqueeze => &[TableId::Hash],
+            MerkleStep => &[TableId::Hash, TableId::U32],
+            SpongeAbsorbMem => &[TableId::Ram, TableId::Hash],
+            ReadMem(_) | WriteMem(_) | XxDotStep | XbDotStep => &[TableId::Ram],
+            Split | Lt | And | Xor | Log2Floor | Pow | DivMod | PopCount => &[TableId::U32],
+            AssertWithCode(_) | AssertVectorWithCode(_) => &[TableId::U32],
+            _ => &[],
+        }
+    }
 }
 
 impl<Dest: Display + PartialEq + Default> Display for AnInstruction<Dest> {
@@ -459,8 +544,10 @@ impl<Dest: Display + PartialEq + Default> Display for AnInstruction<Dest> {
         match self {
             Push(arg) => write!(f, " {arg}"),
             Pop(arg) | Divine(arg) => write!(f, " {arg}"),
+            Pick(arg) | Place(arg) => write!(f, " {arg}"),
             Dup(arg) | Swap(arg) => write!(f, " {arg}"),
             Call(arg) => write!(f, " {arg}"),
+            AssertWithCode(code) | AssertVectorWithCode(code) => write!(f, " {code}"),
             ReadMem(arg) | WriteMem(arg) => write!(f, " {arg}"),
             AddI(arg) => write!(f, " {arg}"),
             ReadIo(arg) | WriteIo(arg) => write!(f, " {arg}"),
@@ -474,7 +561,9 @@ impl Instruction {
     pub fn arg(&self) -> Option<BFieldElement> {
         match self {
             Push(arg) | Call(arg) => Some(*arg),
+            AssertWithCode(code) | AssertVectorWithCode(code) => Some(bfe!(*code)),
             Pop(arg) | Divine(arg) => Some(arg.into()),
+            Pick(arg) | Place(arg) => Some(arg.into()),
             Dup(arg) | Swap(arg) => Some(arg.into()),
             ReadMem(arg) | WriteMem(arg) => Some(arg.into()),
             AddI(arg) => Some(*arg),
@@ -483,20 +572,30 @@ impl Instruction {
         }
     }
 
+    /// Whether the instruction has an [argument](Self::arg).
+    pub fn has_arg(&self) -> bool {
+        self.arg().is_some()
+    }
+
     /// Change the argument of the instruction, if it has one. Returns an `Err` if the instruction
     /// does not have an argument or if the argument is out of range.
     pub fn change_arg(self, new_arg: BFieldElement) -> Result<Self> {
         let illegal_argument_error = InstructionError::IllegalArgument(self, new_arg);
         let num_words = new_arg.try_into().map_err(|_| illegal_argument_error);
         let op_stack_element = new_arg.try_into().map_err(|_| illegal_argument_error);
+        let error_code = new_arg.try_into().map_err(|_| illegal_argument_error);
 
         let new_instruction = match self {
             Pop(_) => Pop(num_words?),
             Push(_) => Push(new_arg),
             Divine(_) => Divine(num_words?),
+            Pick(_) => Pick(op_stack_element?),
+            Place(_) => Place(op_stack_element?),
             Dup(_) => Dup(op_stack_element?),
             Swap(_) => Swap(op_stack_element?),
             Call(_) => Call(new_arg),
+            AssertWithCode(_) => AssertWithCode(error_code?),
+            AssertVectorWithCode(_) => AssertVectorWithCode(error_code?),
             ReadMem(_) => ReadMem(num_words?),
             WriteMem(_) => WriteMem(num_words?),
             AddI(_) => AddI(new_arg),
@@ -509,13 +608,78 @@ impl Instruction {
     }
 }
 
+impl Instruction {
+    /// Every instruction, once for every legal argument that influences the op stack, and once
+    /// with its default argument otherwise.
+    pub(crate) fn all_with_op_stack_influencing_args() -> Vec<Self> {
+        let all_number_of_words_args = |instruction: Self| {
+            NumberOfWords::iter()
+                .map(move |n| instruction.change_arg(n.into()).unwrap())
+                .collect_vec()
+        };
+        ALL_INSTRUCTIONS
+            .into_iter()
+            .flat_map(|instruction| match instruction {
+                Pop(_) | Divine(_) | ReadMem(_) | WriteMem(_) | ReadIo(_) | WriteIo(_) => {
+                    all_number_of_words_args(instruction)
+                }
+                _ => vec![instruction],
+            })
+            .collect()
+    }
+
+    /// Cross-check the op-stack related metadata of every instruction, for every legal argument
+    /// that influences the op stack, against the instruction's opcode. In particular,
+    /// [shrinking](Self::shrinks_op_stack) the op stack must agree with
+    /// [instruction bit](InstructionBit) 1.
+    ///
+    /// Checking the metadata against the instructions' transition constraints is done by the
+    /// [processor table][invariants].
+    ///
+    /// Guards against drift between the instructions' metadata. Returns all instructions for which
+    /// any of the checks fails.
+    ///
+    /// [invariants]: crate::table::processor_table::ExtProcessorTable::verify_op_stack_invariants
+    pub fn verify_op_stack_metadata() -> result::Result<(), Vec<Self>> {
+        let inconsistent_instructions = Self::all_with_op_stack_influencing_args()
+            .into_iter()
+            .filter(|instruction| !instruction.op_stack_metadata_is_consistent())
+            .collect_vec();
+
+        match inconsistent_instructions.is_empty() {
+            true => Ok(()),
+            false => Err(inconsistent_instructions),
+        }
+    }
+
+    pub(crate) fn op_stack_metadata_is_consistent(&self) -> bool {
+        self.shrinks_op_stack() == self.ib(IB1).is_one()
+    }
+}
+
+/// Instructions are ordered by their [opcode](AnInstruction::opcode) first and their
+/// [argument](Instruction::arg), if any, second.
+impl PartialOrd for Instruction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Instruction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |instruction: &Self| (instruction.opcode(), instruction.arg().map(|a| a.value()));
+        key(self).cmp(&key(other))
+    }
+}
+
 impl TryFrom<u32> for Instruction {
     type Error = InstructionError;
 
     fn try_from(opcode: u32) -> Result<Self> {
-        OPCODE_TO_INSTRUCTION_MAP
-            .get(&opcode)
+        OPCODE_TO_INSTRUCTION
+            .get(opcode as usize)
             .copied()
+            .flatten()
             .ok_or(InstructionError::InvalidOpcode(opcode))
     }
 }
@@ -564,10 +728,12 @@ const fn all_instructions_with_default_args() -> [AnInstruction<BFieldElement>;
         Recurse,
         RecurseOrReturn,
         Assert,
+        AssertWithCode(0),
         ReadMem(N1),
         WriteMem(N1),
         Hash,
         AssertVector,
+        AssertVectorWithCode(0),
         SpongeInit,
         SpongeAbsorb,
         SpongeAbsorbMem,
@@ -594,6 +760,8 @@ const fn all_instructions_with_default_args() -> [AnInstruction<BFieldElement>;
         MerkleStep,
         XxDotStep,
         XbDotStep,
+        Pick(ST0),
+        Place(ST0),
     ]
 }
 
@@ -607,6 +775,31 @@ const fn all_instruction_names() -> [&'static str; Instruction::COUNT] {
     names
 }
 
+const fn opcode_to_instruction() -> [Option<Instruction>; NUM_OPCODES] {
+    let mut instructions = [None; NUM_OPCODES];
+    let mut i = 0;
+    while i < Instruction::COUNT {
+        let instruction = ALL_INSTRUCTIONS[i];
+        instructions[instruction.opcode() as usize] = Some(instruction);
+        i += 1;
+    }
+    instructions
+}
+
+/// How executing an [`Instruction`] changes the size of the op stack. See
+/// [`AnInstruction::stack_effect`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum StackEffect {
+    /// The op stack grows by the given number of elements.
+    Grow(usize),
+
+    /// The op stack shrinks by the given number of elements.
+    Shrink(usize),
+
+    /// The size of the op stack does not change.
+    Keep,
+}
+
 /// Indicators for all the possible bits in an [`Instruction`].
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, EnumCount, EnumIter)]
 pub enum InstructionBit {
@@ -792,7 +985,7 @@ pub mod tests {
     impl InstructionBucket {
         pub fn contains(self, instruction: Instruction) -> bool {
             match self {
-                InstructionBucket::HasArg => instruction.arg().is_some(),
+                InstructionBucket::HasArg => instruction.has_arg(),
                 InstructionBucket::ShrinksStack => instruction.op_stack_size_influence() < 0,
                 InstructionBucket::IsU32 => instruction.is_u32_instruction(),
             }
@@ -903,11 +1096,20 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn decoding_opcodes_agrees_with_opcode_to_instruction_map() {
+        for opcode in 0..2 * NUM_OPCODES as u32 {
+            let instruction = Instruction::try_from(opcode).ok();
+            assert!(OPCODE_TO_INSTRUCTION_MAP.get(&opcode).copied() == instruction);
+        }
+    }
+
     #[test]
     /// Serves no other purpose than to increase code coverage results.
     fn run_constant_methods() {
         all_instructions_with_default_args();
         all_instruction_names();
+        opcode_to_instruction();
     }
 
     #[test]
@@ -927,6 +1129,11 @@ pub mod tests {
         assert!(Swap(ST0).change_arg(bfe!(1)).is_ok());
         assert!(Pop(N4).change_arg(bfe!(0)).is_err());
         assert!(Pop(N1).change_arg(bfe!(2)).is_ok());
+        assert!(Assert.change_arg(bfe!(7)).is_err());
+        assert!(AssertWithCode(0).change_arg(bfe!(7)).is_ok());
+        assert!(AssertVectorWithCode(0)
+            .change_arg(bfe!(1_u64 << 32))
+            .is_err());
         assert!(Nop.change_arg(bfe!(7)).is_err());
     }
 
@@ -934,10 +1141,36 @@ pub mod tests {
     fn print_various_instructions() {
         println!("instruction_push: {:?}", Instruction::Push(bfe!(7)));
         println!("instruction_assert: {}", Instruction::Assert);
+        println!(
+            "instruction_assert_with_code: {}",
+            Instruction::AssertWithCode(7)
+        );
         println!("instruction_invert: {:?}", Instruction::Invert);
         println!("instruction_dup: {}", Instruction::Dup(ST14));
     }
 
+    #[test]
+    fn instructions_link_to_expected_coprocessors() {
+        assert!(Instruction::DivMod
+            .coprocessor_links()
+            .contains(&TableId::U32));
+        assert!(Instruction::Hash
+            .coprocessor_links()
+            .contains(&TableId::Hash));
+        assert!(Instruction::ReadMem(N1)
+            .coprocessor_links()
+            .contains(&TableId::Ram));
+        assert!(Instruction::Nop.coprocessor_links().is_empty());
+    }
+
+    #[test]
+    fn instruction_links_to_u32_table_if_and_only_if_it_is_a_u32_instruction() {
+        for instruction in ALL_INSTRUCTIONS {
+            let links_to_u32_table = instruction.coprocessor_links().contains(&TableId::U32);
+            assert!(instruction.is_u32_instruction() == links_to_u32_table);
+        }
+    }
+
     #[test]
     fn instruction_size_is_consistent_with_having_arguments() {
         for instruction in Instruction::iter() {
@@ -948,13 +1181,45 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn argument_of_instruction_can_be_extracted() {
+        assert!(Some(bfe!(5)) == Push(bfe!(5)).arg());
+        assert!(Push(bfe!(5)).has_arg());
+        assert!(None == Add.arg());
+        assert!(!Add.has_arg());
+    }
+
+    #[test]
+    fn instructions_with_argument_advance_instruction_pointer_by_two() {
+        let instructions = [
+            Push(bfe!(5)),
+            ReadMem(NumberOfWords::N1),
+            Dup(OpStackElement::ST0),
+            Swap(OpStackElement::ST1),
+            AddI(bfe!(3)),
+            Nop,
+            Split,
+        ];
+        for instruction in instructions {
+            let program = triton_program!({instruction} halt);
+            let mut vm_state = VMState::new(&program, [].into(), [].into());
+            vm_state.step().unwrap();
+
+            let expected_instruction_pointer = match instruction.has_arg() {
+                true => 2,
+                false => 1,
+            };
+            assert!(expected_instruction_pointer == vm_state.instruction_pointer);
+        }
+    }
+
     #[test]
     fn opcodes_are_consistent_with_argument_indication_bit() {
         let argument_indicator_bit_mask = 1;
         for instruction in Instruction::iter() {
             let opcode = instruction.opcode();
             println!("Testing instruction {instruction} with opcode {opcode}.");
-            let has_arg = instruction.arg().is_some();
+            let has_arg = instruction.has_arg();
             assert!(has_arg == (opcode & argument_indicator_bit_mask != 0));
         }
     }
@@ -970,6 +1235,11 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn op_stack_metadata_is_consistent_for_all_instructions() {
+        assert!(let Ok(()) = Instruction::verify_op_stack_metadata());
+    }
+
     #[test]
     fn opcodes_are_consistent_with_u32_indication_bit() {
         let u32_indicator_bit_mask = 4;
@@ -148,7 +148,7 @@
 //! # use triton_vm::prelude::*;
 //! let crashing_program = triton_program!(push 2 assert halt);
 //! let vm_error = crashing_program.run([].into(), [].into()).unwrap_err();
-//! assert!(matches!(vm_error.source, InstructionError::AssertionFailed));
+//! assert!(matches!(vm_error.source, InstructionError::AssertionFailed { .. }));
 //! // inspect the VM state
 //! eprintln!("{vm_error}");
 //! ```
@@ -401,6 +401,8 @@ macro_rules! triton_asm {
     [pop $arg:literal; $num:expr] => { vec![ $crate::triton_instr!(pop $arg); $num ] };
     [push $arg:literal; $num:expr] => { vec![ $crate::triton_instr!(push $arg); $num ] };
     [divine $arg:literal; $num:expr] => { vec![ $crate::triton_instr!(divine $arg); $num ] };
+    [pick $arg:literal; $num:expr] => { vec![ $crate::triton_instr!(pick $arg); $num ] };
+    [place $arg:literal; $num:expr] => { vec![ $crate::triton_instr!(place $arg); $num ] };
     [dup $arg:literal; $num:expr] => { vec![ $crate::triton_instr!(dup $arg); $num ] };
     [swap $arg:literal; $num:expr] => { vec![ $crate::triton_instr!(swap $arg); $num ] };
     [call $arg:ident; $num:expr] => { vec![ $crate::triton_instr!(call $arg); $num ] };
@@ -449,6 +451,16 @@ macro_rules! triton_instr {
         let instruction = $crate::instruction::AnInstruction::<String>::Divine(argument);
         $crate::instruction::LabelledInstruction::Instruction(instruction)
     }};
+    (pick $arg:literal) => {{
+        let argument = $crate::op_stack::OpStackElement::try_from($arg).unwrap();
+        let instruction = $crate::instruction::AnInstruction::<String>::Pick(argument);
+        $crate::instruction::LabelledInstruction::Instruction(instruction)
+    }};
+    (place $arg:literal) => {{
+        let argument = $crate::op_stack::OpStackElement::try_from($arg).unwrap();
+        let instruction = $crate::instruction::AnInstruction::<String>::Place(argument);
+        $crate::instruction::LabelledInstruction::Instruction(instruction)
+    }};
     (dup $arg:literal) => {{
         let argument = $crate::op_stack::OpStackElement::try_from($arg).unwrap();
         let instruction = $crate::instruction::AnInstruction::<String>::Dup(argument);
@@ -548,6 +560,37 @@ pub fn prove_program(
     Ok((stark, claim, proof))
 }
 
+/// Like [`prove_program`], but hands the program's public output to `check_output` before the
+/// [`Claim`] is finalized. If `check_output` returns an error, proof generation is aborted and
+/// that error is returned. This allows, for example, validating the public output against an
+/// externally computed commitment.
+///
+/// Since the claim must reflect the program's actual output, the public output can only be
+/// inspected, not altered – a claim with a modified output could not be proven.
+pub fn prove_program_with_output_check<F, E>(
+    program: &Program,
+    public_input: PublicInput,
+    non_determinism: NonDeterminism,
+    check_output: F,
+) -> Result<(Stark, Claim, Proof), E>
+where
+    F: FnOnce(&[BFieldElement]) -> Result<(), E>,
+    E: From<ProvingError>,
+{
+    let (aet, public_output) = program
+        .trace_execution(public_input.clone(), non_determinism)
+        .map_err(ProvingError::from)?;
+    check_output(&public_output)?;
+
+    let claim = Claim::about_program(program)
+        .with_input(public_input.individual_tokens)
+        .with_output(public_output);
+    let stark = Stark::default();
+    let proof = stark.prove(&claim, &aet)?;
+
+    Ok((stark, claim, proof))
+}
+
 /// A convenience function for proving a [`Claim`] and the program that claim corresponds to.
 /// Method [`prove_program`] gives a simpler interface with less control.
 pub fn prove(
@@ -630,12 +673,17 @@ mod tests {
         implements_auto_traits::<error::InstructionError>();
         implements_auto_traits::<error::ArithmeticDomainError>();
         implements_auto_traits::<error::ProofStreamError>();
+        implements_auto_traits::<error::StarkParameterError>();
+        implements_auto_traits::<error::NumRandomizersError>();
         implements_auto_traits::<error::FriSetupError>();
         implements_auto_traits::<error::FriProvingError>();
         implements_auto_traits::<error::FriValidationError>();
         implements_auto_traits::<error::ProgramDecodingError>();
+        implements_auto_traits::<error::ResumptionError>();
         implements_auto_traits::<error::ProvingError>();
         implements_auto_traits::<error::VerificationError>();
+        implements_auto_traits::<error::ChallengeError>();
+        implements_auto_traits::<error::TraceError>();
         implements_auto_traits::<error::OpStackElementError>();
         implements_auto_traits::<error::NumberOfWordsError>();
 
@@ -669,6 +717,7 @@ mod tests {
         implements_auto_traits::<table::op_stack_table::OpStackTableEntry>();
         implements_auto_traits::<table::processor_table::ProcessorTable>();
         implements_auto_traits::<table::processor_table::ExtProcessorTable>();
+        implements_auto_traits::<table::ConstraintCounts>();
         implements_auto_traits::<table::program_table::ProgramTable>();
         implements_auto_traits::<table::program_table::ExtProgramTable>();
         implements_auto_traits::<table::ram_table::RamTableCall>();
@@ -705,20 +754,29 @@ mod tests {
         implements_auto_traits::<TypeHint>();
         implements_auto_traits::<instruction::AnInstruction<usize>>();
         implements_auto_traits::<instruction::InstructionBit>();
+        implements_auto_traits::<instruction::StackEffect>();
         implements_auto_traits::<op_stack::OpStack>();
         implements_auto_traits::<op_stack::UnderflowIO>();
         implements_auto_traits::<op_stack::OpStackElement>();
         implements_auto_traits::<op_stack::NumberOfWords>();
         implements_auto_traits::<parser::ParseError>();
         implements_auto_traits::<parser::InstructionToken>();
+        implements_auto_traits::<parser::SourceLocation>();
         implements_auto_traits::<profiler::VMPerformanceProfile>();
+        implements_auto_traits::<program::Coverage>();
         implements_auto_traits::<program::ExecutionTraceProfile>();
         implements_auto_traits::<program::InstructionIter>();
+        implements_auto_traits::<program::NonDeterminismBuilder>();
         implements_auto_traits::<program::ProfileLine>();
         implements_auto_traits::<program::VMTableHeights>();
+        implements_auto_traits::<proof_item::CompressedAuthenticationStructure>();
+        implements_auto_traits::<proof_item::RepeatedDigest>();
         implements_auto_traits::<proof_item::FriResponse>();
         implements_auto_traits::<proof_item::ProofItem>();
+        implements_auto_traits::<proof_stream::FiatShamirCheckpoint>();
         implements_auto_traits::<proof_stream::ProofStream>();
+        implements_auto_traits::<stark::ProvingHints>();
+        implements_auto_traits::<stark::TraceReport>();
         implements_auto_traits::<vm::CoProcessorCall>();
     }
 
@@ -777,6 +835,48 @@ mod tests {
         assert!(verdict);
     }
 
+    #[test]
+    fn output_check_accepting_the_output_gives_verifiable_proof() {
+        let program = triton_program! { read_io 1 push 2 mul write_io 1 halt };
+        let check = |output: &[BFieldElement]| match output {
+            [x] if x.value() % 2 == 0 => Ok(()),
+            _ => Err(ProvingError::PublicOutputMismatch),
+        };
+
+        let (stark, claim, proof) =
+            prove_program_with_output_check(&program, bfe_vec![21].into(), [].into(), check)
+                .unwrap();
+        assert!(bfe_vec![42] == claim.output);
+        assert!(verify(stark, &claim, &proof));
+    }
+
+    #[test]
+    fn output_check_rejecting_the_output_aborts_proving_with_callers_error() {
+        #[derive(Debug, PartialEq, Eq)]
+        enum CommitmentError {
+            Rejected(Vec<BFieldElement>),
+            Proving(ProvingError),
+        }
+
+        impl From<ProvingError> for CommitmentError {
+            fn from(err: ProvingError) -> Self {
+                Self::Proving(err)
+            }
+        }
+
+        let program = triton_program! { read_io 1 push 2 mul write_io 1 halt };
+        let check = |output: &[BFieldElement]| match output {
+            [x] if *x == bfe!(42) => Err(CommitmentError::Rejected(output.to_vec())),
+            _ => Ok(()),
+        };
+
+        let public_input = bfe_vec![21].into();
+        let_assert!(
+            Err(err) = prove_program_with_output_check(&program, public_input, [].into(), check)
+        );
+        assert!(CommitmentError::Rejected(bfe_vec![42]) == err);
+    }
+
     #[test]
     fn lib_prove_verify() {
         let program = triton_program!(push 1 assert halt);
@@ -840,6 +940,24 @@ mod tests {
         triton_instr!(pop 0);
     }
 
+    #[test]
+    #[should_panic(expected = "IndexOutOfBounds(16)")]
+    fn parsing_dup_with_illegal_argument_fails() {
+        triton_instr!(dup 16);
+    }
+
+    #[test]
+    #[should_panic(expected = "IndexOutOfBounds(16)")]
+    fn parsing_swap_with_illegal_argument_fails() {
+        triton_instr!(swap 16);
+    }
+
+    #[test]
+    #[should_panic(expected = "expecting label, instruction or eof")]
+    fn building_program_with_illegal_dup_argument_fails() {
+        triton_program!(push 1 dup 16 halt);
+    }
+
     #[test]
     fn triton_asm_macro_can_parse_type_hints() {
         let instructions = triton_asm!(
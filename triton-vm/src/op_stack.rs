@@ -307,6 +307,13 @@ pub enum OpStackElement {
 }
 
 impl OpStackElement {
+    /// All op-stack elements, in order of their [index](Self::index).
+    pub const fn all() -> [Self; Self::COUNT] {
+        [
+            ST0, ST1, ST2, ST3, ST4, ST5, ST6, ST7, ST8, ST9, ST10, ST11, ST12, ST13, ST14, ST15,
+        ]
+    }
+
     pub const fn index(self) -> u32 {
         match self {
             ST0 => 0,
@@ -650,6 +657,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn all_op_stack_elements_are_in_order_of_their_index() {
+        assert!(OpStackElement::iter().collect_vec() == OpStackElement::all().to_vec());
+        for (index, stack_element) in OpStackElement::all().into_iter().enumerate() {
+            assert!(index == usize::from(stack_element));
+        }
+    }
+
     #[test]
     fn sanity() {
         let mut op_stack = OpStack::default();
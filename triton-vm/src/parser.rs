@@ -4,7 +4,9 @@ use std::error::Error;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::ops::Range;
 
+use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::*;
 use nom::character::complete::digit1;
@@ -13,6 +15,7 @@ use nom::error::*;
 use nom::multi::*;
 use nom::Finish;
 use nom::IResult;
+use nom::Offset;
 use twenty_first::prelude::BFieldElement;
 
 use crate::instruction::AnInstruction::*;
@@ -30,6 +33,22 @@ pub struct ParseError<'a> {
     pub errors: VerboseError<&'a str>,
 }
 
+/// The location of the token that caused a [`ParseError`] in the parsed source code.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SourceLocation<'a> {
+    /// The byte range of the offending token within the source code.
+    pub span: Range<usize>,
+
+    /// The line of the offending token. Starts at 1.
+    pub line: usize,
+
+    /// The column of the offending token, counted in characters. Starts at 1.
+    pub column: usize,
+
+    /// The entire line of source code containing the offending token.
+    pub snippet: &'a str,
+}
+
 /// An intermediate object for the parsing / compilation pipeline. You probably want
 /// [`LabelledInstruction`].
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -48,6 +67,59 @@ impl<'a> Display for ParseError<'a> {
 
 impl<'a> Error for ParseError<'a> {}
 
+impl<'a> ParseError<'a> {
+    /// The location of the token that caused the parse error. If there are multiple such
+    /// tokens, for example because multiple labels are missing, the location of the first one
+    /// is returned.
+    pub fn location(&self) -> SourceLocation<'a> {
+        let (error_s, _) = self.errors.errors[0];
+        self.source_location(error_s)
+    }
+
+    /// The locations of all tokens that caused the parse error, one per reported error.
+    /// Errors reported at the same position are only listed once.
+    pub fn locations(&self) -> Vec<SourceLocation<'a>> {
+        self.errors
+            .errors
+            .iter()
+            .map(|&(error_s, _)| self.source_location(error_s))
+            .fold(vec![], |mut locations, location| {
+                if !locations.contains(&location) {
+                    locations.push(location);
+                }
+                locations
+            })
+    }
+
+    fn source_location(&self, error_s: &'a str) -> SourceLocation<'a> {
+        let start = self.input.offset(error_s);
+        let is_delimiter = |c: char| c.is_whitespace() || c == ':' || c == ']';
+        let token_len = error_s
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| is_delimiter(c))
+            .map_or(error_s.len(), |(idx, _)| idx);
+        let span = start..start + token_len;
+
+        let preceding_input = &self.input[..start];
+        let line_start = preceding_input.rfind('\n').map_or(0, |idx| idx + 1);
+        let line = preceding_input.matches('\n').count() + 1;
+        let column = preceding_input[line_start..].chars().count() + 1;
+
+        let line_end = error_s
+            .find('\n')
+            .map_or(self.input.len(), |idx| start + idx);
+        let snippet = self.input[line_start..line_end].trim_end_matches('\r');
+
+        SourceLocation {
+            span,
+            line,
+            column,
+            snippet,
+        }
+    }
+}
+
 impl<'a> InstructionToken<'a> {
     pub fn token_str(&self) -> &'a str {
         match self {
@@ -139,12 +211,15 @@ fn ensure_no_missing_or_duplicate_labels<'a>(
 fn identify_missing_labels<'a>(
     instructions: &[InstructionToken<'a>],
     seen_labels: HashMap<&str, InstructionToken>,
-) -> HashSet<InstructionToken<'a>> {
+) -> HashSet<&'a str> {
     let mut missing_labels = HashSet::default();
     for instruction in instructions {
-        if let InstructionToken::Instruction(Call(label), _) = instruction {
+        if let InstructionToken::Instruction(Call(label), token_s) = instruction {
             if !seen_labels.contains_key(label.as_str()) {
-                missing_labels.insert(instruction.to_owned());
+                // point at the called label, not at `call` or `[`
+                let prefix_len = if token_s.starts_with("call") { 4 } else { 1 };
+                let label_offset = token_s[prefix_len..].find(label.as_str()).unwrap();
+                missing_labels.insert(&token_s[prefix_len + label_offset..]);
             }
         }
     }
@@ -153,11 +228,12 @@ fn identify_missing_labels<'a>(
 
 fn errors_for_duplicate_and_missing_labels<'a>(
     duplicate_labels: HashSet<InstructionToken<'a>>,
-    missing_labels: HashSet<InstructionToken<'a>>,
+    missing_labels: HashSet<&'a str>,
 ) -> VerboseError<&'a str> {
     let duplicate_label_error_context = VerboseErrorKind::Context("duplicate label");
     let missing_label_error_context = VerboseErrorKind::Context("missing label");
 
+    let duplicate_labels = duplicate_labels.iter().map(|label| label.token_str());
     let duplicate_label_errors =
         errors_for_labels_with_context(duplicate_labels, duplicate_label_error_context);
     let missing_label_errors =
@@ -167,13 +243,17 @@ fn errors_for_duplicate_and_missing_labels<'a>(
     VerboseError { errors }
 }
 
-fn errors_for_labels_with_context(
-    labels: HashSet<InstructionToken>,
+/// Errors are sorted by their position in the source code to make error reporting
+/// deterministic. Every label is a suffix of the source code, starting at the label's
+/// position. Hence, the longer the label's string, the earlier the label's position.
+fn errors_for_labels_with_context<'a>(
+    labels: impl IntoIterator<Item = &'a str>,
     context: VerboseErrorKind,
-) -> Vec<(&str, VerboseErrorKind)> {
+) -> Vec<(&'a str, VerboseErrorKind)> {
     labels
         .into_iter()
-        .map(|label| (label.token_str(), context.clone()))
+        .sorted_by_key(|label_s| std::cmp::Reverse(label_s.len()))
+        .map(|label_s| (label_s, context.clone()))
         .collect()
 }
 
@@ -219,10 +299,12 @@ fn an_instruction(s: &str) -> ParseResult<AnInstruction<String>> {
     let pop = pop_instruction();
     let push = push_instruction();
     let divine = divine_instruction();
+    let pick = pick_instruction();
+    let place = place_instruction();
     let dup = dup_instruction();
     let swap = swap_instruction();
 
-    let opstack_manipulation = alt((pop, push, divine, dup, swap));
+    let opstack_manipulation = alt((pop, push, divine, pick, place, dup, swap));
 
     // Control flow
     let halt = instruction("halt", Halt);
@@ -232,7 +314,7 @@ fn an_instruction(s: &str) -> ParseResult<AnInstruction<String>> {
     let return_ = instruction("return", Return);
     let recurse = instruction("recurse", Recurse);
     let recurse_or_return = instruction("recurse_or_return", RecurseOrReturn);
-    let assert = instruction("assert", Assert);
+    let assert = assert_instruction();
 
     let control_flow = alt((nop, skiz, call, return_, halt));
 
@@ -244,7 +326,7 @@ fn an_instruction(s: &str) -> ParseResult<AnInstruction<String>> {
 
     // Hashing-related instructions
     let hash = instruction("hash", Hash);
-    let assert_vector = instruction("assert_vector", AssertVector);
+    let assert_vector = assert_vector_instruction();
     let sponge_init = instruction("sponge_init", SpongeInit);
     let sponge_absorb = instruction("sponge_absorb", SpongeAbsorb);
     let sponge_absorb_mem = instruction("sponge_absorb_mem", SpongeAbsorbMem);
@@ -367,6 +449,22 @@ fn divine_instruction() -> impl Fn(&str) -> ParseResult<AnInstruction<String>> {
     }
 }
 
+fn pick_instruction() -> impl Fn(&str) -> ParseResult<AnInstruction<String>> {
+    move |s: &str| {
+        let (s, _) = token1("pick")(s)?;
+        let (s, stack_register) = stack_register(s)?;
+        Ok((s, Pick(stack_register)))
+    }
+}
+
+fn place_instruction() -> impl Fn(&str) -> ParseResult<AnInstruction<String>> {
+    move |s: &str| {
+        let (s, _) = token1("place")(s)?;
+        let (s, stack_register) = stack_register(s)?;
+        Ok((s, Place(stack_register)))
+    }
+}
+
 fn dup_instruction() -> impl Fn(&str) -> ParseResult<AnInstruction<String>> {
     move |s: &str| {
         let (s, _) = token1("dup")(s)?; // require space before argument
@@ -414,6 +512,24 @@ fn call_instruction<'a>() -> impl Fn(&'a str) -> ParseResult<AnInstruction<Strin
     }
 }
 
+fn assert_instruction() -> impl Fn(&str) -> ParseResult<AnInstruction<String>> {
+    move |s: &str| {
+        let (s, _) = token1("assert")(s)?;
+        let (s, code) = opt(error_code)(s)?;
+        let instruction = code.map_or(Assert, AssertWithCode);
+        Ok((s, instruction))
+    }
+}
+
+fn assert_vector_instruction() -> impl Fn(&str) -> ParseResult<AnInstruction<String>> {
+    move |s: &str| {
+        let (s, _) = token1("assert_vector")(s)?;
+        let (s, code) = opt(error_code)(s)?;
+        let instruction = code.map_or(AssertVector, AssertVectorWithCode);
+        Ok((s, instruction))
+    }
+}
+
 fn read_mem_instruction() -> impl Fn(&str) -> ParseResult<AnInstruction<String>> {
     move |s: &str| {
         let (s, _) = token1("read_mem")(s)?;
@@ -468,6 +584,17 @@ fn field_element(s_orig: &str) -> ParseResult<BFieldElement> {
     Ok((s, BFieldElement::new(n as u64)))
 }
 
+/// Parse the optional error code of instructions `assert` and `assert_vector`.
+fn error_code(s_orig: &str) -> ParseResult<u32> {
+    let (s, n) = digit1(s_orig)?;
+    let Ok(code) = n.parse() else {
+        return cut(context("out-of-bounds error code (must fit in u32)", fail))(s_orig);
+    };
+    let (s, _) = comment_or_whitespace1(s)?;
+
+    Ok((s, code))
+}
+
 fn stack_register(s: &str) -> ParseResult<OpStackElement> {
     let (s, n) = digit1(s)?;
     let stack_register = match n {
@@ -905,6 +1032,61 @@ pub(crate) mod tests {
         });
     }
 
+    #[test]
+    fn parse_program_assert_error_codes() {
+        parse_program_prop(TestCase {
+            input: "assert 7 assert_vector 42 assert",
+            expected: Program::new(&[
+                Instruction(AssertWithCode(7)),
+                Instruction(AssertVectorWithCode(42)),
+                Instruction(Assert),
+            ]),
+            message: "error codes of assertions are optional",
+        });
+
+        parse_program_neg_prop(NegativeTestCase {
+            input: "assert 4294967296",
+            expected_error: "out-of-bounds error code",
+            expected_error_count: 1,
+            message: "error codes must fit in a u32",
+        });
+    }
+
+    #[test]
+    fn parse_error_locates_unknown_mnemonic() {
+        let source_code = "push 1\n  pop 1 foo 2\nhalt";
+        let_assert!(Err(error) = Program::from_code(source_code));
+
+        let location = error.location();
+        assert!((15..18) == location.span);
+        assert!("foo" == &source_code[location.span.clone()]);
+        assert!(2 == location.line);
+        assert!(9 == location.column);
+        assert!("  pop 1 foo 2" == location.snippet);
+    }
+
+    #[test]
+    fn parse_error_locates_missing_labels() {
+        let source_code = "foo: pop 1\ncall bar\n  call foo call baz\nhalt";
+        let_assert!(Err(error) = Program::from_code(source_code));
+
+        let location = error.location();
+        assert!("bar" == &source_code[location.span.clone()]);
+        assert!(2 == location.line);
+        assert!(6 == location.column);
+        assert!("call bar" == location.snippet);
+
+        let locations = error.locations();
+        assert!(2 == locations.len());
+        assert!("baz" == &source_code[locations[1].span.clone()]);
+        assert!(3 == locations[1].line);
+        assert!(17 == locations[1].column);
+        assert!("  call foo call baz" == locations[1].snippet);
+
+        let_assert!(Err(error) = Program::from_code("foo: [bar]"));
+        assert!((6..9) == error.location().span);
+    }
+
     #[test]
     fn parse_program_label() {
         parse_program_prop(TestCase {
@@ -1259,6 +1441,15 @@ pub(crate) mod tests {
         assert_eq!(Instruction(Return), instructions[1]);
     }
 
+    #[test]
+    fn triton_asm_macro_with_asserts_with_error_codes() {
+        let instructions = triton_asm!(assert 7 assert_vector 42 assert_vector assert);
+        assert_eq!(Instruction(AssertWithCode(7)), instructions[0]);
+        assert_eq!(Instruction(AssertVectorWithCode(42)), instructions[1]);
+        assert_eq!(Instruction(AssertVector), instructions[2]);
+        assert_eq!(Instruction(Assert), instructions[3]);
+    }
+
     #[test]
     fn triton_program_macro() {
         let program = triton_program!(
@@ -1,14 +1,18 @@
 use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::fs;
 use std::hash::Hash;
 use std::io::Cursor;
 use std::ops::Add;
 use std::ops::AddAssign;
 use std::ops::Sub;
+use std::path::Path;
 
 use arbitrary::Arbitrary;
 use get_size::GetSize;
@@ -19,16 +23,22 @@ use twenty_first::prelude::*;
 
 use crate::aet::AlgebraicExecutionTrace;
 use crate::error::ProgramDecodingError;
+use crate::error::ProgramLoadingError;
+use crate::error::ResumptionError;
 use crate::error::VMError;
 use crate::instruction::AnInstruction;
 use crate::instruction::Instruction;
 use crate::instruction::LabelledInstruction;
 use crate::instruction::TypeHint;
+use crate::op_stack::NumberOfWords;
 use crate::parser::parse;
 use crate::parser::to_labelled_instructions;
 use crate::parser::ParseError;
 use crate::profiler::profiler;
 use crate::table::hash_table::PERMUTATION_TRACE_LENGTH;
+use crate::table::master_table::TableId;
+use crate::table::table_column::MasterBaseTableColumn;
+use crate::table::table_column::ProcessorBaseTableColumn;
 use crate::table::u32_table::U32TableEntry;
 use crate::vm::CoProcessorCall;
 use crate::vm::VMState;
@@ -99,7 +109,7 @@ impl BFieldCodec for Program {
             let opcode = sequence[read_idx];
             let mut instruction = Instruction::try_from(opcode)
                 .map_err(|err| Self::Error::InvalidInstruction(read_idx, err))?;
-            let instruction_has_arg = instruction.arg().is_some();
+            let instruction_has_arg = instruction.has_arg();
             if instruction_has_arg && instructions.len() + instruction.size() > program_length {
                 return Err(Self::Error::MissingArgument(read_idx, instruction));
             }
@@ -121,12 +131,14 @@ impl BFieldCodec for Program {
             return Err(Self::Error::LengthMismatch);
         }
 
-        Ok(Box::new(Program {
+        let program = Program {
             instructions,
             address_to_label: HashMap::default(),
             breakpoints: vec![],
             type_hints: HashMap::default(),
-        }))
+        };
+        program.validate()?;
+        Ok(Box::new(program))
     }
 
     fn encode(&self) -> Vec<BFieldElement> {
@@ -316,7 +328,29 @@ impl Program {
         (breakpoints, type_hints)
     }
 
-    /// Create a `Program` by parsing source code.
+    /// Check that the program's words parse cleanly. In particular, a word that follows an
+    /// instruction of size one, like `halt`, must not be consumed as an argument, and the
+    /// argument of an instruction of size two must belong to that instruction.
+    ///
+    /// Programs constructed through [`Program::new`] or [decoding](BFieldCodec::decode) are always
+    /// valid. Other means of construction, like deserialization, might not be.
+    pub fn validate(&self) -> std::result::Result<(), ProgramDecodingError> {
+        let mut index = 0;
+        while let Some(&instruction) = self.instructions.get(index) {
+            let argument_range = index + 1..index + instruction.size();
+            let Some(argument_slots) = self.instructions.get(argument_range) else {
+                return Err(ProgramDecodingError::MissingArgument(index, instruction));
+            };
+            if argument_slots.iter().any(|&slot| slot != instruction) {
+                return Err(ProgramDecodingError::MalformedArgument(index, instruction));
+            }
+            index += instruction.size();
+        }
+        Ok(())
+    }
+
+    /// Create a `Program` by parsing source code. On failure, the returned [`ParseError`]
+    /// can be [located](ParseError::location) in the source code.
     pub fn from_code(code: &str) -> std::result::Result<Self, ParseError> {
         parse(code)
             .map(|tokens| to_labelled_instructions(&tokens))
@@ -375,6 +409,193 @@ impl Program {
             .collect()
     }
 
+    /// The static call graph of the program. Every [`call`](Instruction::Call) target starts a
+    /// routine, which extends up to the next call target. Instructions before the first call
+    /// target belong to the routine at the program's entry point.
+    pub fn call_graph(&self) -> CallGraph {
+        let call_targets = self.call_targets();
+        let mut call_graph = CallGraph::default();
+        let mut current_routine = self.label_for_address(0);
+        call_graph.routines.insert(current_routine.clone());
+
+        let mut address = 0;
+        while let Some(instruction) = self.instructions.get(address) {
+            let address_u64 = u64::try_from(address).unwrap();
+            if call_targets.contains(&address_u64) {
+                current_routine = self.label_for_address(address_u64);
+                call_graph.routines.insert(current_routine.clone());
+            }
+            if let Instruction::Call(target) = instruction {
+                let callee = self.label_for_address(target.value());
+                call_graph.edges.insert((current_routine.clone(), callee));
+            }
+            address += instruction.size();
+        }
+
+        call_graph
+    }
+
+    /// Whether any instruction [`halt`](Instruction::Halt) can be reached from the program's
+    /// entry point. Useful to detect programs that can never terminate gracefully.
+    ///
+    /// This is a best-effort, static analysis of the control flow. It is conservative: every
+    /// branch of [`skiz`](Instruction::Skiz) and
+    /// [`recurse_or_return`](Instruction::RecurseOrReturn) is considered taken, and failing
+    /// assertions are ignored. Consequently, `false` means that the program never halts, but
+    /// `true` does not guarantee that it does, for example, if some loop never terminates.
+    pub fn reachable_halt(&self) -> bool {
+        let returning_routines = self.routines_that_may_return();
+
+        let mut routines_to_explore = vec![None];
+        let mut explored_routines = HashSet::new();
+        while let Some(routine) = routines_to_explore.pop() {
+            if !explored_routines.insert(routine) {
+                continue;
+            }
+            let exploration = self.explore_routine(routine, &returning_routines);
+            if exploration.reaches_halt {
+                return true;
+            }
+            routines_to_explore.extend(exploration.callees.into_iter().map(Some));
+        }
+
+        false
+    }
+
+    /// The entry points of all routines that might [return](Instruction::Return) to their
+    /// caller. Computed as a fixed point, since a routine can only return after all routines it
+    /// calls have returned.
+    fn routines_that_may_return(&self) -> HashSet<u64> {
+        let call_targets = self.call_targets();
+        let mut returning_routines = HashSet::new();
+        loop {
+            let newly_returning_routines = call_targets
+                .iter()
+                .filter(|routine| !returning_routines.contains(*routine))
+                .filter(|&&routine| {
+                    self.explore_routine(Some(routine), &returning_routines)
+                        .may_return
+                })
+                .copied()
+                .collect_vec();
+            if newly_returning_routines.is_empty() {
+                return returning_routines;
+            }
+            returning_routines.extend(newly_returning_routines);
+        }
+    }
+
+    /// Explore the control flow of one routine, starting at its entry point. The routine at the
+    /// program's entry point is identified by `None`. Calls are not followed; execution only
+    /// continues after a call if the callee is in `returning_routines`.
+    fn explore_routine(
+        &self,
+        routine: Option<u64>,
+        returning_routines: &HashSet<u64>,
+    ) -> RoutineExploration {
+        let mut exploration = RoutineExploration::default();
+        let mut addresses_to_visit = vec![routine.unwrap_or(0)];
+        let mut visited_addresses = HashSet::new();
+        while let Some(address) = addresses_to_visit.pop() {
+            if !visited_addresses.insert(address) {
+                continue;
+            }
+            let Some(&instruction) = usize::try_from(address)
+                .ok()
+                .and_then(|address| self.instructions.get(address))
+            else {
+                continue;
+            };
+
+            let next_address = address + instruction.size() as u64;
+            match instruction {
+                Instruction::Halt => exploration.reaches_halt = true,
+                Instruction::Return => exploration.may_return |= routine.is_some(),
+                Instruction::Recurse => addresses_to_visit.extend(routine),
+                Instruction::RecurseOrReturn => {
+                    exploration.may_return |= routine.is_some();
+                    addresses_to_visit.extend(routine);
+                }
+                Instruction::Skiz => {
+                    addresses_to_visit.push(next_address);
+                    let skipped_instruction = usize::try_from(next_address)
+                        .ok()
+                        .and_then(|address| self.instructions.get(address));
+                    if let Some(skipped_instruction) = skipped_instruction {
+                        addresses_to_visit.push(next_address + skipped_instruction.size() as u64);
+                    }
+                }
+                Instruction::Call(callee) => {
+                    exploration.callees.insert(callee.value());
+                    if returning_routines.contains(&callee.value()) {
+                        addresses_to_visit.push(next_address);
+                    }
+                }
+                _ => addresses_to_visit.push(next_address),
+            }
+        }
+
+        exploration
+    }
+
+    /// Remove consecutive instruction pairs that have no effect, like `swap i swap i` or
+    /// `dup i pop 1`. Returns the optimized program as well as the number of eliminated
+    /// instructions.
+    ///
+    /// A pair is only removed if it is not separated by a label, breakpoint, or type hint, and if
+    /// it is not preceded by instruction `skiz`, which could skip only the first of the pair.
+    pub fn eliminate_redundant_stack_shuffles(&self) -> (Self, usize) {
+        let mut optimized_instructions: Vec<LabelledInstruction> = vec![];
+        let mut num_eliminated_instructions = 0;
+        for labelled_instruction in self.labelled_instructions() {
+            let LabelledInstruction::Instruction(instruction) = &labelled_instruction else {
+                optimized_instructions.push(labelled_instruction);
+                continue;
+            };
+            let Some(LabelledInstruction::Instruction(previous_instruction)) =
+                optimized_instructions.last()
+            else {
+                optimized_instructions.push(labelled_instruction);
+                continue;
+            };
+
+            let is_redundant =
+                Self::is_redundant_instruction_pair(previous_instruction, instruction);
+            let instruction_before_previous =
+                optimized_instructions
+                    .iter()
+                    .rev()
+                    .skip(1)
+                    .find_map(|labelled_instruction| match labelled_instruction {
+                        LabelledInstruction::Instruction(instruction) => Some(instruction),
+                        _ => None,
+                    });
+            let previous_might_be_skipped =
+                instruction_before_previous == Some(&AnInstruction::Skiz);
+
+            if is_redundant && !previous_might_be_skipped {
+                optimized_instructions.pop();
+                num_eliminated_instructions += 2;
+            } else {
+                optimized_instructions.push(labelled_instruction);
+            }
+        }
+
+        let optimized_program = Program::new(&optimized_instructions);
+        (optimized_program, num_eliminated_instructions)
+    }
+
+    fn is_redundant_instruction_pair(
+        first: &AnInstruction<String>,
+        second: &AnInstruction<String>,
+    ) -> bool {
+        match (first, second) {
+            (AnInstruction::Swap(st), AnInstruction::Swap(other_st)) => st == other_st,
+            (AnInstruction::Dup(_), AnInstruction::Pop(NumberOfWords::N1)) => true,
+            _ => false,
+        }
+    }
+
     pub fn is_breakpoint(&self, address: u64) -> bool {
         let address: usize = address.try_into().unwrap();
         self.breakpoints.get(address).unwrap_or(&false).to_owned()
@@ -403,6 +624,47 @@ impl Program {
             .collect()
     }
 
+    /// Encode the program as a byte-aligned sequence: the number of program words as an 8-byte
+    /// little-endian integer, followed by each [program word](Self::to_bwords) as an 8-byte
+    /// little-endian integer.
+    ///
+    /// See also [`from_byte_stream`](Self::from_byte_stream).
+    pub fn to_byte_stream(&self) -> Vec<u8> {
+        let words = self.to_bwords();
+        let length = u64::try_from(words.len()).unwrap();
+        let words = words.into_iter().map(|word| word.value());
+        [length]
+            .into_iter()
+            .chain(words)
+            .flat_map(u64::to_le_bytes)
+            .collect()
+    }
+
+    /// Decode a program from a byte sequence produced by
+    /// [`to_byte_stream`](Self::to_byte_stream). Every program word must be the canonical
+    /// representation of a [`BFieldElement`].
+    pub fn from_byte_stream(bytes: &[u8]) -> std::result::Result<Self, ProgramDecodingError> {
+        const WORD_LEN: usize = std::mem::size_of::<u64>();
+        if bytes.is_empty() {
+            return Err(ProgramDecodingError::EmptySequence);
+        }
+        let words = bytes.chunks_exact(WORD_LEN);
+        if !words.remainder().is_empty() {
+            return Err(ProgramDecodingError::LengthMismatch);
+        }
+
+        let mut sequence = vec![];
+        for (word_idx, word) in words.enumerate() {
+            let word = u64::from_le_bytes(word.try_into().unwrap());
+            if word >= BFieldElement::P {
+                return Err(ProgramDecodingError::NonCanonicalWord(word_idx));
+            }
+            sequence.push(bfe!(word));
+        }
+
+        Ok(*Program::decode(&sequence)?)
+    }
+
     /// The total length of the program as `BFieldElement`s. Double-word instructions contribute
     /// two `BFieldElement`s.
     pub fn len_bwords(&self) -> usize {
@@ -415,6 +677,12 @@ impl Program {
 
     /// Produces the program's canonical hash digest. Uses [`Tip5`], the
     /// canonical hash function for Triton VM.
+    ///
+    /// This is exactly the digest the VM places in stack registers `st11` through `st15` when
+    /// starting execution, and which the Processor Table's initial constraints compress and
+    /// compare to the [program digest of the claim](crate::proof::Claim::program_digest). It
+    /// can be used to identify programs without executing them, for example to build a
+    /// [`Claim`](crate::proof::Claim) ahead of time.
     pub fn hash(&self) -> Digest {
         // not encoded using `BFieldCodec` because that would prepend the length
         Tip5::hash_varlen(&self.to_bwords())
@@ -440,6 +708,49 @@ impl Program {
         Ok(state.public_output)
     }
 
+    /// Continue running the program from the given [`VMState`] until it halts. Since the state
+    /// can be (de)serialized, this allows reproducing an execution from any intermediate state,
+    /// for example, one attached to a bug report. If execution succeeds, returns the entire
+    /// output of the program, including any output produced before the given state.
+    ///
+    /// # Errors
+    ///
+    /// - if the given [`VMState`] does not belong to `self`
+    /// - if Triton VM crashes
+    pub fn resume_from(
+        &self,
+        mut state: VMState,
+    ) -> std::result::Result<Vec<BFieldElement>, ResumptionError> {
+        if self.instructions != state.program {
+            return Err(ResumptionError::ProgramMismatch);
+        }
+        if let Err(err) = state.run() {
+            return Err(VMError::new(err, state).into());
+        }
+        Ok(state.public_output)
+    }
+
+    /// Like [`run`][run], but invokes the given `callback` on the [`VMState`] after each
+    /// executed instruction. This allows observing every cycle of the execution, for example
+    /// for live profiling or coverage analysis, without collecting all intermediate states.
+    ///
+    /// [run]: Self::run
+    pub fn run_with_callback<F: FnMut(&VMState)>(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+        mut callback: F,
+    ) -> Result<Vec<BFieldElement>> {
+        let mut state = VMState::new(self, public_input, non_determinism);
+        while !state.halting {
+            if let Err(err) = state.step() {
+                return Err(VMError::new(err, state));
+            }
+            callback(&state);
+        }
+        Ok(state.public_output)
+    }
+
     /// Trace the execution of a [`Program`]. That is, [`run`][run] the [`Program`] and additionally
     /// record that part of every encountered state that is necessary for proving correct execution.
     /// If execution  succeeds, returns
@@ -454,10 +765,28 @@ impl Program {
         &self,
         public_input: PublicInput,
         non_determinism: NonDeterminism,
+    ) -> Result<(AlgebraicExecutionTrace, Vec<BFieldElement>)> {
+        self.trace_execution_with_capacity(public_input, non_determinism, VMTableHeights::default())
+    }
+
+    /// Like [`trace_execution`][trace_execution], but allocates the
+    /// [`AlgebraicExecutionTrace`] for the given table heights up front. This avoids
+    /// repeated reallocation while executing programs that produce large traces. The table
+    /// heights can be obtained using [`estimate_table_heights`][estimate], for example, from a
+    /// previous run. Wrong table heights do not affect the resulting trace.
+    ///
+    /// [trace_execution]: Self::trace_execution
+    /// [estimate]: Self::estimate_table_heights
+    pub fn trace_execution_with_capacity(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+        table_heights: VMTableHeights,
     ) -> Result<(AlgebraicExecutionTrace, Vec<BFieldElement>)> {
         profiler!(start "trace execution" ("gen"));
         let state = VMState::new(self, public_input, non_determinism);
-        let (aet, terminal_state) = self.trace_execution_of_state(state)?;
+        let aet = AlgebraicExecutionTrace::with_capacity(self.clone(), table_heights);
+        let (aet, terminal_state) = self.record_execution_of_state(state, aet)?;
         profiler!(stop "trace execution");
         Ok((aet, terminal_state.public_output))
     }
@@ -474,10 +803,18 @@ impl Program {
     /// - if the given [`VMState`] is not about to `self`
     /// - if the given [`VMState`] is incorrectly initialized
     pub fn trace_execution_of_state(
+        &self,
+        state: VMState,
+    ) -> Result<(AlgebraicExecutionTrace, VMState)> {
+        let aet = AlgebraicExecutionTrace::new(self.clone());
+        self.record_execution_of_state(state, aet)
+    }
+
+    fn record_execution_of_state(
         &self,
         mut state: VMState,
+        mut aet: AlgebraicExecutionTrace,
     ) -> Result<(AlgebraicExecutionTrace, VMState)> {
-        let mut aet = AlgebraicExecutionTrace::new(self.clone());
         assert_eq!(self.instructions, state.program);
         assert_eq!(self.len_bwords(), aet.instruction_multiplicities.len());
 
@@ -497,6 +834,25 @@ impl Program {
         Ok((aet, state))
     }
 
+    /// Run Triton VM with the given public and secret input, and report the heights
+    /// of the most relevant tables before [padding][pad]. This allows rejecting
+    /// inputs that exceed some size budget before committing to proving, which
+    /// is a lot more expensive.
+    ///
+    /// See also [`trace_execution`][trace_execution] and [`profile`][profile].
+    ///
+    /// [pad]: crate::table::master_table::MasterBaseTable::pad
+    /// [trace_execution]: Self::trace_execution
+    /// [profile]: Self::profile
+    pub fn estimate_table_heights(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<VMTableHeights> {
+        let (aet, _) = self.trace_execution(public_input, non_determinism)?;
+        Ok(VMTableHeights::from_aet(&aet))
+    }
+
     /// Run Triton VM with the given public and secret input, recording the
     /// influence of a callable block of instructions on the
     /// [`AlgebraicExecutionTrace`]. For example, this can be used to identify the
@@ -535,6 +891,116 @@ impl Program {
         Ok((state.public_output, profiler.finish()))
     }
 
+    /// Run Triton VM with the given public and secret input, attributing each cycle to the
+    /// call frame that is active at that time. Call frames are identified by the label of the
+    /// called routine; cycles outside any call are attributed to the label of the program's
+    /// entry point. Recursion, be it through [`recurse`](Instruction::Recurse) or through
+    /// repeated [`call`](Instruction::Call)s, accumulates into the same label.
+    ///
+    /// See also [`profile`][profile].
+    ///
+    /// [profile]: Self::profile
+    pub fn profile_call_graph(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<CallGraphProfile> {
+        let (aet, _) = self.trace_execution(public_input, non_determinism)?;
+        let column =
+            |col: ProcessorBaseTableColumn| aet.processor_trace.column(col.base_table_index());
+        let jump_stack_pointers = column(ProcessorBaseTableColumn::JSP);
+        let jump_stack_destinations = column(ProcessorBaseTableColumn::JSD);
+
+        let mut call_stack = vec![self.label_for_address(0)];
+        let mut profile = CallGraphProfile::default();
+        for (jsp, jsd) in jump_stack_pointers.iter().zip(jump_stack_destinations) {
+            let call_depth = usize::try_from(jsp.value()).unwrap();
+            call_stack.truncate(call_depth + 1);
+            if call_stack.len() == call_depth {
+                call_stack.push(self.label_for_address(jsd.value()));
+            }
+
+            let active_frame = call_stack.last().unwrap();
+            *profile.exclusive.entry(active_frame.clone()).or_default() += 1;
+            for label in call_stack.iter().unique() {
+                *profile.inclusive.entry(label.clone()).or_default() += 1;
+            }
+        }
+
+        Ok(profile)
+    }
+
+    /// Run Triton VM with the given public and secret input, recording which instructions are
+    /// executed. Returns the public output and the [`Coverage`], which lists the addresses of all
+    /// instructions that were never executed. These addresses can be mapped back to the source
+    /// code, for example using [`label_for_address`][label_for_address].
+    ///
+    /// [label_for_address]: Self::label_for_address
+    pub fn simulate_with_coverage(
+        &self,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<(Vec<BFieldElement>, Coverage)> {
+        let mut reached_addresses = HashSet::new();
+        let mut state = VMState::new(self, public_input, non_determinism);
+        while !state.halting {
+            reached_addresses.insert(state.instruction_pointer);
+            if let Err(err) = state.step() {
+                return Err(VMError::new(err, state));
+            }
+        }
+
+        let mut coverage = Coverage::default();
+        let mut address = 0;
+        while let Some(instruction) = self.instructions.get(address) {
+            coverage.num_instructions += 1;
+            if !reached_addresses.contains(&address) {
+                coverage.unreached_addresses.insert(address as u64);
+            }
+            address += instruction.size();
+        }
+
+        Ok((state.public_output, coverage))
+    }
+
+    /// Disassemble at most `len` instructions, starting with the instruction at or spanning
+    /// `start_addr`. Every instruction is paired with its address. Arguments, for example of
+    /// `push` or `call`, are part of their instruction and are skipped accordingly.
+    ///
+    /// Fewer than `len` instructions are returned if the end of the program is reached. If
+    /// `start_addr` is out of range, nothing is returned.
+    pub fn disassemble_range(&self, start_addr: u64, len: usize) -> Vec<(u64, Instruction)> {
+        let mut disassembly = Vec::with_capacity(len);
+        let mut address = 0;
+        while let Some(&instruction) = self.instructions.get(address) {
+            if disassembly.len() >= len {
+                break;
+            }
+            let next_address = address + instruction.size();
+            if next_address as u64 > start_addr {
+                disassembly.push((address as u64, instruction));
+            }
+            address = next_address;
+        }
+        disassembly
+    }
+
+    /// A static upper bound on the number of clock cycles needed to execute the program's
+    /// instructions once each, _i.e._, the sum of their [cycle costs](Instruction::cycle_cost).
+    ///
+    /// This is a per-instruction estimate, not a whole-execution estimate: control flow like
+    /// loops, recursion, or conditionally skipped instructions is ignored. It is an upper bound
+    /// on the actual execution's cycle count only for straight-line programs.
+    pub fn static_cycle_bound(&self) -> usize {
+        let mut cycle_bound = 0;
+        let mut address = 0;
+        while let Some(instruction) = self.instructions.get(address) {
+            cycle_bound += instruction.cycle_cost();
+            address += instruction.size();
+        }
+        cycle_bound
+    }
+
     /// The label for the given address, or a deterministic, unique substitute if no label is found.
     pub fn label_for_address(&self, address: u64) -> String {
         // Uniqueness of the label is relevant for printing and subsequent parsing:
@@ -544,6 +1010,16 @@ impl Program {
             .cloned()
             .unwrap_or_else(|| format!("address_{address}"))
     }
+
+    /// The labels resolved at assembly time, keyed by the address they point to.
+    ///
+    /// If multiple labels point to the same address, only one of them is retained.
+    pub fn labels(&self) -> BTreeMap<u64, String> {
+        self.address_to_label
+            .iter()
+            .map(|(&address, label)| (address, label.clone()))
+            .collect()
+    }
 }
 
 #[derive(Debug, Default, Clone, Eq, PartialEq, Arbitrary)]
@@ -578,6 +1054,65 @@ pub struct ExecutionTraceProfile {
     pub profile: Vec<ProfileLine>,
 }
 
+/// The result of exploring the control flow of a single routine, see
+/// [`Program::reachable_halt`].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct RoutineExploration {
+    reaches_halt: bool,
+    may_return: bool,
+    callees: HashSet<u64>,
+}
+
+/// The static call graph of a [`Program`], see [`Program::call_graph`]. Routines are identified
+/// by their labels.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct CallGraph {
+    pub routines: BTreeSet<String>,
+
+    /// Pairs of `(caller, callee)`.
+    pub edges: BTreeSet<(String, String)>,
+}
+
+impl CallGraph {
+    /// The routines called by the given routine.
+    pub fn callees<'a>(&'a self, routine: &'a str) -> impl Iterator<Item = &'a str> {
+        self.edges
+            .iter()
+            .filter(move |(caller, _)| caller == routine)
+            .map(|(_, callee)| callee.as_str())
+    }
+}
+
+/// The number of cycles spent in each routine of a [Triton](crate) program, keyed on the routine's
+/// label. See [`Program::profile_call_graph`].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct CallGraphProfile {
+    /// Cycles spent in a routine, including the cycles spent in any routine it calls.
+    pub inclusive: BTreeMap<String, usize>,
+
+    /// Cycles spent in a routine itself, excluding the cycles spent in any routine it calls.
+    pub exclusive: BTreeMap<String, usize>,
+}
+
+/// The instructions of a [`Program`] that were executed during one particular run, see
+/// [`Program::simulate_with_coverage`].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Coverage {
+    /// The number of instructions in the program. Instruction arguments are not counted
+    /// separately.
+    pub num_instructions: usize,
+
+    /// The addresses of all instructions that were never executed.
+    pub unreached_addresses: BTreeSet<u64>,
+}
+
+impl Coverage {
+    /// The number of instructions that were executed at least once.
+    pub fn num_reached_instructions(&self) -> usize {
+        self.num_instructions - self.unreached_addresses.len()
+    }
+}
+
 /// The heights of various [tables](AlgebraicExecutionTrace) relevant for
 /// proving the correct execution in [Triton VM](crate).
 #[non_exhaustive]
@@ -663,6 +1198,19 @@ impl VMTableHeights {
             ..Default::default()
         }
     }
+
+    /// The heights of the relevant tables in the given [`AlgebraicExecutionTrace`], before
+    /// padding.
+    pub(crate) fn from_aet(aet: &AlgebraicExecutionTrace) -> Self {
+        let height = |table| u32::try_from(aet.height_of_table(table)).unwrap();
+        Self {
+            processor: height(TableId::Processor),
+            op_stack: height(TableId::OpStack),
+            ram: height(TableId::Ram),
+            hash: height(TableId::Hash),
+            u32: height(TableId::U32),
+        }
+    }
 }
 
 impl Sub<Self> for VMTableHeights {
@@ -895,6 +1443,142 @@ impl NonDeterminism {
         self.ram = ram.into();
         self
     }
+
+    /// A [builder](NonDeterminismBuilder) for non-determinism, allowing to preload individual
+    /// words or entire blocks of RAM.
+    pub fn builder() -> NonDeterminismBuilder {
+        NonDeterminismBuilder::default()
+    }
+}
+
+/// Fluently build [`NonDeterminism`]. Obtained through [`NonDeterminism::builder`].
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct NonDeterminismBuilder {
+    non_determinism: NonDeterminism,
+}
+
+impl NonDeterminismBuilder {
+    /// Set the elements that can be read using instruction `divine`.
+    #[must_use]
+    pub fn individual_tokens<V: Into<Vec<BFieldElement>>>(mut self, tokens: V) -> Self {
+        self.non_determinism.individual_tokens = tokens.into();
+        self
+    }
+
+    /// Set the digests that can be read using instruction `merkle_step`.
+    #[must_use]
+    pub fn digests<V: Into<Vec<Digest>>>(mut self, digests: V) -> Self {
+        self.non_determinism.digests = digests.into();
+        self
+    }
+
+    /// Initialize RAM at the given address with the given value. Overwrites any previous
+    /// initialization of that address.
+    #[must_use]
+    pub fn ram_word(mut self, address: BFieldElement, value: BFieldElement) -> Self {
+        self.non_determinism.ram.insert(address, value);
+        self
+    }
+
+    /// Initialize RAM at consecutive addresses, starting at the given address, with the given
+    /// values. Overwrites any previous initialization of those addresses.
+    #[must_use]
+    pub fn ram_block(mut self, start_address: BFieldElement, values: &[BFieldElement]) -> Self {
+        let mut address = start_address;
+        for &value in values {
+            self.non_determinism.ram.insert(address, value);
+            address.increment();
+        }
+        self
+    }
+
+    pub fn build(self) -> NonDeterminism {
+        self.non_determinism
+    }
+}
+
+/// Program and associated inputs.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProgramAndInput {
+    pub program: Program,
+    pub public_input: PublicInput,
+    pub non_determinism: NonDeterminism,
+}
+
+impl ProgramAndInput {
+    pub fn new(program: Program) -> Self {
+        Self {
+            program,
+            public_input: PublicInput::default(),
+            non_determinism: NonDeterminism::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_input<PI: Into<PublicInput>>(mut self, public_input: PI) -> Self {
+        self.public_input = public_input.into();
+        self
+    }
+
+    #[must_use]
+    pub fn with_non_determinism<ND: Into<NonDeterminism>>(mut self, non_determinism: ND) -> Self {
+        self.non_determinism = non_determinism.into();
+        self
+    }
+
+    /// Load a program and its inputs from files, using the same formats as the [Triton TUI]:
+    /// - the program in Triton assembly,
+    /// - the public input as whitespace-separated, canonical representations of
+    ///   [`BFieldElement`]s, and
+    /// - the non-determinism as JSON.
+    ///
+    /// Omitting the public input or the non-determinism means using the respective default.
+    ///
+    /// [Triton TUI]: https://github.com/TritonVM/triton-tui
+    pub fn from_files(
+        program_path: impl AsRef<Path>,
+        input_path: Option<impl AsRef<Path>>,
+        non_determinism_path: Option<impl AsRef<Path>>,
+    ) -> std::result::Result<Self, ProgramLoadingError> {
+        let code = fs::read_to_string(program_path)?;
+        let program = Program::from_code(&code)
+            .map_err(|err| ProgramLoadingError::ParseError(err.to_string()))?;
+
+        let public_input = match input_path {
+            Some(path) => {
+                let tokens = fs::read_to_string(path)?;
+                let tokens = tokens.split_whitespace().map(|token| token.parse::<u64>());
+                let tokens = tokens.map_ok(BFieldElement::new).try_collect()?;
+                PublicInput::new(tokens)
+            }
+            None => PublicInput::default(),
+        };
+
+        let non_determinism = match non_determinism_path {
+            Some(path) => serde_json::from_str(&fs::read_to_string(path)?)?,
+            None => NonDeterminism::default(),
+        };
+
+        Ok(Self {
+            program,
+            public_input,
+            non_determinism,
+        })
+    }
+
+    pub fn public_input(&self) -> PublicInput {
+        self.public_input.clone()
+    }
+
+    pub fn non_determinism(&self) -> NonDeterminism {
+        self.non_determinism.clone()
+    }
+
+    /// A thin wrapper around [`Program::run`].
+    pub fn run(&self) -> Result<Vec<BFieldElement>> {
+        self.program
+            .run(self.public_input(), self.non_determinism())
+    }
 }
 
 #[cfg(test)]
@@ -909,6 +1593,9 @@ mod tests {
 
     use crate::error::InstructionError;
     use crate::example_programs::CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS;
+    use crate::example_programs::FIBONACCI_SEQUENCE;
+    use crate::example_programs::VERIFY_SUDOKU;
+    use crate::op_stack::OpStackElement;
     use crate::table::master_table::TableId;
     use crate::triton_program;
 
@@ -934,6 +1621,35 @@ mod tests {
         let_assert!(ProgramDecodingError::MissingArgument(6, _) = err);
     }
 
+    #[proptest]
+    fn byte_stream_round_trip(#[strategy(arb())] program: Program) {
+        let byte_stream = program.to_byte_stream();
+        prop_assert_eq!(0, byte_stream.len() % 8);
+        let_assert!(Ok(decoded) = Program::from_byte_stream(&byte_stream));
+        prop_assert_eq!(program, decoded);
+    }
+
+    #[test]
+    fn byte_stream_with_non_canonical_word_is_rejected() {
+        let program = triton_program!(push 3 push 4 add halt);
+        let mut byte_stream = program.to_byte_stream();
+        let push_argument = 2;
+        let non_canonical_three = BFieldElement::P + 3;
+        byte_stream[push_argument * 8..][..8].copy_from_slice(&non_canonical_three.to_le_bytes());
+
+        let_assert!(Err(err) = Program::from_byte_stream(&byte_stream));
+        assert!(ProgramDecodingError::NonCanonicalWord(push_argument) == err);
+    }
+
+    #[test]
+    fn byte_stream_with_partial_word_is_rejected() {
+        let program = triton_program!(push 3 push 4 add halt);
+        let mut byte_stream = program.to_byte_stream();
+        byte_stream.pop();
+        let_assert!(Err(err) = Program::from_byte_stream(&byte_stream));
+        assert!(ProgramDecodingError::LengthMismatch == err);
+    }
+
     #[test]
     fn decode_program_with_shorter_than_indicated_sequence() {
         let program = triton_program!(nop nop hash push 0 skiz end: halt call end);
@@ -952,6 +1668,60 @@ mod tests {
         let_assert!(ProgramDecodingError::SequenceTooLong = err);
     }
 
+    #[test]
+    fn word_following_instruction_of_size_one_is_decoded_as_separate_instruction() {
+        let halt = Instruction::Halt.opcode_b();
+        let nop = Instruction::Nop.opcode_b();
+        let_assert!(Ok(program) = Program::decode(&[bfe!(2), halt, nop]));
+        let instructions = program.into_iter().collect_vec();
+        assert!(vec![Instruction::Halt, Instruction::Nop] == instructions);
+
+        let stray_value = bfe!(1_u64 << 20);
+        let_assert!(Err(err) = Program::decode(&[bfe!(2), halt, stray_value]));
+        let_assert!(ProgramDecodingError::InvalidInstruction(1, _) = err);
+    }
+
+    #[test]
+    fn decoding_dup_or_swap_with_out_of_bounds_stack_register_fails() {
+        for instruction in [
+            Instruction::Dup(OpStackElement::ST0),
+            Instruction::Swap(OpStackElement::ST0),
+        ] {
+            let illegal_argument = bfe!(16);
+            let encoding = [bfe!(2), instruction.opcode_b(), illegal_argument];
+            let_assert!(Err(err) = Program::decode(&encoding));
+            let_assert!(ProgramDecodingError::InvalidInstruction(0, err) = err);
+            let_assert!(InstructionError::IllegalArgument(instr, arg) = err);
+            assert!(instruction.opcode() == instr.opcode());
+            assert!(illegal_argument == arg);
+        }
+    }
+
+    #[test]
+    fn program_with_argument_slot_occupied_by_other_instruction_is_invalid() {
+        let push = Instruction::Push(bfe!(5));
+        let program = Program {
+            instructions: vec![push, Instruction::Halt],
+            address_to_label: HashMap::default(),
+            breakpoints: vec![],
+            type_hints: HashMap::default(),
+        };
+        let_assert!(Err(err) = program.validate());
+        let_assert!(ProgramDecodingError::MalformedArgument(0, _) = err);
+
+        let program = Program {
+            instructions: vec![Instruction::Halt, push],
+            ..program
+        };
+        let_assert!(Err(err) = program.validate());
+        let_assert!(ProgramDecodingError::MissingArgument(1, _) = err);
+    }
+
+    #[proptest]
+    fn arbitrary_programs_are_valid(#[strategy(arb())] program: Program) {
+        prop_assert!(program.validate().is_ok());
+    }
+
     #[test]
     fn decode_program_from_empty_sequence() {
         let encoded = vec![];
@@ -976,6 +1746,42 @@ mod tests {
         assert!(expected_digest == digest);
     }
 
+    #[test]
+    fn hashes_of_programs_with_asserts_without_error_codes_are_unchanged() {
+        let assert = triton_program!(push 1 assert halt);
+        let expected_digest = Digest::new(bfe_array![
+            0x275d_c098_a7bb_615a_u64,
+            0x35ef_b52d_d619_1fb3_u64,
+            0xaabe_a8ef_b5ae_00fc_u64,
+            0xf952_54b8_476d_3cf2_u64,
+            0x554f_4043_4f10_91a9_u64,
+        ]);
+        assert!(expected_digest == assert.hash());
+
+        let assert_vector = triton_program!(
+            push 1 push 2 push 3 push 4 push 5
+            push 1 push 2 push 3 push 4 push 5
+            assert_vector halt
+        );
+        let expected_digest = Digest::new(bfe_array![
+            0x6353_bca8_225a_b5a7_u64,
+            0xc959_afc3_22ad_69ec_u64,
+            0x78d8_dab9_9c0a_c7df_u64,
+            0x06ea_eebc_d5bb_40dc_u64,
+            0xd9a8_f20d_8ffd_7aef_u64,
+        ]);
+        assert!(expected_digest == assert_vector.hash());
+
+        let expected_digest = Digest::new(bfe_array![
+            0xd6a5_1c37_7749_9b33_u64,
+            0x8709_8964_6401_fdc9_u64,
+            0x1ef1_233b_2f8c_b620_u64,
+            0x6cd1_ef95_f0d4_6179_u64,
+            0x75cf_3879_a3ed_514a_u64,
+        ]);
+        assert!(expected_digest == VERIFY_SUDOKU.hash());
+    }
+
     #[test]
     fn empty_program_is_empty() {
         let program = triton_program!();
@@ -1005,6 +1811,99 @@ mod tests {
         assert!(NonDeterminism::new(vec![]) == [].into());
     }
 
+    #[test]
+    fn static_cycle_bound_of_loop_free_program_is_number_of_instructions() {
+        let program = triton_program!(
+            push 3 push 4 add push 7 eq assert 42
+            split pop 1 sponge_init push 0 write_io 1 halt
+        );
+        assert!(12 == program.static_cycle_bound());
+        assert!(program.static_cycle_bound() == program.clone().into_iter().count());
+
+        let (_, profile) = program.profile([].into(), [].into()).unwrap();
+        assert!(program.static_cycle_bound() == profile.total.processor as usize);
+    }
+
+    #[test]
+    fn estimated_table_heights_match_algebraic_execution_trace() {
+        let program = triton_program!(
+            push 3 push 4 add push 7 eq assert 42
+            split pop 1 sponge_init push 0 write_mem 1 halt
+        );
+        let_assert!(Ok(heights) = program.estimate_table_heights([].into(), [].into()));
+        assert!(12 == heights.processor);
+
+        let_assert!(Ok((aet, _)) = program.trace_execution([].into(), [].into()));
+        let aet_height = |table| aet.height_of_table(table) as u32;
+        assert!(aet_height(TableId::Processor) == heights.processor);
+        assert!(aet_height(TableId::OpStack) == heights.op_stack);
+        assert!(aet_height(TableId::Ram) == heights.ram);
+        assert!(aet_height(TableId::Hash) == heights.hash);
+        assert!(aet_height(TableId::U32) == heights.u32);
+        assert!(0 < heights.ram);
+        assert!(0 < heights.u32);
+    }
+
+    #[test]
+    fn preloaded_ram_block_is_visible_to_program() {
+        let program = triton_program!(
+            push 102 read_mem 3 pop 1 write_io 3
+            push 200 read_mem 1 pop 1 write_io 1
+            halt
+        );
+        let non_determinism = NonDeterminism::builder()
+            .ram_block(bfe!(100), &bfe_array![10, 11, 12])
+            .ram_word(bfe!(200), bfe!(20))
+            .build();
+        let_assert!(Ok(output) = program.run([].into(), non_determinism));
+        assert!(bfe_vec![10, 11, 12, 20] == output);
+    }
+
+    #[test]
+    fn building_non_determinism_is_equivalent_to_constructing_it() {
+        let tokens = bfe_vec![1, 2, 3];
+        let digests = vec![Digest::default(); 2];
+        let ram = [(bfe!(5), bfe!(7)), (bfe!(6), bfe!(8)), (bfe!(9), bfe!(10))];
+
+        let constructed = NonDeterminism::new(tokens.clone())
+            .with_digests(digests.clone())
+            .with_ram(HashMap::from(ram));
+        let built = NonDeterminism::builder()
+            .individual_tokens(tokens)
+            .digests(digests)
+            .ram_word(bfe!(9), bfe!(0))
+            .ram_block(bfe!(5), &bfe_array![7, 8])
+            .ram_word(bfe!(9), bfe!(10))
+            .build();
+        assert!(constructed == built);
+    }
+
+    #[test]
+    fn program_that_always_loops_has_no_reachable_halt() {
+        let program = triton_program!(push 0 call loop halt loop: push 1 add recurse);
+        assert!(!program.reachable_halt());
+
+        let program = triton_program!(push 1 pop 1 recurse halt);
+        assert!(!program.reachable_halt());
+
+        let program = triton_program!(call foo halt foo: call bar return bar: call foo return);
+        assert!(!program.reachable_halt());
+    }
+
+    #[test]
+    fn program_that_halts_has_reachable_halt() {
+        let program = triton_program!(push 2 call countdown halt
+            countdown: push -1 add dup 0 skiz recurse return
+        );
+        assert!(program.reachable_halt());
+
+        let program = triton_program!(push 0 call loop loop: skiz halt recurse);
+        assert!(program.reachable_halt());
+
+        let program = triton_program!(call foo foo: call bar return bar: halt);
+        assert!(program.reachable_halt());
+    }
+
     #[test]
     fn create_program_from_code() {
         let element_3 = thread_rng().gen_range(0_u64..BFieldElement::P);
@@ -1039,6 +1938,44 @@ mod tests {
         program.run([].into(), [].into()).unwrap();
     }
 
+    #[test]
+    fn resuming_from_deserialized_intermediate_state_gives_same_result_as_running_through() {
+        let program = FIBONACCI_SEQUENCE.clone();
+        let public_input = PublicInput::new(bfe_vec![20]);
+        let non_determinism = NonDeterminism::default();
+        let expected_output = program
+            .run(public_input.clone(), non_determinism.clone())
+            .unwrap();
+
+        let mut state = VMState::new(&program, public_input, non_determinism);
+        for _ in 0..50 {
+            state.step().unwrap();
+        }
+        let serialized_state = serde_json::to_string(&state).unwrap();
+        let deserialized_state = serde_json::from_str(&serialized_state).unwrap();
+
+        let_assert!(Ok(output) = program.resume_from(deserialized_state));
+        assert!(expected_output == output);
+    }
+
+    #[test]
+    fn resuming_from_state_of_different_program_fails() {
+        let program = triton_program!(push 1 assert halt);
+        let other_program = triton_program!(push 2 pop 1 halt);
+        let state = VMState::new(&other_program, [].into(), [].into());
+
+        let_assert!(Err(err) = program.resume_from(state));
+        assert!(ResumptionError::ProgramMismatch == err);
+    }
+
+    #[test]
+    fn labels_are_reported_at_their_resolved_addresses() {
+        let program = triton_program!(call foo halt foo: return);
+        let labels = program.labels();
+        assert!(1 == labels.len());
+        assert!(Some("foo") == labels.get(&3).map(String::as_str));
+    }
+
     #[test]
     fn profile_can_be_created_and_agrees_with_regular_vm_run() {
         let program = CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS.clone();
@@ -1068,6 +2005,165 @@ mod tests {
         println!("{profile}");
     }
 
+    #[test]
+    fn callback_observes_every_cycle_and_run_agrees_with_regular_vm_run() {
+        let program = CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS.clone();
+        let mut num_callbacks = 0;
+        let mut last_cycle_count = 0;
+        let output = program
+            .run_with_callback([].into(), [].into(), |state| {
+                num_callbacks += 1;
+                last_cycle_count = state.cycle_count;
+            })
+            .unwrap();
+
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        let_assert!(Ok(()) = vm_state.run());
+        assert!(vm_state.public_output == output);
+        assert!(vm_state.cycle_count == num_callbacks);
+        assert!(vm_state.cycle_count == last_cycle_count);
+    }
+
+    #[test]
+    fn callback_sees_op_stack_after_instruction_execution() {
+        let program = triton_program!(push 1 push 2 add halt);
+        let mut stack_tops = vec![];
+        program
+            .run_with_callback([].into(), [].into(), |state| {
+                stack_tops.push(state.op_stack[OpStackElement::ST0]);
+            })
+            .unwrap();
+        assert!(bfe_vec![1, 2, 3, 3] == stack_tops);
+    }
+
+    #[test]
+    fn run_with_callback_reports_instruction_error() {
+        let program = triton_program!(push 0 assert halt);
+        let mut num_callbacks = 0;
+        let_assert!(
+            Err(err) = program.run_with_callback([].into(), [].into(), |_| num_callbacks += 1)
+        );
+        let_assert!(InstructionError::AssertionFailed { .. } = err.source);
+        assert!(1 == num_callbacks);
+    }
+
+    #[test]
+    fn call_graph_contains_edges_of_nested_calls() {
+        let program = triton_program! {
+            call a halt
+            a: call b return
+            b: push 0 skiz call b call c return
+            c: return
+        };
+        let call_graph = program.call_graph();
+
+        let main = program.label_for_address(0);
+        let expected_routines = [main.as_str(), "a", "b", "c"].map(String::from);
+        assert!(BTreeSet::from(expected_routines) == call_graph.routines);
+
+        let expected_edges = [(main.as_str(), "a"), ("a", "b"), ("b", "b"), ("b", "c")]
+            .map(|(caller, callee)| (caller.to_string(), callee.to_string()));
+        assert!(BTreeSet::from(expected_edges) == call_graph.edges);
+
+        assert!(vec!["b", "c"] == call_graph.callees("b").collect_vec());
+        assert!(call_graph.callees("c").next().is_none());
+    }
+
+    #[test]
+    fn call_graph_profile_attributes_cycles_to_recursive_loop() {
+        let program = triton_program! {
+            push 10 call loop pop 1 halt
+            loop:
+                dup 0 push 0 eq skiz return
+                addi -1 recurse
+        };
+        let profile = program.profile_call_graph([].into(), [].into()).unwrap();
+
+        let main = program.label_for_address(0);
+        assert!(profile.exclusive["loop"] > profile.exclusive[&main]);
+        assert!(profile.exclusive["loop"] == profile.inclusive["loop"]);
+
+        let total_cycles = profile.exclusive.values().sum::<usize>();
+        assert!(total_cycles == profile.inclusive[&main]);
+    }
+
+    #[test]
+    fn call_graph_profile_distinguishes_inclusive_and_exclusive_cycles() {
+        let program = triton_program! {
+            call outer halt
+            outer: nop call inner nop return
+            inner: nop nop nop return
+        };
+        let profile = program.profile_call_graph([].into(), [].into()).unwrap();
+
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        let_assert!(Ok(()) = vm_state.run());
+        let main = program.label_for_address(0);
+        assert!(vm_state.cycle_count as usize == profile.inclusive[&main]);
+
+        assert!(4 == profile.exclusive["inner"]);
+        assert!(4 == profile.inclusive["inner"]);
+        assert!(4 == profile.exclusive["outer"]);
+        assert!(8 == profile.inclusive["outer"]);
+        assert!(2 == profile.exclusive[&main]);
+    }
+
+    #[test]
+    fn coverage_reports_branch_that_is_never_taken() {
+        let program = triton_program! {
+            push 0 skiz call never_taken halt
+            never_taken: push 1 pop 1 return
+        };
+        let (_, coverage) = program
+            .simulate_with_coverage([].into(), [].into())
+            .unwrap();
+
+        assert!(7 == coverage.num_instructions);
+        assert!(3 == coverage.num_reached_instructions());
+        let expected_unreached_addresses = BTreeSet::from([3, 6, 8, 10]);
+        assert!(expected_unreached_addresses == coverage.unreached_addresses);
+
+        assert!("never_taken" == program.label_for_address(6));
+    }
+
+    #[test]
+    fn coverage_of_program_without_dead_code_is_complete() {
+        let program = triton_program! {
+            push 1 skiz call taken halt
+            taken: push 1 pop 1 return
+        };
+        let (_, coverage) = program
+            .simulate_with_coverage([].into(), [].into())
+            .unwrap();
+        assert!(coverage.unreached_addresses.is_empty());
+        assert!(7 == coverage.num_reached_instructions());
+    }
+
+    #[test]
+    fn disassembly_skips_instruction_arguments() {
+        let program = triton_program!(push 5 dup 0 add halt);
+        let disassembly = program.disassemble_range(0, 2);
+        let push = Instruction::Push(bfe!(5));
+        let dup = Instruction::Dup(OpStackElement::ST0);
+        assert!(vec![(0, push), (2, dup)] == disassembly);
+    }
+
+    #[test]
+    fn disassembly_of_range_starting_in_argument_includes_spanning_instruction() {
+        let program = triton_program!(push 5 dup 0 add halt);
+        let disassembly = program.disassemble_range(3, 10);
+        let dup = Instruction::Dup(OpStackElement::ST0);
+        assert!(vec![(2, dup), (4, Instruction::Add), (5, Instruction::Halt)] == disassembly);
+    }
+
+    #[test]
+    fn disassembly_out_of_range_is_clamped() {
+        let program = triton_program!(push 5 dup 0 add halt);
+        assert!(program.disassemble_range(6, 3).is_empty());
+        assert!(program.disassemble_range(0, 0).is_empty());
+        assert!(1 == program.disassemble_range(5, 100).len());
+    }
+
     #[test]
     fn program_with_too_many_returns_crashes_vm_but_not_profiler() {
         let program = triton_program! {
@@ -1101,6 +2197,53 @@ mod tests {
         assert!(!program.is_breakpoint(9));
     }
 
+    #[test]
+    fn consecutive_identical_swaps_are_eliminated() {
+        let program = triton_program!(
+            push 1 push 2 push 3 push 4
+            swap 3 swap 3 dup 2 pop 1
+            write_io 4 halt
+        );
+        let (optimized_program, num_eliminated_instructions) =
+            program.eliminate_redundant_stack_shuffles();
+        assert!(4 == num_eliminated_instructions);
+
+        let expected_program = triton_program!(push 1 push 2 push 3 push 4 write_io 4 halt);
+        assert!(expected_program == optimized_program);
+
+        let no_input = PublicInput::default;
+        let no_non_determinism = NonDeterminism::default;
+        let_assert!(Ok((aet, output)) = program.trace_execution(no_input(), no_non_determinism()));
+        let_assert!(
+            Ok((optimized_aet, optimized_output)) =
+                optimized_program.trace_execution(no_input(), no_non_determinism())
+        );
+        assert!(output == optimized_output);
+        assert!(optimized_aet.processor_trace.nrows() < aet.processor_trace.nrows());
+    }
+
+    #[test]
+    fn cascading_redundant_stack_shuffles_are_eliminated() {
+        let program = triton_program!(swap 1 swap 2 dup 5 pop 1 swap 2 swap 1 halt);
+        let (optimized_program, num_eliminated_instructions) =
+            program.eliminate_redundant_stack_shuffles();
+        assert!(6 == num_eliminated_instructions);
+        assert!(triton_program!(halt) == optimized_program);
+    }
+
+    #[test]
+    fn stack_shuffles_that_might_be_skipped_or_jumped_into_are_not_eliminated() {
+        let program = triton_program!(
+            push 0 skiz swap 3 swap 3
+            call foo halt
+            swap 2 foo: swap 2 return
+        );
+        let (optimized_program, num_eliminated_instructions) =
+            program.eliminate_redundant_stack_shuffles();
+        assert!(0 == num_eliminated_instructions);
+        assert!(program == optimized_program);
+    }
+
     #[test]
     fn print_program_without_any_debug_information() {
         let program = triton_program! {
@@ -1116,4 +2259,72 @@ mod tests {
         let program = Program::decode(&encoding).unwrap();
         println!("{program}");
     }
+
+    /// A fresh, empty directory exclusive to the calling test.
+    fn temp_dir_for_test(test_name: &str) -> std::path::PathBuf {
+        let directory_name = format!("triton-vm-{test_name}-{}", std::process::id());
+        let directory = std::env::temp_dir().join(directory_name);
+        if directory.exists() {
+            fs::remove_dir_all(&directory).unwrap();
+        }
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    #[test]
+    fn program_and_input_can_be_loaded_from_files() {
+        let directory = temp_dir_for_test("program-and-input-can-be-loaded-from-files");
+        let program_path = directory.join("fibonacci.tasm");
+        let input_path = directory.join("fibonacci.in");
+        let non_determinism_path = directory.join("fibonacci.json");
+
+        let non_determinism = NonDeterminism::new(bfe_vec![1, 2, 3]);
+        fs::write(&program_path, FIBONACCI_SEQUENCE.to_string()).unwrap();
+        fs::write(&input_path, "7\n").unwrap();
+        fs::write(
+            &non_determinism_path,
+            serde_json::to_string(&non_determinism).unwrap(),
+        )
+        .unwrap();
+
+        let program_and_input = ProgramAndInput::from_files(
+            &program_path,
+            Some(&input_path),
+            Some(&non_determinism_path),
+        )
+        .unwrap();
+        assert!(FIBONACCI_SEQUENCE.clone() == program_and_input.program);
+        assert!(PublicInput::new(bfe_vec![7]) == program_and_input.public_input);
+        assert!(non_determinism == program_and_input.non_determinism);
+
+        let output = program_and_input.run().unwrap();
+        assert!(bfe_vec![21] == output);
+    }
+
+    #[test]
+    fn omitted_inputs_default_when_loading_program_and_input_from_files() {
+        let directory = temp_dir_for_test("omitted-inputs-default-when-loading-from-files");
+        let program_path = directory.join("halt.tasm");
+        fs::write(&program_path, "halt").unwrap();
+
+        let no_path: Option<&Path> = None;
+        let program_and_input =
+            ProgramAndInput::from_files(&program_path, no_path, no_path).unwrap();
+        assert!(ProgramAndInput::new(triton_program!(halt)) == program_and_input);
+    }
+
+    #[test]
+    fn loading_program_and_input_from_files_fails_on_malformed_public_input() {
+        let directory = temp_dir_for_test("loading-from-files-fails-on-malformed-public-input");
+        let program_path = directory.join("halt.tasm");
+        let input_path = directory.join("halt.in");
+        fs::write(&program_path, "halt").unwrap();
+        fs::write(&input_path, "1 two 3").unwrap();
+
+        let no_path: Option<&Path> = None;
+        let_assert!(
+            Err(err) = ProgramAndInput::from_files(&program_path, Some(&input_path), no_path)
+        );
+        assert!(let ProgramLoadingError::PublicInputParseError(_) = err);
+    }
 }
@@ -1,20 +1,54 @@
 use arbitrary::Arbitrary;
 use get_size::GetSize;
 use itertools::Itertools;
+use serde::de::Error as _;
 use serde::Deserialize;
 use serde::Serialize;
 use twenty_first::prelude::*;
 
 use crate::error::ProofStreamError;
 use crate::program::Program;
+use crate::proof_item::ProofItem;
 use crate::proof_stream::ProofStream;
 
+/// The version of the JSON representation of a [`Proof`]. Bumped whenever that representation
+/// changes incompatibly.
+const JSON_PROOF_VERSION: u64 = 1;
+
+/// Identifies a [`Proof`] as such. Spells “TRITON” in ASCII.
+pub const PROOF_MAGIC: BFieldElement = BFieldElement::new(0x5452_4954_4f4e);
+
+/// The version of the [`Proof`] format, _i.e._, of its header and the encoding of the
+/// [`ProofStream`] following the header. Bumped whenever the format changes incompatibly.
+///
+/// Version 0 denotes [headerless](Proof::is_headerless) proofs.
+pub const PROOF_FORMAT_VERSION: u64 = 1;
+
+/// The number of [`BFieldElement`]s in the header of a [`Proof`]: the [magic](PROOF_MAGIC), the
+/// [format version](PROOF_FORMAT_VERSION), and the number of [`ProofItem`]s.
+pub(crate) const PROOF_HEADER_LEN: usize = 3;
+
 /// Contains the necessary cryptographic information to verify a computation.
 /// Should be used together with a [`Claim`].
+///
+/// A proof starts with a header consisting of the [magic](PROOF_MAGIC), the
+/// [format version](PROOF_FORMAT_VERSION), and the number of [`ProofItem`]s, followed by the
+/// [`BFieldCodec`] encoding of the [`ProofStream`]. The header is validated when converting the
+/// proof into a proof stream.
+///
+/// Proofs produced before the header was introduced consist of the proof stream's encoding only.
+/// Such proofs can be recognized using [`is_headerless`](Self::is_headerless) and still be decoded
+/// using [`ProofStream::decode`].
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, GetSize, BFieldCodec, Arbitrary)]
 pub struct Proof(pub Vec<BFieldElement>);
 
 impl Proof {
+    /// Whether the proof lacks the header identifying it as a proof, as is the case for proofs
+    /// produced by earlier versions of Triton VM. See [`Proof`] for details.
+    pub fn is_headerless(&self) -> bool {
+        self.0.first() != Some(&PROOF_MAGIC)
+    }
+
     /// Get the height of the trace used during proof generation.
     /// This is an upper bound on the length of the computation this proof is for.
     /// It is one of the main contributing factors to the length of the FRI domain.
@@ -33,6 +67,215 @@ impl Proof {
         }
         Ok(1 << log_2_padded_heights[0])
     }
+
+    /// Decode as many [`ProofItem`]s as possible, stopping at the first item that cannot be
+    /// decoded. Returns the successfully decoded prefix of items, alongside the error that
+    /// stopped decoding, if any.
+    ///
+    /// In contrast to converting the proof into a [`ProofStream`], this recovers the intact part
+    /// of a truncated or partially corrupted proof.
+    pub fn decode_items_lenient(&self) -> (Vec<ProofItem>, Option<ProofStreamError>) {
+        let item_encodings = match ItemEncodings::new(self, usize::MAX) {
+            Ok(item_encodings) => item_encodings,
+            Err(err) => return (vec![], Some(err)),
+        };
+
+        let mut items = vec![];
+        for item_encoding in item_encodings {
+            match item_encoding.and_then(decode_item) {
+                Ok(item) => items.push(item),
+                Err(err) => return (items, Some(err)),
+            }
+        }
+
+        (items, None)
+    }
+
+    /// A self-describing JSON representation of the proof, intended for interoperability with
+    /// other languages. The [`BFieldCodec`] encoding remains the compact default.
+    ///
+    /// The JSON object has two fields: `version`, a number, and `elements`, an array containing
+    /// the proof's field elements as strings of their canonical, decimal representation.
+    /// Using strings avoids precision loss in languages like JavaScript, where numbers are
+    /// [IEEE 754](https://en.wikipedia.org/wiki/IEEE_754) doubles.
+    ///
+    /// ```
+    /// # use triton_vm::prelude::*;
+    /// let proof = Proof(bfe_vec![0, 1, 18446744069414584320_u64]);
+    /// let json = proof.to_json();
+    /// assert_eq!(r#"{"version":1,"elements":["0","1","18446744069414584320"]}"#, json);
+    /// assert_eq!(proof, Proof::from_json(&json).unwrap());
+    /// ```
+    pub fn to_json(&self) -> String {
+        let elements = self.0.iter().map(|bfe| bfe.value().to_string()).collect();
+        let json_proof = JsonProof {
+            version: JSON_PROOF_VERSION,
+            elements,
+        };
+        serde_json::to_string(&json_proof).unwrap()
+    }
+
+    /// Parse a proof from its [JSON representation](Self::to_json).
+    ///
+    /// Fails if the JSON is malformed, if the version is not supported, or if any element is not
+    /// the decimal representation of a canonical field element, _i.e._, of an integer in range
+    /// `0..BFieldElement::P`. To keep the representation unique, elements must not have a sign
+    /// or leading zeros.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let json_proof: JsonProof = serde_json::from_str(json)?;
+        if json_proof.version != JSON_PROOF_VERSION {
+            let version = json_proof.version;
+            let err = format!("unsupported JSON proof version {version}");
+            return Err(serde_json::Error::custom(err));
+        }
+
+        let parse_element = |(i, element): (usize, &String)| {
+            let has_leading_zero = element.len() > 1 && element.starts_with('0');
+            let is_unique_representation = !element.starts_with('+') && !has_leading_zero;
+            element
+                .parse::<u64>()
+                .ok()
+                .filter(|&e| is_unique_representation && e < BFieldElement::P)
+                .map(BFieldElement::new)
+                .ok_or_else(|| {
+                    let err =
+                        format!("element {i} (\"{element}\") is not a canonical field element");
+                    serde_json::Error::custom(err)
+                })
+        };
+        let elements = json_proof.elements.iter().enumerate().map(parse_element);
+        Ok(Self(elements.try_collect()?))
+    }
+}
+
+/// Validate the header of a [`Proof`] and return the number of [`ProofItem`]s it announces.
+pub(crate) fn parse_proof_header(
+    header: [BFieldElement; PROOF_HEADER_LEN],
+) -> Result<u64, ProofStreamError> {
+    let [magic, version, num_items] = header;
+    if magic != PROOF_MAGIC || version.value() != PROOF_FORMAT_VERSION {
+        return Err(ProofStreamError::BadHeader);
+    }
+    Ok(num_items.value())
+}
+
+/// The encodings of the [`ProofItem`]s of a [`Proof`], in order. Stops after the first error.
+///
+/// After the header, the proof stream is encoded as the length of the remaining encoding, the
+/// number of items, and, for each item, that item's length followed by the item's encoding.
+#[derive(Debug, Clone)]
+pub(crate) struct ItemEncodings<'proof> {
+    announced_encoding_len: u64,
+    encoding_len: usize,
+    num_remaining_items: u64,
+    remaining_encoding: &'proof [BFieldElement],
+    max_item_bytes: usize,
+}
+
+impl<'proof> ItemEncodings<'proof> {
+    /// Validate the header and the number of items, which is encoded twice. Items the
+    /// [encoding](ProofItem::byte_size) of which exceeds `max_item_bytes` are rejected before
+    /// they are yielded.
+    pub(crate) fn new(
+        proof: &'proof Proof,
+        max_item_bytes: usize,
+    ) -> Result<Self, ProofStreamError> {
+        let Some((&header, encoding)) = proof.0.split_first_chunk() else {
+            return Err(ProofStreamError::BadHeader);
+        };
+        let num_items = parse_proof_header(header)?;
+        let [encoding_len, encoded_num_items, items_encoding @ ..] = encoding else {
+            return Err(ProofStreamError::Truncated);
+        };
+        if encoded_num_items.value() != num_items {
+            return Err(ProofStreamError::BadHeader);
+        }
+
+        Ok(Self {
+            announced_encoding_len: encoding_len.value(),
+            encoding_len: encoding.len() - 1,
+            num_remaining_items: num_items,
+            remaining_encoding: items_encoding,
+            max_item_bytes,
+        })
+    }
+
+    /// Whether the length of the proof stream's encoding is the announced one. Always false for
+    /// truncated proofs.
+    pub(crate) fn has_announced_length(&self) -> bool {
+        self.announced_encoding_len == self.encoding_len as u64
+    }
+
+    /// An upper bound on the number of items yet to be yielded, for example, to bound an
+    /// allocation. Every item takes up at least one element.
+    pub(crate) fn max_num_remaining_items(&self) -> usize {
+        let num_elements = self.remaining_encoding.len();
+        usize::try_from(self.num_remaining_items).map_or(num_elements, |n| n.min(num_elements))
+    }
+
+    /// The encoding following the items yielded so far. Once all items have been yielded, this is
+    /// empty for well-formed proofs.
+    pub(crate) fn remaining_encoding(&self) -> &'proof [BFieldElement] {
+        self.remaining_encoding
+    }
+
+    fn next_item_encoding(&mut self) -> Result<&'proof [BFieldElement], ProofStreamError> {
+        let [item_len, rest @ ..] = self.remaining_encoding else {
+            return Err(ProofStreamError::Truncated);
+        };
+        let item_len = checked_item_length(*item_len, self.max_item_bytes)?;
+        let Some((item_encoding, rest)) = rest.split_at_checked(item_len) else {
+            return Err(ProofStreamError::Truncated);
+        };
+        self.remaining_encoding = rest;
+        Ok(item_encoding)
+    }
+}
+
+impl<'proof> Iterator for ItemEncodings<'proof> {
+    type Item = Result<&'proof [BFieldElement], ProofStreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.num_remaining_items == 0 {
+            return None;
+        }
+        let item_encoding = self.next_item_encoding();
+        self.num_remaining_items = match item_encoding {
+            Ok(_) => self.num_remaining_items - 1,
+            Err(_) => 0,
+        };
+        Some(item_encoding)
+    }
+}
+
+/// Decode a single [`ProofItem`] as yielded by [`ItemEncodings`].
+pub(crate) fn decode_item(item_encoding: &[BFieldElement]) -> Result<ProofItem, ProofStreamError> {
+    Ok(*ProofItem::decode(item_encoding)?)
+}
+
+/// The length of an item with the given length indicator, if that item's
+/// [encoding](ProofItem::byte_size) does not exceed `max_item_bytes`.
+pub(crate) fn checked_item_length(
+    item_length: BFieldElement,
+    max_item_bytes: usize,
+) -> Result<usize, ProofStreamError> {
+    let item_length = usize::try_from(item_length.value()).unwrap_or(usize::MAX);
+    let size = item_length.saturating_mul(BFieldElement::BYTES);
+    if size > max_item_bytes {
+        return Err(ProofStreamError::ItemTooLarge {
+            size,
+            max_size: max_item_bytes,
+        });
+    }
+    Ok(item_length)
+}
+
+/// The [JSON representation](Proof::to_json) of a [`Proof`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonProof {
+    version: u64,
+    elements: Vec<String>,
 }
 
 /// Contains the public information of a verifiably correct computation.
@@ -79,17 +322,39 @@ impl Claim {
         self.output = output;
         self
     }
+
+    /// The hash digest of the program the claim is about.
+    pub fn program_digest(&self) -> Digest {
+        self.program_digest
+    }
+
+    /// The number of elements in the public input.
+    pub fn input_len(&self) -> usize {
+        self.input.len()
+    }
+
+    /// The number of elements in the public output.
+    pub fn output_len(&self) -> usize {
+        self.output.len()
+    }
+
+    /// Whether the claim is about the given program, _i.e._, whether the program's digest
+    /// matches the claim's [program digest](Self::program_digest).
+    pub fn matches_program(&self, program: &Program) -> bool {
+        program.hash() == self.program_digest
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use assert2::assert;
+    use assert2::let_assert;
     use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use test_strategy::proptest;
 
-    use crate::proof_item::ProofItem;
+    use crate::triton_program;
 
     use super::*;
 
@@ -107,6 +372,90 @@ mod tests {
         prop_assert_eq!(proof, decoded);
     }
 
+    #[proptest]
+    fn json_proof_round_trips(#[strategy(arb())] proof: Proof) {
+        let json = proof.to_json();
+        let decoded = Proof::from_json(&json).unwrap();
+        prop_assert_eq!(proof, decoded);
+    }
+
+    #[test]
+    fn json_proof_encodes_elements_as_decimal_strings() {
+        let proof = Proof(bfe_vec![0, 42, BFieldElement::MAX]);
+        let json = proof.to_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert!(JSON_PROOF_VERSION == value["version"]);
+        let expected_elements = ["0", "42", "18446744069414584320"];
+        assert!(serde_json::json!(expected_elements) == value["elements"]);
+    }
+
+    #[test]
+    fn json_proof_with_non_canonical_element_is_rejected() {
+        let p = BFieldElement::P;
+        let json = format!(r#"{{"version":{JSON_PROOF_VERSION},"elements":["1","{p}"]}}"#);
+        assert!(Proof::from_json(&json).is_err());
+
+        let json = format!(
+            r#"{{"version":{JSON_PROOF_VERSION},"elements":["1","{}"]}}"#,
+            p - 1
+        );
+        assert!(Proof::from_json(&json).is_ok());
+    }
+
+    #[test]
+    fn json_proof_with_malformed_element_is_rejected() {
+        for element in ["-1", "0x1", "one", "1.0", ""] {
+            let json = format!(r#"{{"version":{JSON_PROOF_VERSION},"elements":["{element}"]}}"#);
+            assert!(Proof::from_json(&json).is_err(), "element: {element}");
+        }
+    }
+
+    #[test]
+    fn json_proof_with_explicitly_positive_element_is_rejected() {
+        let json = format!(r#"{{"version":{JSON_PROOF_VERSION},"elements":["+1"]}}"#);
+        assert!(Proof::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn json_proof_with_zero_padded_element_is_rejected() {
+        for element in ["007", "00"] {
+            let json = format!(r#"{{"version":{JSON_PROOF_VERSION},"elements":["{element}"]}}"#);
+            assert!(Proof::from_json(&json).is_err(), "element: {element}");
+        }
+
+        let json = format!(r#"{{"version":{JSON_PROOF_VERSION},"elements":["0","70"]}}"#);
+        assert!(Proof::from_json(&json).is_ok());
+    }
+
+    #[test]
+    fn json_proof_with_unsupported_version_is_rejected() {
+        let json = r#"{"version":0,"elements":["1"]}"#;
+        assert!(Proof::from_json(json).is_err());
+    }
+
+    #[test]
+    fn claim_accessors_report_claimed_data() {
+        let program = triton_program!(read_io 2 add write_io 1 halt);
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![3, 4])
+            .with_output(bfe_vec![7]);
+
+        assert!(program.hash() == claim.program_digest());
+        assert!(2 == claim.input_len());
+        assert!(1 == claim.output_len());
+    }
+
+    #[test]
+    fn claim_matches_program_it_is_about_but_not_a_modified_one() {
+        let program = triton_program!(read_io 2 add write_io 1 halt);
+        let claim = Claim::about_program(&program);
+        assert!(claim.matches_program(&program));
+
+        let modified_program = triton_program!(read_io 2 mul write_io 1 halt);
+        assert!(!claim.matches_program(&modified_program));
+    }
+
     #[proptest]
     fn decode_claim(#[strategy(arb())] claim: Claim) {
         let encoded = claim.encode();
@@ -114,6 +463,59 @@ mod tests {
         prop_assert_eq!(claim, decoded);
     }
 
+    fn proof_with_items(items: Vec<ProofItem>) -> Proof {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue_many(items);
+        proof_stream.into()
+    }
+
+    #[proptest(cases = 10)]
+    fn proof_with_valid_header_can_be_converted_into_proof_stream(#[strategy(arb())] root: Digest) {
+        let items = vec![ProofItem::Log2PaddedHeight(8), ProofItem::MerkleRoot(root)];
+        let proof = proof_with_items(items.clone());
+        assert!(PROOF_MAGIC == proof.0[0]);
+        assert!(bfe!(PROOF_FORMAT_VERSION) == proof.0[1]);
+        assert!(bfe!(items.len() as u64) == proof.0[2]);
+        assert!(!proof.is_headerless());
+
+        let proof_stream = ProofStream::try_from(&proof).unwrap();
+        assert!(items == proof_stream.items);
+    }
+
+    #[test]
+    fn proof_with_wrong_magic_is_rejected() {
+        let mut proof = proof_with_items(vec![ProofItem::Log2PaddedHeight(8)]);
+        proof.0[0] += bfe!(1);
+        assert!(proof.is_headerless());
+        assert!(let Err(ProofStreamError::BadHeader) = ProofStream::try_from(&proof));
+    }
+
+    #[test]
+    fn proof_with_wrong_version_is_rejected() {
+        let mut proof = proof_with_items(vec![ProofItem::Log2PaddedHeight(8)]);
+        proof.0[1] = bfe!(PROOF_FORMAT_VERSION + 1);
+        assert!(let Err(ProofStreamError::BadHeader) = ProofStream::try_from(&proof));
+    }
+
+    #[test]
+    fn proof_with_wrong_number_of_items_in_header_is_rejected() {
+        let mut proof = proof_with_items(vec![ProofItem::Log2PaddedHeight(8)]);
+        proof.0[2] = bfe!(2);
+        assert!(let Err(ProofStreamError::BadHeader) = ProofStream::try_from(&proof));
+    }
+
+    #[test]
+    fn headerless_proof_is_detected_and_can_still_be_decoded() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(8));
+        let headerless_proof = Proof(proof_stream.encode());
+        assert!(headerless_proof.is_headerless());
+        assert!(let Err(ProofStreamError::BadHeader) = ProofStream::try_from(&headerless_proof));
+
+        let decoded = *ProofStream::<Tip5>::decode(&headerless_proof.0).unwrap();
+        assert!(proof_stream.items == decoded.items);
+    }
+
     #[proptest(cases = 10)]
     fn proof_with_no_padded_height_gives_err(#[strategy(arb())] root: Digest) {
         let mut proof_stream = ProofStream::new();
@@ -134,6 +536,60 @@ mod tests {
         assert!(maybe_padded_height.is_err());
     }
 
+    #[proptest(cases = 10)]
+    fn lenient_decoding_of_intact_proof_recovers_all_items(#[strategy(arb())] root: Digest) {
+        let items = vec![
+            ProofItem::Log2PaddedHeight(8),
+            ProofItem::MerkleRoot(root),
+            ProofItem::OutOfDomainQuotientSegments([xfe!(5); 4]),
+        ];
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue_many(items.clone());
+        let proof: Proof = proof_stream.into();
+
+        let (decoded_items, maybe_err) = proof.decode_items_lenient();
+        assert!(items == decoded_items);
+        assert!(maybe_err.is_none());
+    }
+
+    #[proptest(cases = 10)]
+    fn lenient_decoding_of_proof_truncated_mid_item_recovers_preceding_items(
+        #[strategy(arb())] root: Digest,
+    ) {
+        let items = vec![
+            ProofItem::Log2PaddedHeight(8),
+            ProofItem::MerkleRoot(root),
+            ProofItem::OutOfDomainQuotientSegments([xfe!(5); 4]),
+        ];
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue_many(items.clone());
+        let Proof(mut proof_data) = proof_stream.into();
+        proof_data.truncate(proof_data.len() - 3);
+        let proof = Proof(proof_data);
+
+        let (decoded_items, maybe_err) = proof.decode_items_lenient();
+        assert!(items[..2] == decoded_items);
+        assert!(let Some(ProofStreamError::Truncated) = maybe_err);
+    }
+
+    #[test]
+    fn lenient_and_strict_decoding_reject_proof_shorter_than_header_alike() {
+        let proof = Proof(vec![PROOF_MAGIC]);
+        let (decoded_items, maybe_err) = proof.decode_items_lenient();
+        assert!(decoded_items.is_empty());
+        assert!(let Some(ProofStreamError::BadHeader) = maybe_err);
+
+        let_assert!(Err(err) = ProofStream::try_from_proof_bounded(&proof, usize::MAX));
+        assert!(let ProofStreamError::BadHeader = err);
+    }
+
+    #[proptest]
+    fn lenient_decoding_of_arbitrary_proof_data_does_not_panic(
+        #[strategy(vec(arb(), 0..1_000))] proof_data: Vec<BFieldElement>,
+    ) {
+        let _items_and_error = Proof(proof_data).decode_items_lenient();
+    }
+
     #[proptest]
     fn decoding_arbitrary_proof_data_does_not_panic(
         #[strategy(vec(arb(), 0..1_000))] proof_data: Vec<BFieldElement>,
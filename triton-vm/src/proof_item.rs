@@ -1,3 +1,6 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
 use arbitrary::Arbitrary;
 use strum::Display;
 use strum::EnumCount;
@@ -8,6 +11,7 @@ use twenty_first::prelude::*;
 use crate::error::ProofStreamError;
 use crate::error::ProofStreamError::UnexpectedItem;
 use crate::fri::AuthenticationStructure;
+use crate::proof::Claim;
 use crate::table::BaseRow;
 use crate::table::ExtensionRow;
 use crate::table::QuotientSegments;
@@ -24,6 +28,96 @@ pub struct FriResponse {
     pub revealed_leaves: Vec<XFieldElement>,
 }
 
+/// A lossless, compact encoding of an [`AuthenticationStructure`]. Every distinct [`Digest`] is
+/// stored only once; repeated occurrences are stored as references to the first occurrence. This
+/// pays off for Merkle trees with identical subtrees, for example, because of repeated leaves.
+///
+/// Convert from an [`AuthenticationStructure`] using [`From`], and back using [`TryFrom`]. The
+/// latter fails if the compressed structure is malformed, which can happen if it was obtained
+/// from an untrusted source.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Hash, BFieldCodec, Arbitrary)]
+pub struct CompressedAuthenticationStructure {
+    /// The distinct digests, in order of their first occurrence.
+    pub distinct_digests: Vec<Digest>,
+
+    /// The repeated digests, sorted by their position in the uncompressed structure.
+    pub repetitions: Vec<RepeatedDigest>,
+}
+
+/// A digest in an [`AuthenticationStructure`] that already occurred at some smaller position.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, BFieldCodec, Arbitrary)]
+pub struct RepeatedDigest {
+    /// The position of the repeated digest in the uncompressed structure.
+    pub position: u32,
+
+    /// The index of the digest in the [distinct digests][distinct].
+    ///
+    /// [distinct]: CompressedAuthenticationStructure::distinct_digests
+    pub distinct_digest_index: u32,
+}
+
+impl From<&AuthenticationStructure> for CompressedAuthenticationStructure {
+    fn from(auth_structure: &AuthenticationStructure) -> Self {
+        let mut compressed = Self::default();
+        let mut distinct_digest_indices = HashMap::new();
+        for (position, &digest) in auth_structure.iter().enumerate() {
+            let num_distinct_digests = compressed.distinct_digests.len();
+            match distinct_digest_indices.entry(digest) {
+                Entry::Vacant(entry) => {
+                    entry.insert(num_distinct_digests);
+                    compressed.distinct_digests.push(digest);
+                }
+                Entry::Occupied(entry) => compressed.repetitions.push(RepeatedDigest {
+                    position: position.try_into().unwrap(),
+                    distinct_digest_index: (*entry.get()).try_into().unwrap(),
+                }),
+            }
+        }
+        compressed
+    }
+}
+
+impl From<AuthenticationStructure> for CompressedAuthenticationStructure {
+    fn from(auth_structure: AuthenticationStructure) -> Self {
+        Self::from(&auth_structure)
+    }
+}
+
+impl TryFrom<CompressedAuthenticationStructure> for AuthenticationStructure {
+    type Error = ProofStreamError;
+
+    fn try_from(compressed: CompressedAuthenticationStructure) -> Result<Self, ProofStreamError> {
+        let CompressedAuthenticationStructure {
+            distinct_digests,
+            repetitions,
+        } = compressed;
+        let length = distinct_digests.len() + repetitions.len();
+        let mut auth_structure = Vec::with_capacity(length);
+        let mut distinct_digests_iter = distinct_digests.iter();
+        let mut repetitions = repetitions.into_iter().peekable();
+        for position in 0..length {
+            let is_repetition = repetitions
+                .peek()
+                .is_some_and(|repetition| repetition.position as usize == position);
+            let digest = if is_repetition {
+                // only digests that have already occurred can be repeated
+                let repetition = repetitions.next().unwrap();
+                let num_occurred_digests = distinct_digests.len() - distinct_digests_iter.len();
+                let occurred_digests = &distinct_digests[..num_occurred_digests];
+                occurred_digests.get(repetition.distinct_digest_index as usize)
+            } else {
+                distinct_digests_iter.next()
+            };
+            let Some(&digest) = digest else {
+                return Err(ProofStreamError::MalformedAuthenticationStructure);
+            };
+            auth_structure.push(digest);
+        }
+
+        Ok(auth_structure)
+    }
+}
+
 macro_rules! proof_items {
     ($($variant:ident($payload:ty) => $in_fiat_shamir_heuristic:literal, $try_into_fn:ident,)+) => {
         #[derive(
@@ -60,6 +154,16 @@ macro_rules! proof_items {
                 }
             }
 
+            /// The length of this proof item's [`BFieldCodec`] encoding, computed without encoding
+            /// the item.
+            pub fn encoding_length(&self) -> usize {
+                let discriminant_length = 1;
+                let payload_length = match self {
+                    $( Self::$variant(payload) => payload.embedded_encoding_length(), )+
+                };
+                discriminant_length + payload_length
+            }
+
             $(
             pub fn $try_into_fn(self) -> Result<$payload, ProofStreamError> {
                 match self {
@@ -87,6 +191,13 @@ macro_rules! proof_items {
                 }
             }
 
+            /// The name of the variant, for example for display purposes.
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $( Self::$variant => stringify!($variant), )+
+                }
+            }
+
             /// Can be used as “reflection”, for example through `syn`.
             pub const fn payload_type(self) -> &'static str {
                 match self {
@@ -112,15 +223,161 @@ proof_items!(
     FriCodeword(Vec<XFieldElement>) => false, try_into_fri_codeword,
     FriPolynomial(Polynomial<XFieldElement>) => false, try_into_fri_polynomial,
     FriResponse(FriResponse) => false, try_into_fri_response,
+
+    // absorbed into the Fiat-Shamir heuristic separately, before any other proof item
+    Claim(Claim) => false, try_into_claim,
+
+    // implied by some Merkle root, like the uncompressed authentication structure
+    CompressedAuthenticationStructure(CompressedAuthenticationStructure) => false,
+        try_into_compressed_authentication_structure,
+);
+
+/// Borrowing counterparts of the `try_into_*` methods of [`ProofItem`] for payloads that are
+/// expensive to clone. Combine with [`ProofStream::dequeue_ref`] to read such payloads without
+/// cloning the entire proof item.
+///
+/// [`ProofStream::dequeue_ref`]: crate::proof_stream::ProofStream::dequeue_ref
+macro_rules! borrowing_accessors {
+    ($($variant:ident => $try_as_fn:ident -> $borrowed:ty,)+) => {
+        impl ProofItem {
+            $(
+            pub fn $try_as_fn(&self) -> Option<&$borrowed> {
+                match self {
+                    Self::$variant(payload) => Some(payload),
+                    _ => None,
+                }
+            }
+            )+
+        }
+    };
+}
+
+borrowing_accessors!(
+    OutOfDomainBaseRow => try_as_out_of_domain_base_row -> BaseRow<XFieldElement>,
+    OutOfDomainExtRow => try_as_out_of_domain_ext_row -> ExtensionRow,
+    AuthenticationStructure => try_as_authentication_structure -> [Digest],
+    MasterBaseTableRows => try_as_master_base_table_rows -> [BaseRow<BFieldElement>],
+    MasterExtTableRows => try_as_master_ext_table_rows -> [ExtensionRow],
+    QuotientSegmentsElements => try_as_quot_segments_elements -> [QuotientSegments],
+    FriCodeword => try_as_fri_codeword -> [XFieldElement],
+    FriPolynomial => try_as_fri_polynomial -> Polynomial<XFieldElement>,
+    FriResponse => try_as_fri_response -> FriResponse,
 );
 
+impl ProofItem {
+    /// The number of bytes required to encode this proof item, not including the overhead of
+    /// embedding it in a [`ProofStream`](crate::proof_stream::ProofStream).
+    pub fn byte_size(&self) -> usize {
+        self.encoding_length() * BFieldElement::BYTES
+    }
+}
+
+/// The length of a [`BFieldCodec`] encoding, computed from the structure of the encoded value.
+/// Unlike `encode().len()`, this does not allocate.
+trait EncodingLength: BFieldCodec {
+    fn encoding_length(&self) -> usize;
+
+    /// The length of the encoding when embedded in a surrounding encoding, for example as a
+    /// field of a struct. Dynamically sized encodings are then prepended by their length.
+    fn embedded_encoding_length(&self) -> usize {
+        let length_indicator_length = usize::from(Self::static_length().is_none());
+        length_indicator_length + self.encoding_length()
+    }
+}
+
+macro_rules! statically_sized_encoding_length {
+    ($($t:ty),+ $(,)?) => {$(
+        impl EncodingLength for $t {
+            fn encoding_length(&self) -> usize {
+                Self::static_length().unwrap()
+            }
+        }
+    )+};
+}
+
+statically_sized_encoding_length!(u32, BFieldElement, XFieldElement, Digest, RepeatedDigest);
+
+impl<T: EncodingLength, const N: usize> EncodingLength for [T; N] {
+    fn encoding_length(&self) -> usize {
+        self.iter().map(T::embedded_encoding_length).sum()
+    }
+}
+
+impl<T: EncodingLength> EncodingLength for Box<T> {
+    fn encoding_length(&self) -> usize {
+        self.as_ref().encoding_length()
+    }
+}
+
+impl<T: EncodingLength> EncodingLength for Vec<T> {
+    fn encoding_length(&self) -> usize {
+        let num_elements_length = 1;
+        num_elements_length + self.iter().map(T::embedded_encoding_length).sum::<usize>()
+    }
+}
+
+impl EncodingLength for Polynomial<XFieldElement> {
+    fn encoding_length(&self) -> usize {
+        // only the coefficients up to the degree are encoded, like a vector
+        let num_coefficients = usize::try_from(self.degree() + 1).unwrap();
+        let num_coefficients_length = 1;
+        let coefficients_length =
+            num_coefficients_length + num_coefficients * XFieldElement::static_length().unwrap();
+        let coefficients_length_indicator_length = 1;
+        coefficients_length_indicator_length + coefficients_length
+    }
+}
+
+impl EncodingLength for FriResponse {
+    fn encoding_length(&self) -> usize {
+        self.auth_structure.embedded_encoding_length()
+            + self.revealed_leaves.embedded_encoding_length()
+    }
+}
+
+impl EncodingLength for Claim {
+    fn encoding_length(&self) -> usize {
+        self.program_digest.embedded_encoding_length()
+            + self.input.embedded_encoding_length()
+            + self.output.embedded_encoding_length()
+    }
+}
+
+impl EncodingLength for CompressedAuthenticationStructure {
+    fn encoding_length(&self) -> usize {
+        self.distinct_digests.embedded_encoding_length()
+            + self.repetitions.embedded_encoding_length()
+    }
+}
+
+/// Some payloads are arrays too long for serde's derive macros. Instead, a proof item is
+/// serialized as its [`BFieldCodec`] encoding, which is a sequence of `u64`s.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ProofItem {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.encode(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ProofItem {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoding: Vec<BFieldElement> = serde::Deserialize::deserialize(deserializer)?;
+        let item = Self::decode(&encoding).map_err(serde::de::Error::custom)?;
+        Ok(*item)
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use std::collections::HashSet;
 
     use assert2::assert;
     use assert2::let_assert;
+    use itertools::Itertools;
+    use proptest::collection::vec;
     use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
     use strum::IntoEnumIterator;
     use test_strategy::proptest;
 
@@ -176,6 +433,124 @@ pub(crate) mod tests {
         prop_assert_eq!(auth_structure, auth_structure_);
     }
 
+    #[proptest]
+    fn compressing_authentication_structure_is_lossless(
+        #[strategy(vec(arb(), 0..50))] distinct_digests: Vec<Digest>,
+        #[strategy(vec(0..#distinct_digests.len().max(1), 0..100))] digest_indices: Vec<usize>,
+    ) {
+        let auth_structure = digest_indices
+            .into_iter()
+            .filter_map(|i| distinct_digests.get(i).copied())
+            .collect_vec();
+        let compressed = CompressedAuthenticationStructure::from(&auth_structure);
+        let num_distinct_digests = auth_structure.iter().unique().count();
+        prop_assert_eq!(num_distinct_digests, compressed.distinct_digests.len());
+
+        let_assert!(Ok(decompressed) = AuthenticationStructure::try_from(compressed));
+        prop_assert_eq!(auth_structure, decompressed);
+    }
+
+    #[proptest]
+    fn decompressed_authentication_structure_reconstructs_merkle_root(
+        leaved_merkle_tree: LeavedMerkleTreeTestData,
+    ) {
+        let compressed =
+            CompressedAuthenticationStructure::from(&leaved_merkle_tree.auth_structure);
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::CompressedAuthenticationStructure(compressed));
+        let proof: Proof = proof_stream.into();
+
+        let_assert!(Ok(mut proof_stream) = ProofStream::try_from(&proof));
+        let_assert!(Ok(proof_item) = proof_stream.dequeue());
+        let_assert!(Ok(compressed) = proof_item.try_into_compressed_authentication_structure());
+        let_assert!(Ok(auth_structure) = AuthenticationStructure::try_from(compressed));
+
+        let inclusion_proof = MerkleTreeInclusionProof::<Tip5> {
+            tree_height: leaved_merkle_tree.merkle_tree.height(),
+            indexed_leafs: leaved_merkle_tree
+                .revealed_indices
+                .iter()
+                .copied()
+                .zip_eq(leaved_merkle_tree.revealed_leaves.iter().map(|&x| x.into()))
+                .collect(),
+            authentication_structure: auth_structure,
+            ..MerkleTreeInclusionProof::default()
+        };
+        prop_assert!(inclusion_proof.verify(leaved_merkle_tree.root()));
+    }
+
+    #[test]
+    fn compressed_authentication_structure_with_dangling_repetition_is_malformed() {
+        let digest = Digest::new(bfe_array![1, 2, 3, 4, 5]);
+        let dangling_repetition = RepeatedDigest {
+            position: 0,
+            distinct_digest_index: 0,
+        };
+        let compressed = CompressedAuthenticationStructure {
+            distinct_digests: vec![digest],
+            repetitions: vec![dangling_repetition],
+        };
+        let_assert!(Err(err) = AuthenticationStructure::try_from(compressed));
+        assert!(let ProofStreamError::MalformedAuthenticationStructure = err);
+
+        let out_of_order_repetitions = vec![
+            RepeatedDigest {
+                position: 2,
+                distinct_digest_index: 0,
+            },
+            RepeatedDigest {
+                position: 1,
+                distinct_digest_index: 0,
+            },
+        ];
+        let compressed = CompressedAuthenticationStructure {
+            distinct_digests: vec![digest],
+            repetitions: out_of_order_repetitions,
+        };
+        let_assert!(Err(err) = AuthenticationStructure::try_from(compressed));
+        assert!(let ProofStreamError::MalformedAuthenticationStructure = err);
+    }
+
+    #[test]
+    fn compressed_and_uncompressed_authentication_structure_share_fiat_shamir_policy() {
+        let compressed = ProofItemVariant::CompressedAuthenticationStructure;
+        let uncompressed = ProofItemVariant::AuthenticationStructure;
+        assert!(
+            compressed.include_in_fiat_shamir_heuristic()
+                == uncompressed.include_in_fiat_shamir_heuristic()
+        );
+    }
+
+    #[proptest]
+    fn borrowing_accessors_agree_with_owning_accessors(#[strategy(arb())] item: ProofItem) {
+        let owned = item.clone().try_into_out_of_domain_base_row().ok();
+        prop_assert_eq!(owned.as_deref(), item.try_as_out_of_domain_base_row());
+
+        let owned = item.clone().try_into_out_of_domain_ext_row().ok();
+        prop_assert_eq!(owned.as_deref(), item.try_as_out_of_domain_ext_row());
+
+        let owned = item.clone().try_into_authentication_structure().ok();
+        prop_assert_eq!(owned.as_deref(), item.try_as_authentication_structure());
+
+        let owned = item.clone().try_into_master_base_table_rows().ok();
+        prop_assert_eq!(owned.as_deref(), item.try_as_master_base_table_rows());
+
+        let owned = item.clone().try_into_master_ext_table_rows().ok();
+        prop_assert_eq!(owned.as_deref(), item.try_as_master_ext_table_rows());
+
+        let owned = item.clone().try_into_quot_segments_elements().ok();
+        prop_assert_eq!(owned.as_deref(), item.try_as_quot_segments_elements());
+
+        let owned = item.clone().try_into_fri_codeword().ok();
+        prop_assert_eq!(owned.as_deref(), item.try_as_fri_codeword());
+
+        let owned = item.clone().try_into_fri_polynomial().ok();
+        prop_assert_eq!(owned.as_ref(), item.try_as_fri_polynomial());
+
+        let owned = item.clone().try_into_fri_response().ok();
+        prop_assert_eq!(owned.as_ref(), item.try_as_fri_response());
+    }
+
     #[test]
     fn interpreting_a_merkle_root_as_anything_else_gives_appropriate_error() {
         let fake_root = Digest::default();
@@ -191,6 +566,8 @@ pub(crate) mod tests {
         assert!(let Err(UnexpectedItem{..}) = item.clone().try_into_quot_segments_elements());
         assert!(let Err(UnexpectedItem{..}) = item.clone().try_into_fri_codeword());
         assert!(let Err(UnexpectedItem{..}) = item.clone().try_into_fri_polynomial());
+        assert!(let Err(UnexpectedItem{..}) = item.clone().try_into_claim());
+        assert!(let Err(UnexpectedItem{..}) = item.clone().try_into_compressed_authentication_structure());
         assert!(let Err(UnexpectedItem{..}) = item.try_into_fri_response());
     }
 
@@ -230,4 +607,20 @@ pub(crate) mod tests {
     fn proof_item_variants_payload_type_has_expected_format() {
         assert_eq!("Digest", ProofItemVariant::MerkleRoot.payload_type());
     }
+
+    #[proptest]
+    fn encoding_length_agrees_with_actual_encoding(#[strategy(arb())] item: ProofItem) {
+        assert!(item.encode().len() == item.encoding_length());
+        assert!(item.encode().len() * BFieldElement::BYTES == item.byte_size());
+    }
+
+    #[proptest]
+    fn encoding_length_of_polynomial_ignores_trailing_zeros(
+        #[strategy(vec(arb(), 0..10))] coefficients: Vec<XFieldElement>,
+        #[strategy(0_usize..5)] num_trailing_zeros: usize,
+    ) {
+        let coefficients = [coefficients, vec![xfe!(0); num_trailing_zeros]].concat();
+        let item = ProofItem::FriPolynomial(Polynomial::new(coefficients));
+        assert!(item.encode().len() == item.encoding_length());
+    }
 }
@@ -1,34 +1,164 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+
 use arbitrary::Arbitrary;
 use twenty_first::prelude::*;
 
 use crate::error::ProofStreamError;
+use crate::proof::checked_item_length;
+use crate::proof::decode_item;
+use crate::proof::parse_proof_header;
+use crate::proof::ItemEncodings;
 use crate::proof::Proof;
+use crate::proof::PROOF_FORMAT_VERSION;
+use crate::proof::PROOF_HEADER_LEN;
+use crate::proof::PROOF_MAGIC;
 use crate::proof_item::ProofItem;
+use crate::proof_item::ProofItemVariant;
+
+/// The means of deriving the verifier's challenges from the transcript of the
+/// [`ProofStream`], making the proof non-interactive through the Fiat-Shamir heuristic.
+///
+/// All [`AlgebraicHasher`]s, like [`Tip5`], are `FiatShamir`. Implementing this trait allows
+/// plugging in different challenge-derivation strategies, for example, a different
+/// domain-separation scheme.
+pub trait FiatShamir {
+    /// Absorb the given, variable-length input.
+    fn absorb(&mut self, input: &[BFieldElement]);
+
+    /// Produce some uniform random [`BFieldElement`]s. Must produce at least one element.
+    fn squeeze(&mut self) -> Vec<BFieldElement>;
+
+    /// Produce `num_indices` uniform random numbers in the interval `[0; upper_bound)`, where
+    /// `upper_bound` is a power of 2.
+    fn sample_indices(&mut self, upper_bound: u32, num_indices: usize) -> Vec<u32>;
+
+    /// Produce `num_scalars` uniform random [`XFieldElement`]s.
+    fn sample_scalars(&mut self, num_scalars: usize) -> Vec<XFieldElement>;
+}
+
+impl<H: AlgebraicHasher> FiatShamir for H {
+    fn absorb(&mut self, input: &[BFieldElement]) {
+        self.pad_and_absorb_all(input);
+    }
+
+    fn squeeze(&mut self) -> Vec<BFieldElement> {
+        Sponge::squeeze(self).to_vec()
+    }
+
+    fn sample_indices(&mut self, upper_bound: u32, num_indices: usize) -> Vec<u32> {
+        AlgebraicHasher::sample_indices(self, upper_bound, num_indices)
+    }
 
+    fn sample_scalars(&mut self, num_scalars: usize) -> Vec<XFieldElement> {
+        AlgebraicHasher::sample_scalars(self, num_scalars)
+    }
+}
+
+/// With feature `serde`, the proof stream can be (de)serialized using [serde]. Like for the
+/// [`BFieldCodec`] encoding, only the [items](Self::items) are serialized; the remaining fields
+/// are ephemeral and reset upon deserialization.
+///
+/// [serde]: https://serde.rs
 #[derive(Debug, Default, Clone, Eq, PartialEq, BFieldCodec, Arbitrary)]
-pub struct ProofStream {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofStream<S: Default = Tip5> {
     pub items: Vec<ProofItem>,
 
     #[bfield_codec(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub items_index: usize,
 
     #[bfield_codec(ignore)]
-    pub sponge: Tip5,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub sponge: S,
+
+    /// The number of items not yet [read](ProofStream::decode_next_from) from a streamed proof,
+    /// or `None` if the proof's header has not been read.
+    #[bfield_codec(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[arbitrary(default)]
+    num_items_left_to_stream: Option<u64>,
+
+    /// The Fiat-Shamir state the proof stream started out with, used to [reset](Self::reset).
+    #[bfield_codec(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[arbitrary(default)]
+    initial_sponge: S,
+}
+
+/// A snapshot of the Fiat-Shamir state of a [`ProofStream`], together with the position of the
+/// next item to [dequeue](ProofStream::dequeue). Obtained through
+/// [`ProofStream::fiat_shamir_checkpoint`] and consumed by [`ProofStream::restore_checkpoint`].
+///
+/// Checkpoints are ephemeral: they are not part of any [`Proof`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FiatShamirCheckpoint<S = Tip5> {
+    sponge: S,
+    items_index: usize,
 }
 
 impl ProofStream {
     pub fn new() -> Self {
+        Self::with_sponge(Tip5::init())
+    }
+
+    /// A new, empty proof stream whose Fiat-Shamir state is bound to the given domain-separation
+    /// tag. Protocols reusing the proof stream should use distinct tags: since the tag is
+    /// absorbed before any items, a proof created under one tag does not verify under another.
+    ///
+    /// The empty tag is not absorbed, making it equivalent to [`new`](Self::new).
+    pub fn with_domain_separator(tag: &[BFieldElement]) -> Self {
+        let mut sponge = Tip5::init();
+        if !tag.is_empty() {
+            sponge.pad_and_absorb_all(tag);
+        }
+        Self::with_sponge(sponge)
+    }
+}
+
+impl<S: FiatShamir + Default> ProofStream<S> {
+    /// A new, empty proof stream using the given sponge for the Fiat-Shamir heuristic.
+    pub fn with_sponge(sponge: S) -> Self
+    where
+        S: Clone,
+    {
         ProofStream {
             items: vec![],
             items_index: 0,
-            sponge: Tip5::init(),
+            sponge: sponge.clone(),
+            num_items_left_to_stream: None,
+            initial_sponge: sponge,
         }
     }
 
     /// The number of field elements required to encode the proof.
+    ///
+    /// See also [`transcript_length_by_kind`](Self::transcript_length_by_kind).
     pub fn transcript_length(&self) -> usize {
-        let Proof(b_field_elements) = self.into();
-        b_field_elements.len()
+        let Proof(empty_proof_stream_encoding) = ProofStream::new().into();
+        let items_length: usize = self.transcript_length_by_kind().values().sum();
+        empty_proof_stream_encoding.len() + items_length
+    }
+
+    /// The number of field elements required to encode the proof items of each kind, indexed by
+    /// the [name](ProofItemVariant::name) of the kind. Kinds not present in the proof stream are
+    /// omitted.
+    ///
+    /// Together with the (constant) encoding overhead of the proof stream itself, the lengths sum
+    /// up to the [transcript length](Self::transcript_length).
+    pub fn transcript_length_by_kind(&self) -> BTreeMap<&'static str, usize> {
+        let mut lengths = BTreeMap::new();
+        for item in &self.items {
+            let kind = ProofItemVariant::from(item).name();
+
+            // dynamically sized items are prepended by their length
+            let item_length = item.encoding_length() + 1;
+            *lengths.entry(kind).or_default() += item_length;
+        }
+        lengths
     }
 
     /// Alters the Fiat-Shamir's sponge state with the encoding of the given item.
@@ -38,7 +168,66 @@ impl ProofStream {
     ///
     /// See also [`Self::enqueue()`] and [`Self::dequeue()`].
     pub fn alter_fiat_shamir_state_with(&mut self, item: &impl BFieldCodec) {
-        self.sponge.pad_and_absorb_all(&item.encode())
+        self.sponge.absorb(&item.encode())
+    }
+
+    /// A compact fingerprint of the current Fiat-Shamir state, for example for logging or
+    /// for comparing transcripts across runs. Squeezes a [`Digest`] from a copy of the sponge,
+    /// leaving the actual state untouched. Sponges squeezing fewer than [`Digest::LEN`] elements
+    /// at a time are squeezed repeatedly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sponge squeezes no elements at all.
+    pub fn sponge_fingerprint(&self) -> Digest
+    where
+        S: Clone,
+    {
+        let mut sponge = self.sponge.clone();
+        let mut squeezed_elements = vec![];
+        while squeezed_elements.len() < Digest::LEN {
+            let squeeze = sponge.squeeze();
+            assert!(
+                !squeeze.is_empty(),
+                "sponge must squeeze at least one element"
+            );
+            squeezed_elements.extend(squeeze);
+        }
+        let digest_elements = squeezed_elements[..Digest::LEN].try_into().unwrap();
+        Digest::new(digest_elements)
+    }
+
+    /// Snapshot the current Fiat-Shamir state and the position in the proof stream, for example
+    /// to rewind after speculatively sampling some challenges. See
+    /// [`restore_checkpoint`](Self::restore_checkpoint).
+    pub fn fiat_shamir_checkpoint(&self) -> FiatShamirCheckpoint<S>
+    where
+        S: Clone,
+    {
+        FiatShamirCheckpoint {
+            sponge: self.sponge.clone(),
+            items_index: self.items_index,
+        }
+    }
+
+    /// Rewind the Fiat-Shamir state and the position in the proof stream to the given
+    /// [checkpoint](Self::fiat_shamir_checkpoint). Items enqueued since the checkpoint was taken
+    /// are kept.
+    pub fn restore_checkpoint(&mut self, checkpoint: FiatShamirCheckpoint<S>) {
+        self.sponge = checkpoint.sponge;
+        self.items_index = checkpoint.items_index;
+    }
+
+    /// Rewind to the start of the proof stream, allowing another pass over the same items.
+    /// Restores the Fiat-Shamir state the proof stream was created with, including any
+    /// [domain separator](ProofStream::with_domain_separator), thereby discarding all
+    /// Fiat-Shamir progress made since. Items are kept.
+    pub fn reset(&mut self)
+    where
+        S: Clone,
+    {
+        self.sponge = self.initial_sponge.clone();
+        self.items_index = 0;
     }
 
     /// Send a proof item as prover to verifier.
@@ -61,17 +250,248 @@ impl ProofStream {
     /// Receive a proof item from prover as verifier.
     /// See [`ProofStream::enqueue`] for more details.
     pub fn dequeue(&mut self) -> Result<ProofItem, ProofStreamError> {
+        self.dequeue_ref().cloned()
+    }
+
+    /// Like [`dequeue`](Self::dequeue), but borrows the proof item instead of cloning it. Useful
+    /// for large proof items, the payload of which can be accessed using the borrowing
+    /// accessors like [`ProofItem::try_as_out_of_domain_base_row`].
+    pub fn dequeue_ref(&mut self) -> Result<&ProofItem, ProofStreamError> {
         let Some(item) = self.items.get(self.items_index) else {
             return Err(ProofStreamError::EmptyQueue);
         };
-        let item = item.to_owned();
         if item.include_in_fiat_shamir_heuristic() {
-            self.alter_fiat_shamir_state_with(&item);
+            self.sponge.absorb(&item.encode());
         }
         self.items_index += 1;
         Ok(item)
     }
 
+    /// Read the next proof item from a streamed [`Proof`], without requiring the entire proof to
+    /// be in memory. Like [`dequeue`](Self::dequeue), alters the Fiat-Shamir state with the
+    /// item if applicable. Returns `None` once all the proof's items have been read.
+    ///
+    /// The reader must provide the proof's [`BFieldElement`]s, each as 8 little-endian bytes.
+    /// The first call reads the proof's header; every call thereafter reads one item. Read
+    /// items are recorded in, and count as dequeued from, this proof stream.
+    ///
+    /// See also [`decode_next_bounded_from`](Self::decode_next_bounded_from).
+    pub fn decode_next_from(
+        &mut self,
+        reader: &mut impl Read,
+    ) -> Result<Option<ProofItem>, ProofStreamError> {
+        self.decode_next_bounded_from(reader, usize::MAX)
+    }
+
+    /// Like [`decode_next_from`](Self::decode_next_from), but refuses to read an item whose
+    /// [encoding](ProofItem::byte_size) exceeds `max_item_bytes`. The item's size is checked
+    /// before any of its words are read. Useful for verifiers that process proofs from untrusted
+    /// sources.
+    pub fn decode_next_bounded_from(
+        &mut self,
+        reader: &mut impl Read,
+        max_item_bytes: usize,
+    ) -> Result<Option<ProofItem>, ProofStreamError> {
+        let num_items_left = match self.num_items_left_to_stream {
+            Some(num_items_left) => num_items_left,
+            None => {
+                let mut header = [bfe!(0); PROOF_HEADER_LEN];
+                for word in &mut header {
+                    *word = Self::read_word(reader)?;
+                }
+                let num_announced_items = parse_proof_header(header)?;
+                let _encoding_length = Self::read_word(reader)?;
+                let num_items = Self::read_word(reader)?.value();
+                if num_items != num_announced_items {
+                    return Err(ProofStreamError::BadHeader);
+                }
+                num_items
+            }
+        };
+        self.num_items_left_to_stream = Some(num_items_left);
+        if num_items_left == 0 {
+            return Ok(None);
+        }
+
+        let item_length = Self::read_word(reader)?;
+        let item_length = checked_item_length(item_length, max_item_bytes)?;
+        let item = self.read_item_of_length(reader, item_length)?;
+        self.num_items_left_to_stream = Some(num_items_left - 1);
+        Ok(Some(item))
+    }
+
+    /// Read the next proof item [written](Self::write_framed_items) to the given reader. Like
+    /// [`decode_next_from`](Self::decode_next_from), alters the Fiat-Shamir state with the item
+    /// if applicable, and records the item in this proof stream. Returns `None` once the reader
+    /// is exhausted.
+    pub fn decode_next_framed_from(
+        &mut self,
+        reader: &mut impl Read,
+    ) -> Result<Option<ProofItem>, ProofStreamError> {
+        let Some(item_length) = Self::try_read_word(reader)? else {
+            return Ok(None);
+        };
+        let item_length = checked_item_length(item_length, usize::MAX)?;
+        let item = self.read_item_of_length(reader, item_length)?;
+        Ok(Some(item))
+    }
+
+    fn read_item_of_length(
+        &mut self,
+        reader: &mut impl Read,
+        item_length: usize,
+    ) -> Result<ProofItem, ProofStreamError> {
+        // read word by word: the claimed length must not dictate the allocation size
+        let mut item_encoding = vec![];
+        for _ in 0..item_length {
+            item_encoding.push(Self::read_word(reader)?);
+        }
+        let item = *ProofItem::decode(&item_encoding)?;
+
+        if item.include_in_fiat_shamir_heuristic() {
+            self.alter_fiat_shamir_state_with(&item);
+        }
+        self.items.push(item.clone());
+        self.items_index = self.items.len();
+        Ok(item)
+    }
+
+    fn read_word(reader: &mut impl Read) -> Result<BFieldElement, ProofStreamError> {
+        Self::try_read_word(reader)?.ok_or(ProofStreamError::Truncated)
+    }
+
+    /// Read the next word, or `None` if the reader is exhausted before the word's first byte.
+    fn try_read_word(reader: &mut impl Read) -> Result<Option<BFieldElement>, ProofStreamError> {
+        let mut bytes = [0; BFieldElement::BYTES];
+        let num_read_bytes = loop {
+            match reader.read(&mut bytes) {
+                Ok(num_read_bytes) => break num_read_bytes,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        };
+        if num_read_bytes == 0 {
+            return Ok(None);
+        }
+        reader
+            .read_exact(&mut bytes[num_read_bytes..])
+            .map_err(|err| match err.kind() {
+                ErrorKind::UnexpectedEof => ProofStreamError::Truncated,
+                _ => err.into(),
+            })?;
+        let word = u64::from_le_bytes(bytes);
+        if word >= BFieldElement::P {
+            return Err(ProofStreamError::NonCanonicalWord(word));
+        }
+        Ok(Some(bfe!(word)))
+    }
+
+    /// Like [`dequeue`](Self::dequeue), but refuses to dequeue an item whose
+    /// [encoding](ProofItem::byte_size) exceeds `max_item_bytes`.
+    ///
+    /// Since the item has already been decoded, this does not bound memory usage. For that, use
+    /// [`ProofStream::try_from_proof_bounded`] or
+    /// [`decode_next_bounded_from`](Self::decode_next_bounded_from).
+    ///
+    /// If the item is too large, neither advances the proof stream nor alters the Fiat-Shamir
+    /// state.
+    pub fn dequeue_bounded(
+        &mut self,
+        max_item_bytes: usize,
+    ) -> Result<ProofItem, ProofStreamError> {
+        let size = self.peek()?.byte_size();
+        if size > max_item_bytes {
+            return Err(ProofStreamError::ItemTooLarge {
+                size,
+                max_size: max_item_bytes,
+            });
+        }
+        self.dequeue()
+    }
+
+    /// Like [`dequeue`](Self::dequeue), but fails if the next item is not of the `expected`
+    /// [kind](ProofItemVariant). On failure, neither advances the proof stream nor alters the
+    /// Fiat-Shamir state.
+    pub fn dequeue_expect(
+        &mut self,
+        expected: ProofItemVariant,
+    ) -> Result<ProofItem, ProofStreamError> {
+        let item = self.peek()?;
+        if ProofItemVariant::from(item) != expected {
+            let got = item.to_owned();
+            return Err(ProofStreamError::UnexpectedItem { expected, got });
+        }
+        self.dequeue()
+    }
+
+    /// Inspect the next proof item without [dequeuing](Self::dequeue) it. Useful for branching
+    /// on the variant of the upcoming [`ProofItem`].
+    ///
+    /// Neither advances the proof stream nor alters the Fiat-Shamir state; the item is only
+    /// absorbed into the sponge once it is dequeued.
+    pub fn peek(&self) -> Result<&ProofItem, ProofStreamError> {
+        self.items
+            .get(self.items_index)
+            .ok_or(ProofStreamError::EmptyQueue)
+    }
+
+    /// Iterate over all proof items in the order they were [enqueued](Self::enqueue), including
+    /// those that have already been [dequeued](Self::dequeue). Useful for inspecting the
+    /// transcript, for example when debugging a verifier failure.
+    ///
+    /// Neither advances the proof stream nor alters the Fiat-Shamir state.
+    pub fn items_iter(&self) -> impl Iterator<Item = &ProofItem> {
+        self.items.iter()
+    }
+
+    /// The total number of proof items in the proof stream, irrespective of how many have
+    /// already been [dequeued](Self::dequeue).
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the proof stream contains no proof items at all.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Send multiple proof items as prover to verifier. Equivalent to, but more convenient than,
+    /// [enqueuing](Self::enqueue) the items one by one, in order.
+    pub fn enqueue_many(&mut self, items: impl IntoIterator<Item = ProofItem>) {
+        for item in items {
+            self.enqueue(item);
+        }
+    }
+
+    /// Receive `num_items` proof items from prover as verifier. Equivalent to
+    /// [dequeuing](Self::dequeue) the items one by one, in order, except that failure leaves the
+    /// proof stream untouched: either all `num_items` items are dequeued, or none are.
+    pub fn dequeue_many(&mut self, num_items: usize) -> Result<Vec<ProofItem>, ProofStreamError> {
+        let num_remaining_items = self.items.len().saturating_sub(self.items_index);
+        if num_remaining_items < num_items {
+            return Err(ProofStreamError::EmptyQueue);
+        }
+        (0..num_items).map(|_| self.dequeue()).collect()
+    }
+
+    /// Write all items of the proof stream to the given writer, then remove them from the proof
+    /// stream. Every item is framed by its length, like in a [`Proof`], and every
+    /// [`BFieldElement`] is written as 8 little-endian bytes. The Fiat-Shamir state is not
+    /// affected.
+    ///
+    /// Written items can be read using [`decode_next_framed_from`](Self::decode_next_framed_from).
+    pub fn write_framed_items(&mut self, writer: &mut impl Write) -> std::io::Result<()> {
+        for item in self.items.drain(..) {
+            let encoding = item.encode();
+            let item_length = bfe!(encoding.len() as u64);
+            for word in [item_length].into_iter().chain(encoding) {
+                writer.write_all(&word.value().to_le_bytes())?;
+            }
+        }
+        self.items_index = 0;
+        Ok(())
+    }
+
     /// Given an `upper_bound` that is a power of 2, produce `num_indices` uniform random numbers
     /// in the interval `[0; upper_bound)`.
     ///
@@ -87,35 +507,147 @@ impl ProofStream {
             .collect()
     }
 
-    /// A thin wrapper around [`H::sample_scalars`](AlgebraicHasher::sample_scalars).
+    /// Produce `num_indices` uniform random numbers in the interval `[0; upper_bound)`, where
+    /// `upper_bound` does not need to be a power of 2.
+    ///
+    /// Uses rejection sampling: every squeezed element is first reduced to a uniform number below
+    /// the next power of 2 of `upper_bound` (or the field's size, whichever is smaller), which is
+    /// discarded if it is not below `upper_bound`.
+    ///
+    /// For `upper_bound`s of at most 2^32, more than half of all squeezed elements are accepted,
+    /// and sampling consumes fewer than `2 · num_indices / RATE + 1` [squeezes](Sponge::squeeze)
+    /// in expectation. For larger `upper_bound`s, at least a quarter of all squeezed elements are
+    /// accepted, and sampling consumes fewer than `4 · num_indices / RATE + 1` squeezes in
+    /// expectation. In the worst case, the number of squeezes is unbounded, but the probability of
+    /// requiring more squeezes decreases exponentially. Any squeezed elements not required to
+    /// produce the indices are discarded.
+    ///
+    /// - `upper_bound`: The (non-inclusive) upper bound. Must be in range `1..=BFieldElement::MAX`.
+    /// - `num_indices`: The number of indices to sample
+    ///
+    /// # Panics
+    ///
+    /// Panics if `upper_bound` is out of range, or if the sponge squeezes no elements at all,
+    /// which would make sampling loop forever.
+    pub fn sample_indices_bounded(&mut self, upper_bound: usize, num_indices: usize) -> Vec<usize> {
+        assert!(upper_bound > 0);
+        assert!(upper_bound as u64 <= BFieldElement::MAX);
+
+        // If the next power of 2 exceeds the field's size, the reduction is the identity.
+        let upper_bound = u128::from(upper_bound as u64);
+        let num_field_elements = u128::from(BFieldElement::P);
+        let reduction_modulus = upper_bound.next_power_of_two().min(num_field_elements);
+        let acceptance_bound = num_field_elements - num_field_elements % reduction_modulus;
+
+        let mut indices = Vec::with_capacity(num_indices);
+        while indices.len() < num_indices {
+            let squeezed_elements = self.sponge.squeeze();
+            assert!(
+                !squeezed_elements.is_empty(),
+                "sponge must squeeze at least one element"
+            );
+            for element in squeezed_elements {
+                let element = u128::from(element.value());
+                if element >= acceptance_bound {
+                    continue;
+                }
+                let index = element % reduction_modulus;
+                if index < upper_bound && indices.len() < num_indices {
+                    indices.push(index as usize);
+                }
+            }
+        }
+        indices
+    }
+
+    /// A thin wrapper around [`S::sample_scalars`](FiatShamir::sample_scalars).
     pub fn sample_scalars(&mut self, num_scalars: usize) -> Vec<XFieldElement> {
         self.sponge.sample_scalars(num_scalars)
     }
 }
 
+impl ProofStream {
+    /// Like [`ProofStream::try_from`], but using a decoder specialized to the layout of an encoded
+    /// proof stream instead of the derived [`BFieldCodec`] implementation. Both agree on every
+    /// proof; the derived implementation backing `try_from` remains the reference.
+    pub fn try_from_proof_fast(proof: &Proof) -> Result<Self, ProofStreamError> {
+        Self::try_from_proof_bounded(proof, usize::MAX)
+    }
+
+    /// Like [`try_from_proof_fast`](Self::try_from_proof_fast), but rejects the proof if the
+    /// [encoding](ProofItem::byte_size) of any of its items exceeds `max_item_bytes`. Each
+    /// item's size is checked before that item is decoded. Useful for verifiers that process
+    /// proofs from untrusted sources.
+    pub fn try_from_proof_bounded(
+        proof: &Proof,
+        max_item_bytes: usize,
+    ) -> Result<Self, ProofStreamError> {
+        let mut item_encodings = ItemEncodings::new(proof, max_item_bytes)?;
+        if !item_encodings.has_announced_length() {
+            return Err(ProofStreamError::LengthMismatch);
+        }
+
+        let mut items = Vec::with_capacity(item_encodings.max_num_remaining_items());
+        for item_encoding in &mut item_encodings {
+            items.push(decode_item(item_encoding?)?);
+        }
+        if !item_encodings.remaining_encoding().is_empty() {
+            return Err(ProofStreamError::LengthMismatch);
+        }
+
+        Ok(ProofStream {
+            items,
+            ..Self::default()
+        })
+    }
+
+    /// Like [`ProofStream::try_from`], but the Fiat-Shamir state of the resulting proof stream is
+    /// bound to the given [domain-separation tag](Self::with_domain_separator). Verifying a
+    /// proof requires the tag it was created under.
+    pub fn try_from_proof_with_domain_separator(
+        proof: &Proof,
+        tag: &[BFieldElement],
+    ) -> Result<Self, ProofStreamError> {
+        let ProofStream { items, .. } = ProofStream::try_from(proof)?;
+        Ok(ProofStream {
+            items,
+            ..Self::with_domain_separator(tag)
+        })
+    }
+}
+
 impl TryFrom<&Proof> for ProofStream {
     type Error = ProofStreamError;
 
     fn try_from(proof: &Proof) -> Result<Self, ProofStreamError> {
-        let proof_stream = *ProofStream::decode(&proof.0)?;
+        let Some(&header) = proof.0.first_chunk() else {
+            return Err(ProofStreamError::BadHeader);
+        };
+        let num_items = parse_proof_header(header)?;
+        let proof_stream = *ProofStream::decode(&proof.0[PROOF_HEADER_LEN..])?;
+        if proof_stream.items.len() as u64 != num_items {
+            return Err(ProofStreamError::BadHeader);
+        }
         Ok(proof_stream)
     }
 }
 
-impl From<&ProofStream> for Proof {
-    fn from(proof_stream: &ProofStream) -> Self {
-        Proof(proof_stream.encode())
+impl<S: Default> From<&ProofStream<S>> for Proof {
+    fn from(proof_stream: &ProofStream<S>) -> Self {
+        let num_items = bfe!(proof_stream.items.len() as u64);
+        let header = [PROOF_MAGIC, bfe!(PROOF_FORMAT_VERSION), num_items];
+        Proof([header.to_vec(), proof_stream.encode()].concat())
     }
 }
 
-impl From<ProofStream> for Proof {
-    fn from(proof_stream: ProofStream) -> Self {
+impl<S: Default> From<ProofStream<S>> for Proof {
+    fn from(proof_stream: ProofStream<S>) -> Self {
         (&proof_stream).into()
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use std::collections::VecDeque;
 
     use assert2::assert;
@@ -126,6 +658,8 @@ mod tests {
     use test_strategy::proptest;
     use twenty_first::math::other::random_elements;
 
+    use crate::arithmetic_domain::ArithmeticDomain;
+    use crate::fri::Fri;
     use crate::proof_item::FriResponse;
     use crate::proof_item::ProofItem;
     use crate::shared_tests::LeavedMerkleTreeTestData;
@@ -269,6 +803,44 @@ mod tests {
         let_assert!(Err(ProofStreamError::EmptyQueue) = proof_stream.dequeue());
     }
 
+    #[test]
+    fn peeking_neither_advances_proof_stream_nor_alters_fiat_shamir_state() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::FriCodeword(vec![]));
+        let proof = proof_stream.into();
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+        let pristine_proof_stream = proof_stream.clone();
+
+        let_assert!(Ok(ProofItem::FriCodeword(_)) = proof_stream.peek());
+        assert!(pristine_proof_stream == proof_stream);
+
+        let_assert!(Ok(item) = proof_stream.dequeue());
+        let_assert!(ProofItem::FriCodeword(_) = item);
+        let_assert!(Err(ProofStreamError::EmptyQueue) = proof_stream.peek());
+    }
+
+    #[proptest]
+    fn iterating_over_items_yields_them_in_enqueue_order_without_side_effects(
+        #[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>,
+        #[strategy(0_usize..=#items.len())] num_dequeued_items: usize,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        assert!(proof_stream.is_empty());
+        proof_stream.enqueue_many(items.clone());
+        assert!(items.len() == proof_stream.len());
+        assert!(items.is_empty() == proof_stream.is_empty());
+
+        let proof = proof_stream.into();
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+        proof_stream.dequeue_many(num_dequeued_items).unwrap();
+        let pristine_proof_stream = proof_stream.clone();
+
+        let iterated_items = proof_stream.items_iter().cloned().collect_vec();
+        assert!(items == iterated_items);
+        assert!(num_dequeued_items == proof_stream.items_index);
+        assert!(pristine_proof_stream == proof_stream);
+    }
+
     #[test]
     fn dequeuing_more_items_than_have_been_enqueued_fails() {
         let mut proof_stream = ProofStream::new();
@@ -280,8 +852,586 @@ mod tests {
         let_assert!(Err(ProofStreamError::EmptyQueue) = proof_stream.dequeue());
     }
 
+    #[proptest]
+    fn bounded_dequeuing_of_sufficiently_small_items_is_identical_to_dequeuing(
+        #[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue_many(items.clone());
+        let proof = proof_stream.into();
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+        let mut bounded_proof_stream = ProofStream::try_from(&proof).unwrap();
+
+        for item in items {
+            let_assert!(Ok(dequeued_item) = proof_stream.dequeue());
+            let_assert!(Ok(bounded_item) = bounded_proof_stream.dequeue_bounded(item.byte_size()));
+            assert!(dequeued_item == bounded_item);
+        }
+        assert!(proof_stream == bounded_proof_stream);
+    }
+
+    #[test]
+    fn bounded_dequeuing_of_too_large_item_fails_without_side_effects() {
+        let codeword = ProofItem::FriCodeword(vec![xfe!(1); 100]);
+        let size_of_codeword = codeword.byte_size();
+
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(codeword);
+        let proof = proof_stream.into();
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+        let pristine_proof_stream = proof_stream.clone();
+
+        let_assert!(
+            Err(ProofStreamError::ItemTooLarge { size, max_size }) =
+                proof_stream.dequeue_bounded(size_of_codeword - 1)
+        );
+        assert!(size_of_codeword == size);
+        assert!(size_of_codeword - 1 == max_size);
+        assert!(pristine_proof_stream == proof_stream);
+    }
+
+    #[test]
+    fn dequeuing_expected_kind_of_item_succeeds() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::MerkleRoot(Digest::default()));
+        let proof = proof_stream.into();
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+        let mut reference_proof_stream = ProofStream::try_from(&proof).unwrap();
+
+        let_assert!(Ok(item) = proof_stream.dequeue_expect(ProofItemVariant::MerkleRoot));
+        let_assert!(Ok(reference_item) = reference_proof_stream.dequeue());
+        assert!(reference_item == item);
+        assert!(reference_proof_stream == proof_stream);
+    }
+
+    #[test]
+    fn dequeuing_unexpected_kind_of_item_fails_without_side_effects() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::MerkleRoot(Digest::default()));
+        let proof = proof_stream.into();
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+        let pristine_proof_stream = proof_stream.clone();
+
+        let_assert!(
+            Err(ProofStreamError::UnexpectedItem { expected, got }) =
+                proof_stream.dequeue_expect(ProofItemVariant::FriCodeword)
+        );
+        assert!(ProofItemVariant::FriCodeword == expected);
+        assert!(ProofItem::MerkleRoot(Digest::default()) == got);
+        assert!(pristine_proof_stream == proof_stream);
+    }
+
+    #[proptest]
+    fn batched_and_individual_enqueuing_and_dequeuing_are_equivalent(
+        #[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>,
+        #[strategy(0_usize..=#items.len())] num_batched_items: usize,
+    ) {
+        let mut individual_proof_stream = ProofStream::new();
+        for item in items.clone() {
+            individual_proof_stream.enqueue(item);
+        }
+
+        let (batched_items, individual_items) = items.split_at(num_batched_items);
+        let mut mixed_proof_stream = ProofStream::new();
+        mixed_proof_stream.enqueue_many(batched_items.to_vec());
+        for item in individual_items.iter().cloned() {
+            mixed_proof_stream.enqueue(item);
+        }
+        assert!(individual_proof_stream == mixed_proof_stream);
+
+        let proof = mixed_proof_stream.into();
+        let mut individual_proof_stream = ProofStream::try_from(&proof).unwrap();
+        let mut mixed_proof_stream = ProofStream::try_from(&proof).unwrap();
+
+        let individually_dequeued_items = (0..items.len())
+            .map(|_| individual_proof_stream.dequeue().unwrap())
+            .collect_vec();
+
+        let mut mixedly_dequeued_items = vec![];
+        for _ in individual_items {
+            mixedly_dequeued_items.push(mixed_proof_stream.dequeue().unwrap());
+        }
+        let batch = mixed_proof_stream.dequeue_many(num_batched_items).unwrap();
+        mixedly_dequeued_items.extend(batch);
+
+        assert!(items == individually_dequeued_items);
+        assert!(items == mixedly_dequeued_items);
+        assert!(individual_proof_stream.sponge.state == mixed_proof_stream.sponge.state);
+    }
+
+    #[test]
+    fn dequeuing_more_items_than_available_in_one_batch_fails_without_side_effects() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue_many([
+            ProofItem::FriCodeword(vec![]),
+            ProofItem::Log2PaddedHeight(7),
+        ]);
+        let proof = proof_stream.into();
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+        let pristine_proof_stream = proof_stream.clone();
+
+        let_assert!(Err(ProofStreamError::EmptyQueue) = proof_stream.dequeue_many(3));
+        assert!(pristine_proof_stream == proof_stream);
+
+        let_assert!(Ok(items) = proof_stream.dequeue_many(2));
+        assert!(2 == items.len());
+    }
+
+    #[proptest]
+    fn bounded_indices_are_within_bounds_and_deterministic(
+        #[strategy(1_usize..=BFieldElement::MAX as usize)] upper_bound: usize,
+        #[strategy(0_usize..100)] num_indices: usize,
+        #[strategy(arb())] seed: Vec<BFieldElement>,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.sponge.pad_and_absorb_all(&seed);
+        let mut other_proof_stream = proof_stream.clone();
+
+        let indices = proof_stream.sample_indices_bounded(upper_bound, num_indices);
+        let other_indices = other_proof_stream.sample_indices_bounded(upper_bound, num_indices);
+        assert!(num_indices == indices.len());
+        assert!(indices.iter().all(|&index| index < upper_bound));
+        assert!(indices == other_indices);
+        assert!(proof_stream == other_proof_stream);
+    }
+
+    #[test]
+    fn bounded_indices_are_roughly_uniformly_distributed() {
+        let upper_bound = 10;
+        let num_indices = 10_000;
+        let expected_count = num_indices / upper_bound;
+
+        let indices = ProofStream::new().sample_indices_bounded(upper_bound, num_indices);
+        let counts = indices.into_iter().counts();
+        for index in 0..upper_bound {
+            let count = counts.get(&index).copied().unwrap_or_default();
+            assert!(count.abs_diff(expected_count) < expected_count / 5);
+        }
+    }
+
+    /// Never squeezes any elements.
+    #[derive(Debug, Default, Clone, Eq, PartialEq)]
+    struct MuteTip5(Tip5);
+
+    impl FiatShamir for MuteTip5 {
+        fn absorb(&mut self, input: &[BFieldElement]) {
+            FiatShamir::absorb(&mut self.0, input);
+        }
+
+        fn squeeze(&mut self) -> Vec<BFieldElement> {
+            vec![]
+        }
+
+        fn sample_indices(&mut self, upper_bound: u32, num_indices: usize) -> Vec<u32> {
+            FiatShamir::sample_indices(&mut self.0, upper_bound, num_indices)
+        }
+
+        fn sample_scalars(&mut self, num_scalars: usize) -> Vec<XFieldElement> {
+            FiatShamir::sample_scalars(&mut self.0, num_scalars)
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "sponge must squeeze at least one element")]
+    fn sampling_bounded_indices_from_mute_sponge_panics_instead_of_looping_forever() {
+        let mut proof_stream = ProofStream::with_sponge(MuteTip5::default());
+        proof_stream.sample_indices_bounded(10, 1);
+    }
+
+    #[proptest]
+    fn transcript_length_by_kind_sums_up_to_transcript_length(
+        #[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue_many(items);
+
+        let Proof(encoding) = (&proof_stream).into();
+        assert!(encoding.len() == proof_stream.transcript_length());
+
+        let lengths = proof_stream.transcript_length_by_kind();
+        for item in &proof_stream.items {
+            let kind = ProofItemVariant::from(item).name();
+            assert!(lengths[kind] > item.encode().len());
+        }
+        let kinds = proof_stream
+            .items
+            .iter()
+            .map(|item| ProofItemVariant::from(item).name());
+        assert!(kinds.unique().count() == lengths.len());
+    }
+
+    #[test]
+    fn transcript_length_by_kind_attributes_lengths_to_correct_kind() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(7));
+        proof_stream.enqueue(ProofItem::FriCodeword(vec![xfe!(1); 10]));
+        proof_stream.enqueue(ProofItem::FriCodeword(vec![xfe!(2); 5]));
+
+        let lengths = proof_stream.transcript_length_by_kind();
+        assert!(2 == lengths.len());
+        assert!(lengths["Log2PaddedHeight"] < lengths["FriCodeword"]);
+    }
+
+    /// Prepends a domain separator to every absorbed input.
+    #[derive(Debug, Default, Clone, Eq, PartialEq)]
+    pub(crate) struct DomainSeparatedTip5(Tip5);
+
+    impl FiatShamir for DomainSeparatedTip5 {
+        fn absorb(&mut self, input: &[BFieldElement]) {
+            let input = [bfe!(42)].iter().chain(input).copied().collect_vec();
+            FiatShamir::absorb(&mut self.0, &input);
+        }
+
+        fn squeeze(&mut self) -> Vec<BFieldElement> {
+            FiatShamir::squeeze(&mut self.0)
+        }
+
+        fn sample_indices(&mut self, upper_bound: u32, num_indices: usize) -> Vec<u32> {
+            FiatShamir::sample_indices(&mut self.0, upper_bound, num_indices)
+        }
+
+        fn sample_scalars(&mut self, num_scalars: usize) -> Vec<XFieldElement> {
+            FiatShamir::sample_scalars(&mut self.0, num_scalars)
+        }
+    }
+
+    #[proptest]
+    fn custom_fiat_shamir_sponge_changes_challenges_but_not_proof(
+        #[strategy(arb())] merkle_root: Digest,
+    ) {
+        let item = ProofItem::MerkleRoot(merkle_root);
+        let mut proof_stream = ProofStream::new();
+        let mut custom_proof_stream = ProofStream::with_sponge(DomainSeparatedTip5(Tip5::init()));
+        proof_stream.enqueue(item.clone());
+        custom_proof_stream.enqueue(item);
+
+        let challenges = proof_stream.sample_scalars(3);
+        let custom_challenges = custom_proof_stream.sample_scalars(3);
+        assert!(challenges != custom_challenges);
+
+        let proof = Proof::from(proof_stream);
+        let custom_proof = Proof::from(custom_proof_stream);
+        assert!(proof == custom_proof);
+
+        let_assert!(
+            Ok(decoded) =
+                ProofStream::<DomainSeparatedTip5>::decode(&custom_proof.0[PROOF_HEADER_LEN..])
+        );
+        assert!(decoded.items == [ProofItem::MerkleRoot(merkle_root)]);
+    }
+
+    #[proptest]
+    fn sponge_fingerprint_only_changes_with_fiat_shamir_relevant_items(
+        #[strategy(arb())] merkle_root: Digest,
+        #[strategy(arb())] log_2_padded_height: u32,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        let initial_fingerprint = proof_stream.sponge_fingerprint();
+        let initial_sponge_state = proof_stream.sponge.state;
+        assert!(initial_fingerprint == proof_stream.sponge_fingerprint());
+        assert!(initial_sponge_state == proof_stream.sponge.state);
+
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(log_2_padded_height));
+        assert!(initial_fingerprint == proof_stream.sponge_fingerprint());
+
+        proof_stream.enqueue(ProofItem::MerkleRoot(merkle_root));
+        assert!(initial_fingerprint != proof_stream.sponge_fingerprint());
+    }
+
+    #[proptest]
+    fn restoring_checkpoint_reproduces_sampled_challenges(
+        #[strategy(arb())] merkle_root: Digest,
+        #[strategy(arb())] other_merkle_root: Digest,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::MerkleRoot(merkle_root));
+        proof_stream.enqueue(ProofItem::MerkleRoot(other_merkle_root));
+        let proof = proof_stream.into();
+        let mut proof_stream = ProofStream::try_from(&proof).unwrap();
+
+        proof_stream.dequeue().unwrap();
+        let first_challenges = proof_stream.sample_scalars(3);
+
+        let checkpoint = proof_stream.fiat_shamir_checkpoint();
+        let second_challenges = proof_stream.sample_scalars(3);
+        let_assert!(Ok(ProofItem::MerkleRoot(root)) = proof_stream.dequeue());
+        assert!(other_merkle_root == root);
+        assert!(first_challenges != second_challenges);
+
+        proof_stream.restore_checkpoint(checkpoint);
+        assert!(second_challenges == proof_stream.sample_scalars(3));
+        let_assert!(Ok(ProofItem::MerkleRoot(root)) = proof_stream.dequeue());
+        assert!(other_merkle_root == root);
+    }
+
+    #[proptest]
+    fn dequeueing_by_reference_and_by_value_advance_index_and_sponge_alike(
+        #[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>,
+    ) {
+        let mut owning_proof_stream = ProofStream::new();
+        owning_proof_stream.items = items.clone();
+        let mut borrowing_proof_stream = owning_proof_stream.clone();
+        let mut expected_sponge = owning_proof_stream.sponge.clone();
+
+        for (num_dequeued_items, item) in (1..).zip(&items) {
+            if item.include_in_fiat_shamir_heuristic() {
+                expected_sponge.pad_and_absorb_all(&item.encode());
+            }
+
+            let_assert!(Ok(owned_item) = owning_proof_stream.dequeue());
+            assert!(item == &owned_item);
+            assert!(num_dequeued_items == owning_proof_stream.items_index);
+            assert!(expected_sponge.state == owning_proof_stream.sponge.state);
+
+            let_assert!(Ok(borrowed_item) = borrowing_proof_stream.dequeue_ref());
+            assert!(item == borrowed_item);
+            assert!(num_dequeued_items == borrowing_proof_stream.items_index);
+            assert!(expected_sponge.state == borrowing_proof_stream.sponge.state);
+        }
+        assert!(let Err(ProofStreamError::EmptyQueue) = owning_proof_stream.dequeue());
+        assert!(let Err(ProofStreamError::EmptyQueue) = borrowing_proof_stream.dequeue_ref());
+    }
+
+    #[proptest]
+    fn resetting_allows_identical_second_pass(
+        #[strategy(vec(arb(), 0..10))] tag: Vec<BFieldElement>,
+        #[strategy(vec(arb(), 1..10))] merkle_roots: Vec<Digest>,
+    ) {
+        let mut proof_stream = ProofStream::with_domain_separator(&tag);
+        proof_stream.items = merkle_roots
+            .into_iter()
+            .map(ProofItem::MerkleRoot)
+            .collect();
+
+        let dequeue_all = |proof_stream: &mut ProofStream| {
+            let mut items_and_fingerprints = vec![];
+            while let Ok(item) = proof_stream.dequeue() {
+                items_and_fingerprints.push((item, proof_stream.sponge_fingerprint()));
+            }
+            items_and_fingerprints
+        };
+
+        let first_pass = dequeue_all(&mut proof_stream);
+        assert!(proof_stream.items.len() == first_pass.len());
+
+        proof_stream.reset();
+        let second_pass = dequeue_all(&mut proof_stream);
+        assert!(first_pass == second_pass);
+    }
+
+    #[proptest]
+    fn fast_proof_decoding_agrees_with_derived_decoding(
+        #[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue_many(items);
+        let proof = proof_stream.into();
+
+        let_assert!(Ok(reference) = ProofStream::try_from(&proof));
+        let_assert!(Ok(fast) = ProofStream::try_from_proof_fast(&proof));
+        assert!(reference == fast);
+    }
+
+    #[proptest]
+    fn fast_proof_decoding_agrees_with_derived_decoding_on_corrupted_proofs(
+        #[strategy(vec(arb(), 1..10))] items: Vec<ProofItem>,
+        #[strategy(arb())] corruption_index: usize,
+        #[strategy(arb())] corruption: BFieldElement,
+        truncate: bool,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue_many(items);
+        let Proof(mut encoding) = proof_stream.into();
+
+        let corruption_index = corruption_index % encoding.len();
+        if truncate {
+            encoding.truncate(corruption_index);
+        } else {
+            encoding[corruption_index] = corruption;
+        }
+        let proof = Proof(encoding);
+
+        let reference = ProofStream::try_from(&proof);
+        let fast = ProofStream::try_from_proof_fast(&proof);
+        assert!(reference.is_ok() == fast.is_ok());
+        if let (Ok(reference), Ok(fast)) = (reference, fast) {
+            assert!(reference == fast);
+        }
+    }
+
+    #[test]
+    fn empty_domain_separator_is_the_default() {
+        assert!(ProofStream::new() == ProofStream::with_domain_separator(&[]));
+    }
+
+    #[proptest]
+    fn distinct_domain_separators_result_in_distinct_challenges(
+        #[strategy(vec(arb(), 1..10))] tag: Vec<BFieldElement>,
+        #[strategy(vec(arb(), 1..10))]
+        #[filter(#tag != #other_tag)]
+        other_tag: Vec<BFieldElement>,
+    ) {
+        let mut proof_stream = ProofStream::with_domain_separator(&tag);
+        let mut other_proof_stream = ProofStream::with_domain_separator(&other_tag);
+        let mut default_proof_stream = ProofStream::new();
+
+        let challenges = proof_stream.sample_scalars(3);
+        assert!(challenges != other_proof_stream.sample_scalars(3));
+        assert!(challenges != default_proof_stream.sample_scalars(3));
+    }
+
+    #[test]
+    fn proof_created_under_one_domain_separator_fails_verification_under_another() {
+        let domain = ArithmeticDomain::of_length(1 << 8).unwrap();
+        let fri = Fri::<Tip5>::new(domain, 4, 16).unwrap();
+        let codeword = domain.evaluate(&Polynomial::new(random_elements(1 << 6)));
+
+        let prover_tag = bfe_array![1, 2, 3];
+        let mut prover_proof_stream = ProofStream::with_domain_separator(&prover_tag);
+        fri.prove(&codeword, &mut prover_proof_stream).unwrap();
+        let proof = Proof::from(prover_proof_stream);
+
+        let verifier_proof_stream_with_tag = |tag: &[BFieldElement]| {
+            ProofStream::try_from_proof_with_domain_separator(&proof, tag).unwrap()
+        };
+
+        let mut proof_stream = verifier_proof_stream_with_tag(&prover_tag);
+        assert!(let Ok(_) = fri.verify(&mut proof_stream));
+
+        let mut proof_stream = verifier_proof_stream_with_tag(&bfe_array![1, 2, 4]);
+        assert!(let Err(_) = fri.verify(&mut proof_stream));
+
+        let mut proof_stream = verifier_proof_stream_with_tag(&[]);
+        assert!(let Err(_) = fri.verify(&mut proof_stream));
+    }
+
+    fn proof_as_bytes(proof: &Proof) -> Vec<u8> {
+        proof
+            .0
+            .iter()
+            .flat_map(|word| word.value().to_le_bytes())
+            .collect()
+    }
+
+    #[proptest]
+    fn streaming_a_proof_yields_same_items_and_challenges_as_decoding_it(
+        #[strategy(arb())] items: Vec<ProofItem>,
+    ) {
+        let mut prover_proof_stream = ProofStream::new();
+        prover_proof_stream.enqueue_many(items.clone());
+        let proof = Proof::from(prover_proof_stream);
+
+        let mut in_memory_proof_stream = ProofStream::try_from(&proof).unwrap();
+        let mut streaming_proof_stream = ProofStream::new();
+        let bytes = proof_as_bytes(&proof);
+        let mut reader = bytes.as_slice();
+
+        for _ in 0..items.len() {
+            let_assert!(Ok(in_memory_item) = in_memory_proof_stream.dequeue());
+            let_assert!(
+                Ok(Some(streamed_item)) = streaming_proof_stream.decode_next_from(&mut reader)
+            );
+            assert!(in_memory_item == streamed_item);
+            assert!(in_memory_proof_stream.sponge == streaming_proof_stream.sponge);
+        }
+        assert!(let Ok(None) = streaming_proof_stream.decode_next_from(&mut reader));
+        assert!(let Ok(None) = streaming_proof_stream.decode_next_from(&mut reader));
+        assert!(reader.is_empty());
+
+        assert!(in_memory_proof_stream.items == streaming_proof_stream.items);
+        assert!(in_memory_proof_stream.items_index == streaming_proof_stream.items_index);
+    }
+
+    #[test]
+    fn streaming_a_truncated_proof_fails() {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(8));
+        proof_stream.enqueue(ProofItem::MerkleRoot(Digest::default()));
+        let bytes = proof_as_bytes(&proof_stream.into());
+        let mut reader = &bytes[..bytes.len() - 1];
+
+        let mut proof_stream = ProofStream::new();
+        assert!(let Ok(Some(_)) = proof_stream.decode_next_from(&mut reader));
+        let_assert!(Err(err) = proof_stream.decode_next_from(&mut reader));
+        assert!(let ProofStreamError::Truncated = err);
+    }
+
+    #[test]
+    fn streaming_a_too_large_item_fails_before_reading_the_item() {
+        let codeword = ProofItem::FriCodeword(vec![xfe!(1); 100]);
+        let size_of_codeword = codeword.byte_size();
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(codeword);
+        let bytes = proof_as_bytes(&proof_stream.into());
+
+        // header, encoding length, number of items, and the item's length indicator
+        let num_words_before_item = PROOF_HEADER_LEN + 3;
+        let mut reader = &bytes[..num_words_before_item * BFieldElement::BYTES];
+
+        let mut proof_stream = ProofStream::new();
+        let max_item_bytes = size_of_codeword - 1;
+        let_assert!(Err(err) = proof_stream.decode_next_bounded_from(&mut reader, max_item_bytes));
+        let_assert!(ProofStreamError::ItemTooLarge { size, max_size } = err);
+        assert!(size_of_codeword == size);
+        assert!(max_item_bytes == max_size);
+    }
+
+    #[test]
+    fn bounded_decoding_of_proof_with_too_large_item_fails() {
+        let codeword = ProofItem::FriCodeword(vec![xfe!(1); 100]);
+        let size_of_codeword = codeword.byte_size();
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::MerkleRoot(Digest::default()));
+        proof_stream.enqueue(codeword);
+        let proof = proof_stream.into();
+
+        let_assert!(Ok(bounded) = ProofStream::try_from_proof_bounded(&proof, size_of_codeword));
+        assert!(ProofStream::try_from(&proof).unwrap() == bounded);
+
+        let max_item_bytes = size_of_codeword - 1;
+        let_assert!(Err(err) = ProofStream::try_from_proof_bounded(&proof, max_item_bytes));
+        let_assert!(ProofStreamError::ItemTooLarge { size, max_size } = err);
+        assert!(size_of_codeword == size);
+        assert!(max_item_bytes == max_size);
+    }
+
+    #[test]
+    fn streaming_a_proof_with_non_canonical_word_fails() {
+        let bytes = u64::MAX.to_le_bytes();
+        let_assert!(Err(err) = ProofStream::new().decode_next_from(&mut bytes.as_slice()));
+        assert!(let ProofStreamError::NonCanonicalWord(u64::MAX) = err);
+    }
+
+    #[cfg(feature = "serde")]
+    #[proptest]
+    fn proof_stream_round_trips_through_bincode(
+        #[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>,
+        #[strategy(0_usize..10)] num_dequeued_items: usize,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue_many(items.clone());
+        proof_stream.items_index = num_dequeued_items.min(items.len());
+
+        let_assert!(Ok(serialized) = bincode::serialize(&proof_stream));
+        let_assert!(Ok(deserialized) = bincode::deserialize::<ProofStream>(&serialized));
+        assert!(items == deserialized.items);
+        assert!(0 == deserialized.items_index);
+        assert!(ProofStream::new().sponge == deserialized.sponge);
+    }
+
+    #[proptest]
+    fn proof_round_trips_through_bincode(#[strategy(vec(arb(), 0..10))] items: Vec<ProofItem>) {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue_many(items);
+        let proof = Proof::from(proof_stream);
+
+        let_assert!(Ok(serialized) = bincode::serialize(&proof));
+        let_assert!(Ok(deserialized) = bincode::deserialize::<Proof>(&serialized));
+        assert!(proof == deserialized);
+    }
+
     #[test]
     fn encoded_length_of_prove_stream_is_not_known_at_compile_time() {
-        assert!(ProofStream::static_length().is_none());
+        assert!(<ProofStream>::static_length().is_none());
     }
 }
@@ -8,16 +8,13 @@ use test_strategy::Arbitrary;
 use twenty_first::prelude::*;
 
 use crate::aet::AlgebraicExecutionTrace;
-use crate::error::VMError;
 use crate::fri::AuthenticationStructure;
 use crate::profiler::profiler;
-use crate::program::Program;
+use crate::program::ProgramAndInput;
 use crate::proof::Claim;
 use crate::proof_item::FriResponse;
 use crate::stark::Stark;
 use crate::table::master_table::MasterBaseTable;
-use crate::NonDeterminism;
-use crate::PublicInput;
 
 pub(crate) const DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS: usize = 2;
 
@@ -159,47 +156,3 @@ pub(crate) fn construct_master_base_table(
         fri.domain,
     )
 }
-
-/// Program and associated inputs.
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub(crate) struct ProgramAndInput {
-    pub program: Program,
-    pub public_input: PublicInput,
-    pub non_determinism: NonDeterminism,
-}
-
-impl ProgramAndInput {
-    pub fn new(program: Program) -> Self {
-        Self {
-            program,
-            public_input: PublicInput::default(),
-            non_determinism: NonDeterminism::default(),
-        }
-    }
-
-    #[must_use]
-    pub fn with_input<PI: Into<PublicInput>>(mut self, public_input: PI) -> Self {
-        self.public_input = public_input.into();
-        self
-    }
-
-    #[must_use]
-    pub fn with_non_determinism<ND: Into<NonDeterminism>>(mut self, non_determinism: ND) -> Self {
-        self.non_determinism = non_determinism.into();
-        self
-    }
-
-    pub fn public_input(&self) -> PublicInput {
-        self.public_input.clone()
-    }
-
-    pub fn non_determinism(&self) -> NonDeterminism {
-        self.non_determinism.clone()
-    }
-
-    /// A thin wrapper around [`Program::run`].
-    pub fn run(&self) -> Result<Vec<BFieldElement>, VMError> {
-        self.program
-            .run(self.public_input(), self.non_determinism())
-    }
-}
@@ -1,5 +1,11 @@
+use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io::Write;
 use std::ops::Mul;
 use std::ops::MulAssign;
+use std::time::Duration;
+use std::time::Instant;
 
 use arbitrary::Arbitrary;
 use arbitrary::Unstructured;
@@ -8,35 +14,60 @@ use itertools::Itertools;
 use ndarray::prelude::*;
 use ndarray::Zip;
 use num_traits::Zero;
+use rand::thread_rng;
+use rand::Rng;
 use rayon::prelude::*;
 use serde::Deserialize;
 use serde::Serialize;
+use strum::IntoEnumIterator;
 use twenty_first::math::ntt::intt;
 use twenty_first::math::traits::FiniteField;
 use twenty_first::math::traits::PrimitiveRootOfUnity;
 use twenty_first::prelude::*;
 
 use crate::aet::AlgebraicExecutionTrace;
+use crate::aet::TableHeight;
 use crate::arithmetic_domain::ArithmeticDomain;
+use crate::error::ProofStreamError;
 use crate::error::ProvingError;
+use crate::error::StarkParameterError;
+use crate::error::VMError;
 use crate::error::VerificationError;
 use crate::fri;
 use crate::fri::Fri;
 use crate::profiler::profiler;
+use crate::program::NonDeterminism;
+use crate::program::Program;
+use crate::program::PublicInput;
+use crate::program::VMTableHeights;
 use crate::proof::Claim;
 use crate::proof::Proof;
 use crate::proof_item::ProofItem;
+use crate::proof_item::ProofItemVariant;
 use crate::proof_stream::ProofStream;
+use crate::table::cascade_table::ExtCascadeTable;
 use crate::table::challenges::Challenges;
+use crate::table::constraint_circuit::ConstraintCircuitBuilder;
+use crate::table::constraint_circuit::ConstraintCircuitMonad;
+use crate::table::constraint_circuit::InputIndicator;
 use crate::table::extension_table::Evaluable;
 use crate::table::extension_table::Quotientable;
+use crate::table::hash_table::ExtHashTable;
+use crate::table::jump_stack_table::ExtJumpStackTable;
+use crate::table::lookup_table::ExtLookupTable;
 use crate::table::master_table::all_quotients_combined;
 use crate::table::master_table::interpolant_degree;
 use crate::table::master_table::max_degree_with_origin;
 use crate::table::master_table::MasterBaseTable;
 use crate::table::master_table::MasterExtTable;
 use crate::table::master_table::MasterTable;
+use crate::table::master_table::TableId;
 use crate::table::master_table::AIR_TARGET_DEGREE;
+use crate::table::op_stack_table::ExtOpStackTable;
+use crate::table::processor_table::ExtProcessorTable;
+use crate::table::program_table::ExtProgramTable;
+use crate::table::ram_table::ExtRamTable;
+use crate::table::u32_table::ExtU32Table;
 use crate::table::QuotientSegments;
 use crate::table::NUM_BASE_COLUMNS;
 use crate::table::NUM_EXT_COLUMNS;
@@ -85,8 +116,40 @@ impl Stark {
         );
 
         let fri_expansion_factor = 1 << log2_of_fri_expansion_factor;
-        let num_collinearity_checks = security_level / log2_of_fri_expansion_factor;
+        let num_collinearity_checks = security_level.div_ceil(log2_of_fri_expansion_factor);
 
+        Self::with_parameters(
+            security_level,
+            fri_expansion_factor,
+            num_collinearity_checks,
+        )
+    }
+
+    /// Like [`new`](Self::new), but with a custom FRI expansion factor and number of
+    /// collinearity checks, trading off prover performance against proof size.
+    ///
+    /// Every collinearity check contributes log₂ of the FRI expansion factor bits of security.
+    /// Returns an error if the parameters are invalid or do not achieve the requested security
+    /// level.
+    pub fn try_new(
+        security_level: usize,
+        fri_expansion_factor: usize,
+        num_collinearity_checks: usize,
+    ) -> Result<Self, StarkParameterError> {
+        let stark = Self::with_parameters(
+            security_level,
+            fri_expansion_factor,
+            num_collinearity_checks,
+        );
+        stark.validate_parameters()?;
+        Ok(stark)
+    }
+
+    fn with_parameters(
+        security_level: usize,
+        fri_expansion_factor: usize,
+        num_collinearity_checks: usize,
+    ) -> Self {
         let num_out_of_domain_rows = 2;
         let num_trace_randomizers = num_collinearity_checks
             + num_out_of_domain_rows * x_field_element::EXTENSION_DEGREE
@@ -100,13 +163,279 @@ impl Stark {
         }
     }
 
+    /// Check that the FRI expansion factor is valid and that, together with the number of
+    /// collinearity checks, it achieves the [security level](Self::security_level).
+    pub fn validate_parameters(&self) -> Result<(), StarkParameterError> {
+        if self.fri_expansion_factor <= 1 {
+            return Err(StarkParameterError::FriExpansionFactorTooSmall);
+        }
+        if !self.fri_expansion_factor.is_power_of_two() {
+            return Err(StarkParameterError::FriExpansionFactorUnsupported);
+        }
+
+        if self.achieved_security_level() < self.security_level {
+            return Err(StarkParameterError::InsufficientSecurity {
+                security_level: self.security_level,
+                fri_expansion_factor: self.fri_expansion_factor,
+                num_collinearity_checks: self.num_collinearity_checks,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The security level in bits that the FRI parameters actually achieve. Every
+    /// collinearity check contributes log₂ of the [FRI expansion factor] bits of security.
+    /// For [valid](Self::validate_parameters) parameters, this is at least the
+    /// [targeted security level](Self::security_level).
+    ///
+    /// [FRI expansion factor]: Self::fri_expansion_factor
+    pub fn achieved_security_level(&self) -> usize {
+        let bits_per_collinearity_check = self.fri_expansion_factor.checked_ilog2().unwrap_or(0);
+        self.num_collinearity_checks * bits_per_collinearity_check as usize
+    }
+
+    /// The maximal degree of each table's AIR constraints, across all initial, consistency,
+    /// transition, and terminal constraints. The degrees are those before
+    /// [degree lowering](crate::table::degree_lowering_table), and thus indicate how far each
+    /// table exceeds the [`AIR_TARGET_DEGREE`].
+    ///
+    /// The [degree lowering table](TableId::DegreeLowering) has no constraints of its own and is
+    /// not included.
+    pub fn max_constraint_degree_by_table() -> BTreeMap<TableId, usize> {
+        fn max_degree<II: InputIndicator>(constraints: Vec<ConstraintCircuitMonad<II>>) -> isize {
+            let degrees = constraints.into_iter().map(|c| c.consume().degree());
+            degrees.max().unwrap_or(0)
+        }
+
+        macro_rules! max_constraint_degree {
+            ($table:ident) => {{
+                let degrees = [
+                    max_degree($table::initial_constraints(&ConstraintCircuitBuilder::new())),
+                    max_degree($table::consistency_constraints(
+                        &ConstraintCircuitBuilder::new(),
+                    )),
+                    max_degree($table::transition_constraints(
+                        &ConstraintCircuitBuilder::new(),
+                    )),
+                    max_degree($table::terminal_constraints(
+                        &ConstraintCircuitBuilder::new(),
+                    )),
+                ];
+                let max_degree = degrees.into_iter().max().unwrap_or(0);
+                usize::try_from(max_degree).unwrap_or(0)
+            }};
+        }
+
+        [
+            (TableId::Program, max_constraint_degree!(ExtProgramTable)),
+            (
+                TableId::Processor,
+                max_constraint_degree!(ExtProcessorTable),
+            ),
+            (TableId::OpStack, max_constraint_degree!(ExtOpStackTable)),
+            (TableId::Ram, max_constraint_degree!(ExtRamTable)),
+            (
+                TableId::JumpStack,
+                max_constraint_degree!(ExtJumpStackTable),
+            ),
+            (TableId::Hash, max_constraint_degree!(ExtHashTable)),
+            (TableId::Cascade, max_constraint_degree!(ExtCascadeTable)),
+            (TableId::Lookup, max_constraint_degree!(ExtLookupTable)),
+            (TableId::U32, max_constraint_degree!(ExtU32Table)),
+        ]
+        .into_iter()
+        .collect()
+    }
+
     pub fn prove(
         &self,
         claim: &Claim,
         aet: &AlgebraicExecutionTrace,
     ) -> Result<Proof, ProvingError> {
-        profiler!(start "Fiat-Shamir: claim" ("hash"));
+        self.prove_with_rng(claim, aet, &mut thread_rng())
+    }
+
+    /// Like [`prove`](Self::prove), but samples the prover's randomness, _i.e._, the trace
+    /// randomizers and the randomizer polynomials, from the given random number generator. With
+    /// a seeded random number generator, the resulting proof is reproducible, which is useful
+    /// for testing and debugging.
+    ///
+    /// Zero-knowledge relies on the prover's randomness being unpredictable. Outside of tests,
+    /// prefer [`prove`](Self::prove).
+    pub fn prove_with_rng(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        rng: &mut impl Rng,
+    ) -> Result<Proof, ProvingError> {
+        self.prove_into_proof_stream(claim, aet, ProofStream::new(), rng)
+    }
+
+    /// Like [`prove`](Self::prove), but the proof's Fiat-Shamir state is bound to the given
+    /// [domain-separation tag](ProofStream::with_domain_separator). The resulting proof only
+    /// verifies using [`verify_with_domain_separator`](Self::verify_with_domain_separator) and
+    /// the same tag.
+    ///
+    /// The empty tag is equivalent to no tag, _i.e._, to [`prove`](Self::prove).
+    pub fn prove_with_domain_separator(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        tag: &[BFieldElement],
+    ) -> Result<Proof, ProvingError> {
+        let proof_stream = ProofStream::with_domain_separator(tag);
+        self.prove_into_proof_stream(claim, aet, proof_stream, &mut thread_rng())
+    }
+
+    /// Like [`prove`](Self::prove), but every [proof item](ProofItem) is written to the given
+    /// writer as soon as it is produced instead of being kept in memory until proving finishes.
+    /// The written items are framed and can be read using
+    /// [`ProofStream::decode_next_framed_from`].
+    pub fn prove_to_writer(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        writer: &mut impl Write,
+    ) -> Result<(), ProvingError> {
+        let write_items = |proof_stream: &mut ProofStream| {
+            proof_stream
+                .write_framed_items(writer)
+                .map_err(ProvingError::from)
+        };
+        let mut rng = thread_rng();
+        self.prove_with_item_sink(claim, aet, ProofStream::new(), &mut rng, write_items)?;
+        Ok(())
+    }
+
+    /// Like [`prove`](Self::prove), but the resulting proof carries the [`Claim`] as its first
+    /// item. Such a proof can be verified using [`verify_self_claimed`](Self::verify_self_claimed)
+    /// without transmitting the claim separately.
+    pub fn prove_self_claimed(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+    ) -> Result<Proof, ProvingError> {
         let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Claim(claim.clone()));
+        self.prove_into_proof_stream(claim, aet, proof_stream, &mut thread_rng())
+    }
+
+    /// Run only the trace generation phase of [proving](Self::prove): execute the program,
+    /// create the [master base table](MasterBaseTable), and pad it. Extension, quotient
+    /// computation, and [FRI](Fri) are skipped. This allows measuring the cost of trace
+    /// generation in isolation, for example, for profiling or memory planning.
+    ///
+    /// Like [`prove`](crate::prove), fails if the program's digest, its public input, or its
+    /// public output do not match the claim.
+    pub fn prove_trace_only(
+        &self,
+        claim: &Claim,
+        program: &Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<TraceReport, ProvingError> {
+        if program.hash() != claim.program_digest {
+            return Err(ProvingError::ProgramDigestMismatch);
+        }
+        if public_input.individual_tokens != claim.input {
+            return Err(ProvingError::PublicInputMismatch);
+        }
+
+        let start = Instant::now();
+        let (aet, public_output) = program.trace_execution(public_input, non_determinism)?;
+        if public_output != claim.output {
+            return Err(ProvingError::PublicOutputMismatch);
+        }
+
+        let padded_height = aet.padded_height();
+        let max_degree = self.derive_max_degree(padded_height);
+        let fri = self.derive_fri(padded_height)?;
+        let quotient_domain = Self::quotient_domain(fri.domain, max_degree)?;
+        let mut master_base_table = MasterBaseTable::new(
+            &aet,
+            self.num_trace_randomizers,
+            quotient_domain,
+            fri.domain,
+        );
+        master_base_table.pad();
+        let duration = start.elapsed();
+
+        let randomized_trace_table = master_base_table.randomized_trace_table();
+        let base_table_memory = randomized_trace_table.len() * std::mem::size_of::<BFieldElement>();
+
+        let table_heights = TableId::iter()
+            .filter(|&table| table != TableId::DegreeLowering)
+            .map(|table| TableHeight {
+                table,
+                height: aet.height_of_table(table),
+            })
+            .collect();
+        let report = TraceReport {
+            table_heights,
+            padded_height,
+            num_base_rows: randomized_trace_table.nrows(),
+            num_base_columns: randomized_trace_table.ncols(),
+            base_table_memory,
+            duration,
+        };
+        Ok(report)
+    }
+
+    /// Execute the program and [prove](Self::prove) its correct execution, using the given
+    /// [hints](ProvingHints) to speed up proving. Unlike [`prove`](Self::prove), this function
+    /// takes care of program execution, much like [`prove_program`](crate::prove_program).
+    ///
+    /// Wrong hints do not affect the resulting proof, only the prover's performance. Fails if the
+    /// program's digest, its public input, or its public output do not match the claim.
+    pub fn prove_with_hints(
+        &self,
+        claim: &Claim,
+        program: &Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+        hints: ProvingHints,
+    ) -> Result<Proof, ProvingError> {
+        if program.hash() != claim.program_digest {
+            return Err(ProvingError::ProgramDigestMismatch);
+        }
+        if public_input.individual_tokens != claim.input {
+            return Err(ProvingError::PublicInputMismatch);
+        }
+
+        let table_heights = hints.table_heights.unwrap_or_default();
+        let (aet, public_output) =
+            program.trace_execution_with_capacity(public_input, non_determinism, table_heights)?;
+        if public_output != claim.output {
+            return Err(ProvingError::PublicOutputMismatch);
+        }
+
+        self.prove(claim, &aet)
+    }
+
+    fn prove_into_proof_stream(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        proof_stream: ProofStream,
+        rng: &mut impl Rng,
+    ) -> Result<Proof, ProvingError> {
+        let keep_items = |_: &mut ProofStream| Ok(());
+        let proof_stream = self.prove_with_item_sink(claim, aet, proof_stream, rng, keep_items)?;
+        Ok(proof_stream.into())
+    }
+
+    /// The prover. Whenever a batch of [proof items](ProofItem) has been enqueued, the proof
+    /// stream is handed to `flush_items`, which may remove the items from it.
+    fn prove_with_item_sink(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        mut proof_stream: ProofStream,
+        rng: &mut impl Rng,
+        mut flush_items: impl FnMut(&mut ProofStream) -> Result<(), ProvingError>,
+    ) -> Result<ProofStream, ProvingError> {
+        profiler!(start "Fiat-Shamir: claim" ("hash"));
         proof_stream.alter_fiat_shamir_state_with(claim);
         profiler!(stop "Fiat-Shamir: claim");
 
@@ -129,7 +458,7 @@ impl Stark {
         profiler!(stop "pad");
 
         profiler!(start "randomize trace" ("gen"));
-        master_base_table.randomize_trace();
+        master_base_table.randomize_trace_with_rng(rng);
         profiler!(stop "randomize trace");
 
         profiler!(start "LDE" ("LDE"));
@@ -142,18 +471,18 @@ impl Stark {
 
         profiler!(start "Fiat-Shamir" ("hash"));
         proof_stream.enqueue(ProofItem::MerkleRoot(base_merkle_tree.root()));
-        let challenges = proof_stream.sample_scalars(Challenges::SAMPLE_COUNT);
-        let challenges = Challenges::new(challenges, claim);
+        flush_items(&mut proof_stream)?;
+        let challenges = Challenges::sample_from_proof_stream(&mut proof_stream, claim);
         profiler!(stop "Fiat-Shamir");
 
         profiler!(start "extend" ("gen"));
-        let mut master_ext_table = master_base_table.extend(&challenges);
+        let mut master_ext_table = master_base_table.extend_with_rng(&challenges, rng);
         profiler!(stop "extend");
         profiler!(stop "base tables");
 
         profiler!(start "ext tables");
         profiler!(start "randomize trace" ("gen"));
-        master_ext_table.randomize_trace();
+        master_ext_table.randomize_trace_with_rng(rng);
         profiler!(stop "randomize trace");
 
         profiler!(start "LDE" ("LDE"));
@@ -166,6 +495,7 @@ impl Stark {
 
         profiler!(start "Fiat-Shamir" ("hash"));
         proof_stream.enqueue(ProofItem::MerkleRoot(ext_merkle_tree.root()));
+        flush_items(&mut proof_stream)?;
 
         // Get the weights with which to compress the many quotients into one.
         let quotient_combination_weights =
@@ -200,6 +530,7 @@ impl Stark {
             CpuParallel::from_digests(&fri_domain_quotient_segment_codewords_digests)?;
         let quot_merkle_tree_root = quot_merkle_tree.root();
         proof_stream.enqueue(ProofItem::MerkleRoot(quot_merkle_tree_root));
+        flush_items(&mut proof_stream)?;
         profiler!(stop "Merkle tree");
 
         debug_assert_eq!(fri.domain.length, quot_merkle_tree.num_leafs());
@@ -235,6 +566,7 @@ impl Stark {
         proof_stream.enqueue(ProofItem::OutOfDomainQuotientSegments(
             out_of_domain_curr_row_quot_segments,
         ));
+        flush_items(&mut proof_stream)?;
         profiler!(stop "out-of-domain rows");
 
         profiler!(start "Fiat-Shamir" ("hash"));
@@ -356,6 +688,7 @@ impl Stark {
             self.num_collinearity_checks,
             revealed_current_row_indices.len()
         );
+        flush_items(&mut proof_stream)?;
         profiler!(stop "FRI");
 
         profiler!(start "open trace leafs");
@@ -410,9 +743,10 @@ impl Stark {
         proof_stream.enqueue(ProofItem::AuthenticationStructure(
             revealed_quotient_authentication_structure,
         ));
+        flush_items(&mut proof_stream)?;
         profiler!(stop "open trace leafs");
 
-        Ok(proof_stream.into())
+        Ok(proof_stream)
     }
 
     fn compute_quotient_segments(
@@ -570,17 +904,42 @@ impl Stark {
     /// (see [`AIR_TARGET_DEGREE`]). However, by segmenting the quotient polynomial into
     /// [`AIR_TARGET_DEGREE`]-many parts, that influence is mitigated.
     pub fn derive_fri(&self, padded_height: usize) -> fri::SetupResult<Fri<Tip5>> {
+        Fri::new(
+            self.fri_domain(padded_height)?,
+            self.fri_expansion_factor,
+            self.num_collinearity_checks,
+        )
+    }
+
+    /// The domain FRI operates on, given the length of the padded execution trace. Its length is
+    /// the [FRI expansion factor](Self::fri_expansion_factor) times the length of the
+    /// randomized trace, which includes the [trace randomizers](Self::num_trace_randomizers).
+    /// Contains everything needed to reproduce the domain, for example in an external verifier.
+    ///
+    /// See also [`derive_fri`](Self::derive_fri).
+    pub fn fri_domain(&self, padded_height: usize) -> fri::SetupResult<ArithmeticDomain> {
         let interpolant_degree = interpolant_degree(padded_height, self.num_trace_randomizers);
         let interpolant_codeword_length = interpolant_degree as usize + 1;
         let fri_domain_length = self.fri_expansion_factor * interpolant_codeword_length;
         let coset_offset = BFieldElement::generator();
         let domain = ArithmeticDomain::of_length(fri_domain_length)?.with_offset(coset_offset);
+        Ok(domain)
+    }
 
-        Fri::new(
-            domain,
-            self.fri_expansion_factor,
-            self.num_collinearity_checks,
-        )
+    /// The number of rows that need to be added to the processor trace of the given program to
+    /// reach the [padded height](AlgebraicExecutionTrace::padded_height). Gives an indication of
+    /// the proving overhead incurred by [padding][pad].
+    ///
+    /// Executes the program, which is why the error of a failing execution is propagated.
+    ///
+    /// [pad]: MasterBaseTable::pad
+    pub fn num_padding_rows(
+        program: &Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<usize, VMError> {
+        let (aet, _) = program.trace_execution(public_input, non_determinism)?;
+        Ok(aet.padded_height() - aet.processor_trace.nrows())
     }
 
     /// Read the indicated rows from the cached table. The indices come from FRI.
@@ -723,10 +1082,171 @@ impl Stark {
     }
 
     pub fn verify(&self, claim: &Claim, proof: &Proof) -> Result<(), VerificationError> {
+        self.verify_with_domain_separator(claim, proof, &[])
+    }
+
+    /// Verify a proof generated by
+    /// [`prove_with_domain_separator`](Self::prove_with_domain_separator). Fails if the proof
+    /// was created under a different domain-separation tag.
+    pub fn verify_with_domain_separator(
+        &self,
+        claim: &Claim,
+        proof: &Proof,
+        tag: &[BFieldElement],
+    ) -> Result<(), VerificationError> {
+        profiler!(start "deserialize");
+        let proof_stream = ProofStream::try_from_proof_with_domain_separator(proof, tag)?;
+        profiler!(stop "deserialize");
+
+        self.verify_proof_stream(claim, proof_stream, &mut HashMap::new())
+    }
+
+    /// Verify multiple proofs at once. Succeeds if and only if every proof verifies against its
+    /// claim. Otherwise, returns the error of the first proof that fails verification.
+    ///
+    /// This is a convenience around calling [`verify`](Self::verify) for each proof. Only the
+    /// [FRI](Fri) setup is reused for proofs of the same padded height, which is cheap compared to
+    /// the remaining verification work. In particular, the low-degree tests are not combined:
+    /// each proof's FRI is verified separately. Verifying a batch therefore costs about as much
+    /// as verifying each of its proofs individually.
+    pub fn verify_batch(&self, claim_proofs: &[(Claim, Proof)]) -> Result<(), VerificationError> {
+        let mut fri_cache = HashMap::new();
+        for (claim, proof) in claim_proofs {
+            profiler!(start "deserialize");
+            let proof_stream = ProofStream::try_from(proof)?;
+            profiler!(stop "deserialize");
+
+            self.verify_proof_stream(claim, proof_stream, &mut fri_cache)?;
+        }
+        Ok(())
+    }
+
+    /// Verify a proof generated by [`prove_self_claimed`](Self::prove_self_claimed). If
+    /// verification succeeds, returns the [`Claim`] embedded in the proof.
+    pub fn verify_self_claimed(&self, proof: &Proof) -> Result<Claim, VerificationError> {
         profiler!(start "deserialize");
         let mut proof_stream = ProofStream::try_from(proof)?;
         profiler!(stop "deserialize");
 
+        let claim = proof_stream.dequeue()?.try_into_claim()?;
+        self.verify_proof_stream(&claim, proof_stream, &mut HashMap::new())?;
+        Ok(claim)
+    }
+
+    /// Cheaply check that the proof has the shape [verification](Self::verify) expects, without
+    /// any of the hashing-heavy work. Concretely, the proof must start with a
+    /// [`Log2PaddedHeight`](ProofItem::Log2PaddedHeight), followed by the number and order of
+    /// proof items expected for that padded height, including the [FRI](Fri) rounds. If the
+    /// proof is [self-claimed](Self::prove_self_claimed), the embedded claim must match the
+    /// given one.
+    ///
+    /// Passing this check does not imply that the proof is valid. However, it allows rejecting
+    /// malformed proofs early.
+    pub fn check_proof_shape(&self, claim: &Claim, proof: &Proof) -> Result<(), VerificationError> {
+        let proof_stream = ProofStream::try_from(proof)?;
+        let mut items = proof_stream.items.as_slice();
+        if let Some(ProofItem::Claim(embedded_claim)) = items.first() {
+            if embedded_claim != claim {
+                return Err(VerificationError::ClaimMismatch);
+            }
+            items = &items[1..];
+        }
+
+        let log2_padded_height = match items.first() {
+            Some(&ProofItem::Log2PaddedHeight(log2_padded_height)) => log2_padded_height,
+            Some(item) => {
+                return Err(ProofStreamError::UnexpectedItem {
+                    expected: ProofItemVariant::Log2PaddedHeight,
+                    got: item.clone(),
+                }
+                .into())
+            }
+            None => return Err(ProofStreamError::EmptyQueue.into()),
+        };
+
+        let expected_variants = self.expected_proof_item_variants(log2_padded_height)?;
+        for (&expected, item) in expected_variants.iter().zip(items) {
+            if expected != ProofItemVariant::from(item) {
+                let got = item.clone();
+                return Err(ProofStreamError::UnexpectedItem { expected, got }.into());
+            }
+        }
+        if expected_variants.len() != items.len() {
+            return Err(VerificationError::IncorrectNumberOfProofItems {
+                expected: expected_variants.len(),
+                actual: items.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The kinds of proof items in a proof for the given padded height, in order of their
+    /// appearance. Does not include the claim of [self-claimed](Self::prove_self_claimed) proofs.
+    fn expected_proof_item_variants(
+        &self,
+        log2_padded_height: u32,
+    ) -> Result<Vec<ProofItemVariant>, VerificationError> {
+        // Deriving FRI for an absurdly large padded height overflows. Reject such heights before
+        // that happens by requiring the FRI domain's length to fit in a `u32`, which is far
+        // beyond anything provable.
+        let padded_height = 1_usize
+            .checked_shl(log2_padded_height)
+            .filter(|&padded_height| {
+                padded_height
+                    .checked_add(self.num_trace_randomizers)
+                    .and_then(usize::checked_next_power_of_two)
+                    .and_then(|len| len.checked_mul(self.fri_expansion_factor))
+                    .is_some_and(|fri_domain_length| u32::try_from(fri_domain_length).is_ok())
+            })
+            .ok_or(VerificationError::Log2PaddedHeightOutOfRange(
+                log2_padded_height,
+            ))?;
+
+        // one Merkle root and one response for the first round, and one each per folding
+        let num_fri_rounds = self.derive_fri(padded_height)?.num_rounds() + 1;
+
+        let mut variants = vec![
+            ProofItemVariant::Log2PaddedHeight,
+            ProofItemVariant::MerkleRoot,
+            ProofItemVariant::MerkleRoot,
+            ProofItemVariant::MerkleRoot,
+            ProofItemVariant::OutOfDomainBaseRow,
+            ProofItemVariant::OutOfDomainExtRow,
+            ProofItemVariant::OutOfDomainBaseRow,
+            ProofItemVariant::OutOfDomainExtRow,
+            ProofItemVariant::OutOfDomainQuotientSegments,
+        ];
+        variants.extend(std::iter::repeat_n(
+            ProofItemVariant::MerkleRoot,
+            num_fri_rounds,
+        ));
+        variants.push(ProofItemVariant::FriCodeword);
+        variants.push(ProofItemVariant::FriPolynomial);
+        variants.extend(std::iter::repeat_n(
+            ProofItemVariant::FriResponse,
+            num_fri_rounds,
+        ));
+        variants.extend([
+            ProofItemVariant::MasterBaseTableRows,
+            ProofItemVariant::AuthenticationStructure,
+            ProofItemVariant::MasterExtTableRows,
+            ProofItemVariant::AuthenticationStructure,
+            ProofItemVariant::QuotientSegmentsElements,
+            ProofItemVariant::AuthenticationStructure,
+        ]);
+
+        Ok(variants)
+    }
+
+    /// Verify the proof stream against the claim. FRI instances are looked up in, and newly
+    /// derived ones added to, the given cache, which is indexed by padded height.
+    fn verify_proof_stream(
+        &self,
+        claim: &Claim,
+        mut proof_stream: ProofStream,
+        fri_cache: &mut HashMap<usize, Fri<Tip5>>,
+    ) -> Result<(), VerificationError> {
         profiler!(start "Fiat-Shamir: Claim" ("hash"));
         proof_stream.alter_fiat_shamir_state_with(claim);
         profiler!(stop "Fiat-Shamir: Claim");
@@ -734,14 +1254,16 @@ impl Stark {
         profiler!(start "derive additional parameters");
         let log_2_padded_height = proof_stream.dequeue()?.try_into_log2_padded_height()?;
         let padded_height = 1 << log_2_padded_height;
-        let fri = self.derive_fri(padded_height)?;
+        let fri = match fri_cache.entry(padded_height) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(self.derive_fri(padded_height)?),
+        };
         let merkle_tree_height = fri.domain.length.ilog2() as usize;
         profiler!(stop "derive additional parameters");
 
         profiler!(start "Fiat-Shamir 1" ("hash"));
         let base_merkle_tree_root = proof_stream.dequeue()?.try_into_merkle_root()?;
-        let extension_challenge_weights = proof_stream.sample_scalars(Challenges::SAMPLE_COUNT);
-        let challenges = Challenges::new(extension_challenge_weights, claim);
+        let challenges = Challenges::sample_from_proof_stream(&mut proof_stream, claim);
         let extension_tree_merkle_root = proof_stream.dequeue()?.try_into_merkle_root()?;
         // Sample weights for quotient codeword, which is a part of the combination codeword.
         // See corresponding part in the prover for a more detailed explanation.
@@ -757,22 +1279,29 @@ impl Stark {
         let out_of_domain_point_curr_row_pow_num_segments =
             out_of_domain_point_curr_row.mod_pow_u32(NUM_QUOTIENT_SEGMENTS as u32);
 
-        let out_of_domain_curr_base_row =
-            proof_stream.dequeue()?.try_into_out_of_domain_base_row()?;
-        let out_of_domain_curr_ext_row =
-            proof_stream.dequeue()?.try_into_out_of_domain_ext_row()?;
-        let out_of_domain_next_base_row =
-            proof_stream.dequeue()?.try_into_out_of_domain_base_row()?;
-        let out_of_domain_next_ext_row =
-            proof_stream.dequeue()?.try_into_out_of_domain_ext_row()?;
+        let dequeue_ood_base_row = |proof_stream: &mut ProofStream| {
+            let row = Self::dequeue_borrowed(
+                proof_stream,
+                ProofItemVariant::OutOfDomainBaseRow,
+                ProofItem::try_as_out_of_domain_base_row,
+            )?;
+            Ok::<_, ProofStreamError>(Array1::from(row.to_vec()))
+        };
+        let dequeue_ood_ext_row = |proof_stream: &mut ProofStream| {
+            let row = Self::dequeue_borrowed(
+                proof_stream,
+                ProofItemVariant::OutOfDomainExtRow,
+                ProofItem::try_as_out_of_domain_ext_row,
+            )?;
+            Ok::<_, ProofStreamError>(Array1::from(row.to_vec()))
+        };
+        let out_of_domain_curr_base_row = dequeue_ood_base_row(&mut proof_stream)?;
+        let out_of_domain_curr_ext_row = dequeue_ood_ext_row(&mut proof_stream)?;
+        let out_of_domain_next_base_row = dequeue_ood_base_row(&mut proof_stream)?;
+        let out_of_domain_next_ext_row = dequeue_ood_ext_row(&mut proof_stream)?;
         let out_of_domain_curr_row_quot_segments = proof_stream
             .dequeue()?
             .try_into_out_of_domain_quot_segments()?;
-
-        let out_of_domain_curr_base_row = Array1::from(out_of_domain_curr_base_row.to_vec());
-        let out_of_domain_curr_ext_row = Array1::from(out_of_domain_curr_ext_row.to_vec());
-        let out_of_domain_next_base_row = Array1::from(out_of_domain_next_base_row.to_vec());
-        let out_of_domain_next_ext_row = Array1::from(out_of_domain_next_ext_row.to_vec());
         let out_of_domain_curr_row_quot_segments =
             Array1::from(out_of_domain_curr_row_quot_segments.to_vec());
         profiler!(stop "dequeue ood point and rows");
@@ -875,14 +1404,22 @@ impl Stark {
 
         profiler!(start "check leafs");
         profiler!(start "dequeue base elements");
-        let base_table_rows = proof_stream.dequeue()?.try_into_master_base_table_rows()?;
-        let base_authentication_structure = proof_stream
-            .dequeue()?
-            .try_into_authentication_structure()?;
+        let base_table_rows = Self::dequeue_borrowed(
+            &mut proof_stream,
+            ProofItemVariant::MasterBaseTableRows,
+            ProofItem::try_as_master_base_table_rows,
+        )?;
         let leaf_digests_base: Vec<_> = base_table_rows
             .par_iter()
             .map(|revealed_base_elem| Tip5::hash_varlen(revealed_base_elem))
             .collect();
+        let base_table_rows = base_table_rows
+            .iter()
+            .map(|row| Array1::from(row.to_vec()))
+            .collect_vec();
+        let base_authentication_structure = proof_stream
+            .dequeue()?
+            .try_into_authentication_structure()?;
         profiler!(stop "dequeue base elements");
 
         let index_leaves = |leaves| {
@@ -902,10 +1439,11 @@ impl Stark {
         profiler!(stop "Merkle verify (base tree)");
 
         profiler!(start "dequeue extension elements");
-        let ext_table_rows = proof_stream.dequeue()?.try_into_master_ext_table_rows()?;
-        let ext_authentication_structure = proof_stream
-            .dequeue()?
-            .try_into_authentication_structure()?;
+        let ext_table_rows = Self::dequeue_borrowed(
+            &mut proof_stream,
+            ProofItemVariant::MasterExtTableRows,
+            ProofItem::try_as_master_ext_table_rows,
+        )?;
         let leaf_digests_ext = ext_table_rows
             .par_iter()
             .map(|xvalues| {
@@ -913,6 +1451,13 @@ impl Stark {
                 Tip5::hash_varlen(&b_values.collect_vec())
             })
             .collect::<Vec<_>>();
+        let ext_table_rows = ext_table_rows
+            .iter()
+            .map(|row| Array1::from(row.to_vec()))
+            .collect_vec();
+        let ext_authentication_structure = proof_stream
+            .dequeue()?
+            .try_into_authentication_structure()?;
         profiler!(stop "dequeue extension elements");
 
         profiler!(start "Merkle verify (extension tree)" ("hash"));
@@ -928,10 +1473,17 @@ impl Stark {
         profiler!(stop "Merkle verify (extension tree)");
 
         profiler!(start "dequeue quotient segments' elements");
-        let revealed_quotient_segments_elements =
-            proof_stream.dequeue()?.try_into_quot_segments_elements()?;
+        let revealed_quotient_segments_elements = Self::dequeue_borrowed(
+            &mut proof_stream,
+            ProofItemVariant::QuotientSegmentsElements,
+            ProofItem::try_as_quot_segments_elements,
+        )?;
         let revealed_quotient_segments_digests =
-            Self::hash_quotient_segment_elements(&revealed_quotient_segments_elements);
+            Self::hash_quotient_segment_elements(revealed_quotient_segments_elements);
+        let revealed_quotient_segments_elements = revealed_quotient_segments_elements
+            .iter()
+            .map(|segments| Array1::from(segments.to_vec()))
+            .collect_vec();
         let revealed_quotient_authentication_structure = proof_stream
             .dequeue()?
             .try_into_authentication_structure()?;
@@ -974,8 +1526,6 @@ impl Stark {
             revealed_quotient_segments_elements,
             revealed_fri_values,
         ) {
-            let base_row = Array1::from(base_row.to_vec());
-            let ext_row = Array1::from(ext_row.to_vec());
             let current_fri_domain_value = fri.domain.domain_value(row_idx as u32);
 
             profiler!(start "base & ext elements" ("CC"));
@@ -984,9 +1534,8 @@ impl Stark {
                 ext_row.view(),
                 base_and_ext_codeword_weights.view(),
             );
-            let quotient_segments_curr_row_element = weights
-                .quot_segments
-                .dot(&Array1::from(quotient_segments_elements.to_vec()));
+            let quotient_segments_curr_row_element =
+                weights.quot_segments.dot(&quotient_segments_elements);
             profiler!(stop "base & ext elements");
 
             profiler!(start "DEEP update");
@@ -1025,6 +1574,20 @@ impl Stark {
         Ok(())
     }
 
+    /// Like [`ProofStream::dequeue`] followed by one of the `try_into_*` methods of
+    /// [`ProofItem`], but borrows the payload instead of cloning it.
+    fn dequeue_borrowed<'stream, T: ?Sized>(
+        proof_stream: &'stream mut ProofStream,
+        expected: ProofItemVariant,
+        try_as: impl FnOnce(&'stream ProofItem) -> Option<&'stream T>,
+    ) -> Result<&'stream T, ProofStreamError> {
+        let item = proof_stream.dequeue_ref()?;
+        try_as(item).ok_or_else(|| ProofStreamError::UnexpectedItem {
+            expected,
+            got: item.clone(),
+        })
+    }
+
     fn hash_quotient_segment_elements(quotient_segment_rows: &[QuotientSegments]) -> Vec<Digest> {
         let interpret_xfe_as_bfes = |xfe: XFieldElement| xfe.coefficients.to_vec();
         let collect_row_as_bfes = |row: &QuotientSegments| row.map(interpret_xfe_as_bfes).concat();
@@ -1250,6 +1813,8 @@ impl Stark {
     }
 }
 
+/// The default parameters target a security level of 160 bits using a FRI expansion factor
+/// of 4. This results in 80 collinearity checks and 98 trace randomizers.
 impl Default for Stark {
     fn default() -> Self {
         let log_2_of_fri_expansion_factor = 2;
@@ -1267,6 +1832,40 @@ impl<'a> Arbitrary<'a> for Stark {
     }
 }
 
+/// The result of [running only the trace generation phase](Stark::prove_trace_only) of proving.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraceReport {
+    /// The height of every table before padding, in the order of [`TableId`]. The
+    /// [degree-lowering table](TableId::DegreeLowering) is omitted since it is derived from
+    /// the other tables.
+    pub table_heights: Vec<TableHeight>,
+
+    /// The height of the [master base table](MasterBaseTable) after padding.
+    pub padded_height: usize,
+
+    /// The number of rows of the master base table, including trace randomizers.
+    pub num_base_rows: usize,
+
+    /// The number of columns of the master base table.
+    pub num_base_columns: usize,
+
+    /// The memory occupied by the master base table, in bytes.
+    pub base_table_memory: usize,
+
+    /// The time it took to execute the program and to create and pad the master base table.
+    pub duration: Duration,
+}
+
+/// Optional information to speed up [proving](Stark::prove_with_hints). Hints only affect the
+/// prover's performance, never the resulting proof.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct ProvingHints {
+    /// The expected heights of the most relevant tables, before padding. Allows allocating the
+    /// corresponding traces once, instead of growing them during program execution. Can be
+    /// obtained using [`Program::estimate_table_heights`], for example, from a previous run.
+    pub table_heights: Option<VMTableHeights>,
+}
+
 /// Fiat-Shamir-sampled challenges to compress a row into a single
 /// [extension field element][XFieldElement].
 struct LinearCombinationWeights {
@@ -1321,8 +1920,10 @@ pub(crate) mod tests {
     use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
+    use rand::rngs::StdRng;
     use rand::thread_rng;
     use rand::Rng;
+    use rand::SeedableRng;
     use strum::EnumCount;
     use strum::IntoEnumIterator;
     use test_strategy::proptest;
@@ -1337,10 +1938,14 @@ pub(crate) mod tests {
     use crate::op_stack::OpStackElement;
     use crate::prelude::Program;
     use crate::program::NonDeterminism;
+    use crate::program::ProgramAndInput;
+    use crate::proof_item::ProofItemVariant;
     use crate::shared_tests::*;
     use crate::table::cascade_table::ExtCascadeTable;
     use crate::table::challenges::ChallengeId::StandardInputIndeterminate;
+    use crate::table::challenges::ChallengeId::StandardInputTerminal;
     use crate::table::challenges::ChallengeId::StandardOutputIndeterminate;
+    use crate::table::challenges::ChallengeId::StandardOutputTerminal;
     use crate::table::constraint_circuit::ConstraintCircuitBuilder;
     use crate::table::cross_table_argument::CrossTableArg;
     use crate::table::cross_table_argument::EvalArg;
@@ -1351,6 +1956,7 @@ pub(crate) mod tests {
     use crate::table::hash_table::ExtHashTable;
     use crate::table::jump_stack_table::ExtJumpStackTable;
     use crate::table::lookup_table::ExtLookupTable;
+    use crate::table::master_table::randomized_padded_trace_len;
     use crate::table::master_table::MasterExtTable;
     use crate::table::master_table::TableId;
     use crate::table::op_stack_table::ExtOpStackTable;
@@ -1636,6 +2242,59 @@ pub(crate) mod tests {
         check!(ptoe == oute);
     }
 
+    #[test]
+    fn reading_input_in_bulk_or_symbol_by_symbol_results_in_identical_input_terminal() {
+        let read_io_1_program = triton_program!(read_io 1 read_io 1 read_io 1 halt);
+        let read_io_3_program = triton_program!(read_io 3 halt);
+        let public_input = PublicInput::from([3, 5, 7].map(|b| bfe!(b)));
+
+        let mut tops_of_stack = vec![];
+        let mut input_terminals = vec![];
+        for program in [read_io_1_program, read_io_3_program] {
+            let mut vm_state = VMState::new(&program, public_input.clone(), [].into());
+            let_assert!(Ok(()) = vm_state.run());
+            let_assert!(Ok(top_of_stack) = vm_state.op_stack.pop_multiple::<3>());
+            tops_of_stack.push(top_of_stack);
+
+            let program_and_input =
+                ProgramAndInput::new(program).with_input(public_input.individual_tokens.clone());
+            let (_, _, _, master_ext_table, challenges) =
+                master_tables_for_low_security_level(program_and_input);
+            let processor_table = master_ext_table.table(TableId::Processor);
+            let last_row = processor_table.slice(s![-1, ..]);
+            let input_terminal = last_row[InputTableEvalArg.ext_table_index()];
+            assert!(challenges[StandardInputTerminal] == input_terminal);
+            input_terminals.push(input_terminal);
+        }
+
+        assert!(tops_of_stack[0] == tops_of_stack[1]);
+        assert!(input_terminals[0] == input_terminals[1]);
+    }
+
+    #[test]
+    fn writing_output_in_bulk_or_symbol_by_symbol_results_in_identical_output_terminal() {
+        let write_io_1_program = triton_program!(
+            push 3 push 5 push 7 write_io 1 write_io 1 write_io 1 halt
+        );
+        let write_io_3_program = triton_program!(push 3 push 5 push 7 write_io 3 halt);
+
+        let mut outputs = vec![];
+        let mut output_terminals = vec![];
+        for program in [write_io_1_program, write_io_3_program] {
+            let (_, claim, _, master_ext_table, challenges) =
+                master_tables_for_low_security_level(ProgramAndInput::new(program));
+            let processor_table = master_ext_table.table(TableId::Processor);
+            let last_row = processor_table.slice(s![-1, ..]);
+            let output_terminal = last_row[OutputTableEvalArg.ext_table_index()];
+            assert!(challenges[StandardOutputTerminal] == output_terminal);
+            outputs.push(claim.output);
+            output_terminals.push(output_terminal);
+        }
+
+        assert!(outputs[0] == outputs[1]);
+        assert!(output_terminals[0] == output_terminals[1]);
+    }
+
     #[test]
     fn constraint_polynomials_use_right_number_of_variables() {
         let challenges = Challenges::default();
@@ -1651,6 +2310,42 @@ pub(crate) mod tests {
         MasterExtTable::evaluate_terminal_constraints(br, er, &challenges);
     }
 
+    #[test]
+    fn max_constraint_degree_of_processor_table_matches_instruction_constraints() {
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        let max_instruction_constraint_degree = ExtProcessorTable::transition_constraint_degrees()
+            .into_iter()
+            .map(|(instruction, degrees)| {
+                let deselector = ExtProcessorTable::instruction_deselector_current_row(
+                    &circuit_builder,
+                    instruction,
+                );
+                let deselector_degree = usize::try_from(deselector.consume().degree()).unwrap();
+                degrees.into_iter().max().unwrap_or(0) + deselector_degree
+            })
+            .max()
+            .unwrap();
+
+        // Transition constraints are disabled in padding rows, increasing their degree by one.
+        let padding_row_deselector_degree = 1;
+        let expected_degree = max_instruction_constraint_degree + padding_row_deselector_degree;
+
+        let max_degrees = Stark::max_constraint_degree_by_table();
+        assert!(expected_degree == max_degrees[&TableId::Processor]);
+    }
+
+    #[test]
+    fn max_constraint_degree_is_reported_for_every_table_with_constraints() {
+        let max_degrees = Stark::max_constraint_degree_by_table();
+        for table in TableId::iter() {
+            let has_constraints = table != TableId::DegreeLowering;
+            assert!(has_constraints == max_degrees.contains_key(&table));
+        }
+        for degree in max_degrees.into_values() {
+            assert!(degree > 0);
+        }
+    }
+
     #[test]
     fn print_number_of_all_constraints_per_table() {
         let table_names = [
@@ -1864,6 +2559,11 @@ pub(crate) mod tests {
         triton_constraints_evaluate_to_zero(test_program_for_assert_vector())
     }
 
+    #[test]
+    fn constraints_evaluate_to_zero_on_program_for_asserts_with_error_codes() {
+        triton_constraints_evaluate_to_zero(test_program_for_asserts_with_error_codes())
+    }
+
     #[test]
     fn constraints_evaluate_to_zero_on_program_for_sponge_instructions() {
         triton_constraints_evaluate_to_zero(test_program_for_sponge_instructions())
@@ -2292,6 +2992,471 @@ pub(crate) mod tests {
         );
     }
 
+    fn claim_and_aet_of_doubling_program() -> (Claim, AlgebraicExecutionTrace) {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let public_input = PublicInput::new(bfe_vec![21]);
+        let_assert!(Ok((aet, output)) = program.trace_execution(public_input, [].into()));
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![21])
+            .with_output(output);
+        (claim, aet)
+    }
+
+    #[test]
+    fn self_claimed_proof_verifies_and_returns_embedded_claim() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let (claim, aet) = claim_and_aet_of_doubling_program();
+        let_assert!(Ok(proof) = stark.prove_self_claimed(&claim, &aet));
+        let_assert!(Ok(embedded_claim) = stark.verify_self_claimed(&proof));
+        assert!(claim == embedded_claim);
+    }
+
+    #[test]
+    fn self_claimed_proof_with_tampered_claim_is_rejected() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let (claim, aet) = claim_and_aet_of_doubling_program();
+        let_assert!(Ok(proof) = stark.prove_self_claimed(&claim, &aet));
+
+        let_assert!(Ok(mut proof_stream) = ProofStream::try_from(&proof));
+        let tampered_claim = claim.with_output(bfe_vec![43]);
+        proof_stream.items[0] = ProofItem::Claim(tampered_claim);
+        let tampered_proof = proof_stream.into();
+
+        let_assert!(Err(err) = stark.verify_self_claimed(&tampered_proof));
+        assert!(let VerificationError::OutOfDomainQuotientValueMismatch = err);
+    }
+
+    #[test]
+    fn regular_proof_is_not_self_claimed() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let (claim, aet) = claim_and_aet_of_doubling_program();
+        let_assert!(Ok(proof) = stark.prove(&claim, &aet));
+
+        let_assert!(Err(err) = stark.verify_self_claimed(&proof));
+        let_assert!(VerificationError::ProofStreamError(err) = err);
+        assert!(let ProofStreamError::UnexpectedItem { .. } = err);
+    }
+
+    #[test]
+    fn proving_with_same_rng_seed_gives_identical_proofs() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = triton_program!(push 3 push 4 add write_io 1 halt);
+        let_assert!(Ok((aet, output)) = program.trace_execution([].into(), [].into()));
+        let claim = Claim::about_program(&program).with_output(output);
+
+        let prove = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            stark.prove_with_rng(&claim, &aet, &mut rng).unwrap()
+        };
+        let proof = prove(42);
+        assert!(proof == prove(42));
+        assert!(proof != prove(43));
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+    }
+
+    #[test]
+    fn proof_created_under_one_domain_separator_fails_verification_under_another() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = triton_program!(push 3 push 4 add write_io 1 halt);
+        let_assert!(Ok((aet, output)) = program.trace_execution([].into(), [].into()));
+        let claim = Claim::about_program(&program).with_output(output);
+
+        let tag = bfe_array![1, 2, 3];
+        let_assert!(Ok(proof) = stark.prove_with_domain_separator(&claim, &aet, &tag));
+        assert!(let Ok(()) = stark.verify_with_domain_separator(&claim, &proof, &tag));
+
+        let other_tag = bfe_array![1, 2, 4];
+        let verdict = stark.verify_with_domain_separator(&claim, &proof, &other_tag);
+        assert!(let Err(_) = verdict);
+        assert!(let Err(_) = stark.verify(&claim, &proof));
+    }
+
+    #[test]
+    fn failure_to_write_proof_reports_underlying_error() {
+        struct FailingWriter;
+
+        impl Write for FailingWriter {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk full"))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = triton_program!(halt);
+        let_assert!(Ok((aet, output)) = program.trace_execution([].into(), [].into()));
+        let claim = Claim::about_program(&program).with_output(output);
+
+        let_assert!(Err(err) = stark.prove_to_writer(&claim, &aet, &mut FailingWriter));
+        let_assert!(ProvingError::WriteError { kind, message } = err);
+        assert!(std::io::ErrorKind::Other == kind);
+        assert!("disk full" == message);
+    }
+
+    #[test]
+    fn proof_streamed_to_writer_has_same_shape_as_in_memory_proof_and_verifies(
+    ) -> Result<(), ProofStreamError> {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let public_input = PublicInput::new(bfe_vec![21]);
+        let_assert!(Ok((aet, output)) = program.trace_execution(public_input, [].into()));
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![21])
+            .with_output(output);
+
+        let mut streamed_proof = vec![];
+        let_assert!(Ok(()) = stark.prove_to_writer(&claim, &aet, &mut streamed_proof));
+        let mut reader = streamed_proof.as_slice();
+        let mut streamed_proof_stream = ProofStream::new();
+        while streamed_proof_stream
+            .decode_next_framed_from(&mut reader)?
+            .is_some()
+        {}
+
+        let_assert!(Ok(proof) = stark.prove(&claim, &aet));
+        let_assert!(Ok(proof_stream) = ProofStream::try_from(&proof));
+        let variants = |stream: &ProofStream| {
+            stream
+                .items
+                .iter()
+                .map(ProofItemVariant::from)
+                .collect_vec()
+        };
+        assert!(variants(&proof_stream) == variants(&streamed_proof_stream));
+
+        let streamed_proof = streamed_proof_stream.into();
+        let_assert!(Ok(()) = stark.verify(&claim, &streamed_proof));
+        Ok(())
+    }
+
+    #[test]
+    fn trace_only_proving_reports_dimensions_of_master_base_table() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![21])
+            .with_output(bfe_vec![42]);
+        let public_input = PublicInput::new(bfe_vec![21]);
+        let_assert!(Ok(report) = stark.prove_trace_only(&claim, &program, public_input, [].into()));
+
+        let reported_tables = report.table_heights.iter().map(|h| h.table).collect_vec();
+        let all_tables = TableId::iter().filter(|&t| t != TableId::DegreeLowering);
+        assert!(all_tables.collect_vec() == reported_tables);
+
+        let height_of = |table| {
+            let table_height = report.table_heights.iter().find(|h| h.table == table);
+            table_height.unwrap().height
+        };
+        assert!(5 == height_of(TableId::Processor));
+        assert!(AlgebraicExecutionTrace::LOOKUP_TABLE_HEIGHT == height_of(TableId::Lookup));
+        assert!(report.padded_height.is_power_of_two());
+        for table_height in &report.table_heights {
+            assert!(table_height.height <= report.padded_height);
+        }
+
+        let num_rows =
+            randomized_padded_trace_len(report.padded_height, stark.num_trace_randomizers);
+        assert!(num_rows == report.num_base_rows);
+        assert!(NUM_BASE_COLUMNS == report.num_base_columns);
+        let num_bytes = num_rows * NUM_BASE_COLUMNS * std::mem::size_of::<BFieldElement>();
+        assert!(num_bytes == report.base_table_memory);
+    }
+
+    #[test]
+    fn trace_only_proving_rejects_claim_with_wrong_input() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![21])
+            .with_output(bfe_vec![42]);
+        let public_input = PublicInput::new(bfe_vec![20]);
+        let_assert!(Err(err) = stark.prove_trace_only(&claim, &program, public_input, [].into()));
+        assert!(let ProvingError::PublicInputMismatch = err);
+    }
+
+    #[test]
+    fn trace_only_proving_rejects_claim_with_wrong_output() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![21])
+            .with_output(bfe_vec![43]);
+        let public_input = PublicInput::new(bfe_vec![21]);
+        let_assert!(Err(err) = stark.prove_trace_only(&claim, &program, public_input, [].into()));
+        assert!(let ProvingError::PublicOutputMismatch = err);
+    }
+
+    #[test]
+    fn proving_with_correct_or_wrong_hints_gives_verifying_proofs() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = triton_program!(
+            sponge_init sponge_squeeze sponge_squeeze hash sponge_absorb pop 5
+            read_io 1 write_io 1 halt
+        );
+        let public_input = PublicInput::new(bfe_vec![42]);
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![42])
+            .with_output(bfe_vec![42]);
+        let_assert!(
+            Ok(table_heights) = program.estimate_table_heights(public_input.clone(), [].into())
+        );
+
+        let too_small = VMTableHeights::default();
+        let mut too_large = table_heights;
+        too_large.processor *= 3;
+        too_large.hash *= 3;
+        for table_heights in [None, Some(table_heights), Some(too_small), Some(too_large)] {
+            let hints = ProvingHints { table_heights };
+            let_assert!(
+                Ok(proof) = stark.prove_with_hints(
+                    &claim,
+                    &program,
+                    public_input.clone(),
+                    [].into(),
+                    hints
+                )
+            );
+            assert!(let Ok(()) = stark.verify(&claim, &proof));
+        }
+    }
+
+    #[test]
+    fn proving_with_hints_rejects_claim_with_wrong_input() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![21])
+            .with_output(bfe_vec![42]);
+        let public_input = PublicInput::new(bfe_vec![20]);
+        let hints = ProvingHints::default();
+        let_assert!(
+            Err(err) = stark.prove_with_hints(&claim, &program, public_input, [].into(), hints)
+        );
+        assert!(let ProvingError::PublicInputMismatch = err);
+    }
+
+    #[test]
+    fn proofs_have_expected_shape() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let (claim, aet) = claim_and_aet_of_doubling_program();
+        let_assert!(Ok(proof) = stark.prove(&claim, &aet));
+        assert!(let Ok(()) = stark.check_proof_shape(&claim, &proof));
+
+        let_assert!(Ok(proof) = stark.prove_self_claimed(&claim, &aet));
+        assert!(let Ok(()) = stark.check_proof_shape(&claim, &proof));
+
+        let other_claim = claim.with_output(bfe_vec![43]);
+        let_assert!(Err(err) = stark.check_proof_shape(&other_claim, &proof));
+        assert!(let VerificationError::ClaimMismatch = err);
+    }
+
+    #[test]
+    fn truncated_proof_has_unexpected_shape() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let (claim, aet) = claim_and_aet_of_doubling_program();
+        let_assert!(Ok(proof) = stark.prove(&claim, &aet));
+
+        let_assert!(Ok(mut proof_stream) = ProofStream::try_from(&proof));
+        let num_items = proof_stream.items.len();
+        proof_stream.items.pop();
+        let truncated_proof = proof_stream.into();
+
+        let_assert!(Err(err) = stark.check_proof_shape(&claim, &truncated_proof));
+        let_assert!(VerificationError::IncorrectNumberOfProofItems { expected, actual } = err);
+        assert!(num_items == expected);
+        assert!(num_items - 1 == actual);
+    }
+
+    #[test]
+    fn reordered_proof_has_unexpected_shape() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let (claim, aet) = claim_and_aet_of_doubling_program();
+        let_assert!(Ok(proof) = stark.prove(&claim, &aet));
+
+        let_assert!(Ok(mut proof_stream) = ProofStream::try_from(&proof));
+        let_assert!(
+            Some(ood_row_index) = proof_stream
+                .items
+                .iter()
+                .position(|item| { matches!(item, ProofItem::OutOfDomainBaseRow(_)) })
+        );
+        proof_stream.items.swap(1, ood_row_index);
+        let reordered_proof = proof_stream.into();
+
+        let_assert!(Err(err) = stark.check_proof_shape(&claim, &reordered_proof));
+        let_assert!(VerificationError::ProofStreamError(err) = err);
+        let_assert!(ProofStreamError::UnexpectedItem { expected, got } = err);
+        assert!(ProofItemVariant::MerkleRoot == expected);
+        assert!(let ProofItem::OutOfDomainBaseRow(_) = got);
+    }
+
+    #[test]
+    fn proof_without_log2_padded_height_has_unexpected_shape() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let claim = Claim::about_program(&triton_program!(halt));
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::MerkleRoot(Digest::default()));
+
+        let_assert!(Err(err) = stark.check_proof_shape(&claim, &proof_stream.into()));
+        let_assert!(VerificationError::ProofStreamError(err) = err);
+        assert!(let ProofStreamError::UnexpectedItem { .. } = err);
+    }
+
+    #[test]
+    fn proof_with_huge_log2_padded_height_has_unexpected_shape() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let claim = Claim::about_program(&triton_program!(halt));
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(62));
+
+        let_assert!(Err(err) = stark.check_proof_shape(&claim, &proof_stream.into()));
+        assert!(let VerificationError::Log2PaddedHeightOutOfRange(62) = err);
+    }
+
+    #[test]
+    fn default_stark_parameters_are_valid() {
+        assert!(let Ok(()) = Stark::default().validate_parameters());
+        for log2_of_fri_expansion_factor in [1, 2, 3, 4, 5] {
+            let stark = Stark::new(160, log2_of_fri_expansion_factor);
+            assert!(let Ok(()) = stark.validate_parameters());
+        }
+    }
+
+    #[test]
+    fn invalid_stark_parameters_are_rejected() {
+        let_assert!(Err(err) = Stark::try_new(32, 1, 100));
+        assert!(StarkParameterError::FriExpansionFactorTooSmall == err);
+
+        let_assert!(Err(err) = Stark::try_new(32, 6, 100));
+        assert!(StarkParameterError::FriExpansionFactorUnsupported == err);
+
+        let_assert!(Err(err) = Stark::try_new(32, 8, 10));
+        let_assert!(StarkParameterError::InsufficientSecurity { .. } = err);
+
+        assert!(let Ok(_) = Stark::try_new(32, 8, 11));
+    }
+
+    #[test]
+    fn default_stark_has_documented_parameters() {
+        let stark = Stark::default();
+        assert!(160 == stark.security_level);
+        assert!(4 == stark.fri_expansion_factor);
+        assert!(80 == stark.num_collinearity_checks);
+        assert!(98 == stark.num_trace_randomizers);
+        assert!(160 == stark.achieved_security_level());
+    }
+
+    #[test]
+    fn achieved_security_level_can_exceed_targeted_security_level() {
+        let_assert!(Ok(stark) = Stark::try_new(32, 8, 11));
+        assert!(32 == stark.security_level);
+        assert!(33 == stark.achieved_security_level());
+    }
+
+    #[test]
+    fn prove_and_verify_with_different_fri_expansion_factors() {
+        let (claim, aet) = claim_and_aet_of_doubling_program();
+        let_assert!(Ok(stark_a) = Stark::try_new(32, 4, 16));
+        let_assert!(Ok(stark_b) = Stark::try_new(32, 16, 8));
+
+        let_assert!(Ok(proof_a) = stark_a.prove(&claim, &aet));
+        let_assert!(Ok(proof_b) = stark_b.prove(&claim, &aet));
+        assert!(let Ok(()) = stark_a.verify(&claim, &proof_a));
+        assert!(let Ok(()) = stark_b.verify(&claim, &proof_b));
+
+        assert!(let Err(_) = stark_a.verify(&claim, &proof_b));
+        assert!(let Err(_) = stark_b.verify(&claim, &proof_a));
+    }
+
+    #[test]
+    fn batch_verification_agrees_with_individual_verification() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let prove = |input: u64| {
+            let public_input = PublicInput::new(bfe_vec![input]);
+            let (aet, output) = program.trace_execution(public_input, [].into()).unwrap();
+            let claim = Claim::about_program(&program)
+                .with_input(bfe_vec![input])
+                .with_output(output);
+            let proof = stark.prove(&claim, &aet).unwrap();
+            (claim, proof)
+        };
+
+        let mut claim_proofs = [21, 1337, 0].map(prove).to_vec();
+        for (claim, proof) in &claim_proofs {
+            assert!(let Ok(()) = stark.verify(claim, proof));
+        }
+        assert!(let Ok(()) = stark.verify_batch(&claim_proofs));
+        assert!(let Ok(()) = stark.verify_batch(&[]));
+
+        let (claim, _) = &mut claim_proofs[1];
+        *claim = claim.clone().with_output(bfe_vec![42]);
+        let (claim, proof) = &claim_proofs[1];
+        let_assert!(Err(individual_err) = stark.verify(claim, proof));
+        let_assert!(Err(batch_err) = stark.verify_batch(&claim_proofs));
+        assert!(individual_err.to_string() == batch_err.to_string());
+    }
+
+    #[test]
+    fn num_padding_rows_is_difference_between_padded_height_and_processor_trace_length() {
+        let program = triton_program!(
+            push 100 call countdown pop 1 halt
+            countdown: push -1 add dup 0 skiz recurse return
+        );
+        let public_input = PublicInput::default;
+        let non_determinism = NonDeterminism::default;
+        let_assert!(Ok((aet, _)) = program.trace_execution(public_input(), non_determinism()));
+        assert!(aet.processor_trace.nrows() == aet.height().height);
+
+        let_assert!(
+            Ok(num_padding_rows) =
+                Stark::num_padding_rows(&program, public_input(), non_determinism())
+        );
+        let processor_trace_length = aet.processor_trace.nrows();
+        assert!(aet.padded_height() - processor_trace_length == num_padding_rows);
+    }
+
+    #[test]
+    fn num_padding_rows_refers_to_processor_trace_even_if_other_tables_are_taller() {
+        let program = triton_program!(push 1 assert halt);
+        let public_input = PublicInput::default;
+        let non_determinism = NonDeterminism::default;
+        let_assert!(Ok((aet, _)) = program.trace_execution(public_input(), non_determinism()));
+        let processor_trace_length = aet.processor_trace.nrows();
+        assert!(processor_trace_length < aet.height().height);
+
+        let_assert!(
+            Ok(num_padding_rows) =
+                Stark::num_padding_rows(&program, public_input(), non_determinism())
+        );
+        assert!(aet.padded_height() - processor_trace_length == num_padding_rows);
+    }
+
+    #[proptest]
+    fn fri_domain_has_expected_length_and_generator(
+        #[strategy(1_usize..=4)] log_2_fri_expansion_factor: usize,
+        #[strategy(0_u32..=20)] log_2_padded_height: u32,
+    ) {
+        let stark = Stark::new(32, log_2_fri_expansion_factor);
+        let padded_height = 1 << log_2_padded_height;
+        let fri_domain = stark.fri_domain(padded_height).unwrap();
+
+        let randomized_trace_len =
+            randomized_padded_trace_len(padded_height, stark.num_trace_randomizers);
+        let expected_length = stark.fri_expansion_factor * randomized_trace_len;
+        prop_assert_eq!(expected_length, fri_domain.length);
+
+        let length = fri_domain.length as u64;
+        prop_assert_eq!(bfe!(1), fri_domain.generator.mod_pow(length));
+        prop_assert_ne!(bfe!(1), fri_domain.generator.mod_pow(length / 2));
+
+        let fri = stark.derive_fri(padded_height).unwrap();
+        prop_assert_eq!(fri.domain, fri_domain);
+    }
+
     #[test]
     fn prove_and_verify_halt_with_different_fri_expansion_factors() {
         for log_2_fri_expansion_factor in 1..5 {
@@ -2325,6 +3490,14 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn prove_verify_asserts_with_error_codes() {
+        prove_and_verify(
+            test_program_for_asserts_with_error_codes(),
+            DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS,
+        );
+    }
+
     #[proptest]
     fn verifying_arbitrary_proof_does_not_panic(
         #[strategy(arb())] stark: Stark,
@@ -2807,7 +3980,12 @@ pub(crate) mod tests {
             merkle_step             // _ 1 [digest; 5]
             divine 5                // _ 1 [digest; 5] [digest; 5]
             assert_vector           // _ 1 [digest; 5]
+            dup 4 dup 4 dup 4       // _ 1 [digest; 5] [digest; 3]
+            dup 4 dup 4             // _ 1 [digest; 5] [digest; 5]
+            assert_vector 42        // _ 1 [digest; 5]
             pop 5                   // _ 1
+            dup 0                   // _ 1 1
+            assert 7                // _ 1
             assert                  // _
 
             // dot_step
@@ -2837,6 +4015,12 @@ pub(crate) mod tests {
             eq                      // _ bfe_0
             pop 1                   // _
 
+            // op stack manipulation
+            push 1 push 2 push 3    // _ 1 2 3
+            pick 2                  // _ 2 3 1
+            place 2                 // _ 1 2 3
+            pop 3                   // _
+
             // bit-wise arithmetic
             push 38                 // _ 38
             push 2                  // _ 38 2
@@ -61,6 +61,23 @@ impl Display for ConstraintType {
     }
 }
 
+/// The number of AIR constraints of a table, by [constraint type](ConstraintType). The counts
+/// refer to the constraints before degree lowering.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ConstraintCounts {
+    pub initial: usize,
+    pub consistency: usize,
+    pub transition: usize,
+    pub terminal: usize,
+}
+
+impl ConstraintCounts {
+    /// The total number of constraints across all constraint types.
+    pub const fn total(&self) -> usize {
+        self.initial + self.consistency + self.transition + self.terminal
+    }
+}
+
 /// A single row of a [`MasterBaseTable`][table].
 ///
 /// Usually, the elements in the table are [`BFieldElement`]s. For out-of-domain rows, which is
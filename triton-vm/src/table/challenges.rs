@@ -18,6 +18,8 @@
 //!   table. Instead, the terminal of the Evaluation Argument is computed directly from the
 //!   public input (respectively output) and the indeterminate.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::ops::Index;
@@ -28,11 +30,18 @@ use arbitrary::Arbitrary;
 use strum::Display;
 use strum::EnumCount;
 use strum::EnumIter;
+use strum::IntoEnumIterator;
 use twenty_first::prelude::*;
 
+use crate::error::ChallengeError;
+use crate::error::ChallengeError::MissingChallenge;
+use crate::proof_stream::FiatShamir;
+use crate::proof_stream::ProofStream;
 use crate::table::challenges::ChallengeId::*;
+use crate::table::constraint_circuit::ConstraintCircuitBuilder;
 use crate::table::cross_table_argument::CrossTableArg;
 use crate::table::cross_table_argument::EvalArg;
+use crate::table::processor_table::ExtProcessorTable;
 use crate::Claim;
 
 /// A `ChallengeId` is a unique, symbolic identifier for a challenge used in Triton VM. The
@@ -281,6 +290,52 @@ impl Challenges {
 
         Self { challenges }
     }
+
+    /// Sample the [challenges](Self::SAMPLE_COUNT) from the given [`ProofStream`] using the
+    /// Fiat-Shamir heuristic, then derive the remaining challenges from the given [`Claim`].
+    ///
+    /// Prover and verifier arrive at identical challenges if their proof streams are in the same
+    /// state.
+    pub fn sample_from_proof_stream<S: FiatShamir + Default>(
+        proof_stream: &mut ProofStream<S>,
+        claim: &Claim,
+    ) -> Self {
+        let sampled_challenges = proof_stream.sample_scalars(Self::SAMPLE_COUNT);
+        Self::new(sampled_challenges, claim)
+    }
+}
+
+impl Challenges {
+    /// Assemble the challenges from the given map. Fails if any [`ChallengeId`] is missing.
+    ///
+    /// In contrast to [`Challenges::new`], no challenges are derived from public information.
+    pub fn from_map(
+        mut challenges: HashMap<ChallengeId, XFieldElement>,
+    ) -> Result<Self, ChallengeError> {
+        let challenges = ChallengeId::iter()
+            .map(|id| challenges.remove(&id).ok_or(MissingChallenge(id)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let challenges = challenges.try_into().unwrap();
+
+        Ok(Self { challenges })
+    }
+
+    /// The challenges referenced by any of the [Processor Table](crate::table::processor_table)'s
+    /// constraints.
+    pub fn ids_used_by_processor_table() -> HashSet<ChallengeId> {
+        let single_row_builder = ConstraintCircuitBuilder::new();
+        let dual_row_builder = ConstraintCircuitBuilder::new();
+        ExtProcessorTable::initial_constraints(&single_row_builder);
+        ExtProcessorTable::consistency_constraints(&single_row_builder);
+        ExtProcessorTable::transition_constraints(&dual_row_builder);
+        ExtProcessorTable::terminal_constraints(&single_row_builder);
+
+        let mut used_indices = single_row_builder.challenge_indices();
+        used_indices.extend(dual_row_builder.challenge_indices());
+        ChallengeId::iter()
+            .filter(|id| used_indices.contains(&id.index()))
+            .collect()
+    }
 }
 
 impl Index<usize> for Challenges {
@@ -333,6 +388,14 @@ impl Index<RangeInclusive<ChallengeId>> for Challenges {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use assert2::let_assert;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
+    use test_strategy::proptest;
+
+    use crate::proof_item::ProofItem;
+    use crate::proof_stream::tests::DomainSeparatedTip5;
+
     use super::*;
 
     // For testing purposes only.
@@ -353,6 +416,41 @@ pub(crate) mod tests {
         }
     }
 
+    #[proptest]
+    fn prover_and_verifier_sample_identical_challenges(
+        #[strategy(arb())] claim: Claim,
+        #[strategy(arb())] merkle_root: Digest,
+    ) {
+        let mut prover_proof_stream = ProofStream::new();
+        prover_proof_stream.enqueue(ProofItem::MerkleRoot(merkle_root));
+        let prover_challenges =
+            Challenges::sample_from_proof_stream(&mut prover_proof_stream, &claim);
+
+        let proof = prover_proof_stream.into();
+        let_assert!(Ok(mut verifier_proof_stream) = ProofStream::try_from(&proof));
+        let_assert!(Ok(_) = verifier_proof_stream.dequeue());
+        let verifier_challenges =
+            Challenges::sample_from_proof_stream(&mut verifier_proof_stream, &claim);
+
+        prop_assert_eq!(prover_challenges.challenges, verifier_challenges.challenges);
+    }
+
+    #[proptest]
+    fn challenges_can_be_sampled_using_custom_fiat_shamir_sponge(
+        #[strategy(arb())] claim: Claim,
+        #[strategy(arb())] merkle_root: Digest,
+    ) {
+        let mut proof_stream = ProofStream::new();
+        let mut custom_proof_stream = ProofStream::with_sponge(DomainSeparatedTip5::default());
+        proof_stream.enqueue(ProofItem::MerkleRoot(merkle_root));
+        custom_proof_stream.enqueue(ProofItem::MerkleRoot(merkle_root));
+
+        let challenges = Challenges::sample_from_proof_stream(&mut proof_stream, &claim);
+        let custom_challenges =
+            Challenges::sample_from_proof_stream(&mut custom_proof_stream, &claim);
+        prop_assert_ne!(challenges.challenges, custom_challenges.challenges);
+    }
+
     #[test]
     const fn compile_time_index_assertions() {
         // Terminal challenges are computed from public information, such as public input or
@@ -383,6 +481,37 @@ pub(crate) mod tests {
     // Ensure the compile-time assertions are actually executed by the compiler.
     const _: () = compile_time_index_assertions();
 
+    fn all_challenges_as_map() -> HashMap<ChallengeId, XFieldElement> {
+        let challenges = Challenges::placeholder(&Claim::default());
+        ChallengeId::iter().map(|id| (id, challenges[id])).collect()
+    }
+
+    #[test]
+    fn challenges_can_be_assembled_from_complete_map() {
+        let map = all_challenges_as_map();
+        let_assert!(Ok(challenges) = Challenges::from_map(map.clone()));
+        for (id, challenge) in map {
+            assert_eq!(challenge, challenges[id]);
+        }
+    }
+
+    #[test]
+    fn assembling_challenges_from_map_with_missing_id_gives_error_naming_that_id() {
+        let mut map = all_challenges_as_map();
+        map.remove(&RamIndeterminate);
+        let_assert!(Err(err) = Challenges::from_map(map));
+        assert_eq!(ChallengeError::MissingChallenge(RamIndeterminate), err);
+        assert!(err.to_string().contains("RamIndeterminate"));
+    }
+
+    #[test]
+    fn processor_table_uses_some_but_not_all_challenges() {
+        let used_ids = Challenges::ids_used_by_processor_table();
+        assert!(used_ids.contains(&RamIndeterminate));
+        assert!(used_ids.contains(&StandardInputIndeterminate));
+        assert!(!used_ids.contains(&LookupTablePublicTerminal));
+    }
+
     #[test]
     fn various_challenge_indexing_operations_are_possible() {
         let challenges = Challenges::placeholder(&Claim::default());
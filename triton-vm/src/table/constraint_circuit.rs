@@ -9,12 +9,15 @@
 use std::cell::RefCell;
 use std::cmp;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::io;
+use std::io::Write;
 use std::iter::Sum;
 use std::ops::Add;
 use std::ops::AddAssign;
@@ -920,6 +923,60 @@ impl<II: InputIndicator> ConstraintCircuitMonad<II> {
         all_nodes
     }
 
+    /// Write the multicircuit as a directed graph in [Graphviz DOT][dot] format. Every root,
+    /// _i.e._, every constraint, gets its own labelled node. Nodes are identified by their
+    /// [ID](ConstraintCircuit::id), so subexpressions shared between constraints, or within one
+    /// constraint, appear only once.
+    ///
+    /// [dot]: https://graphviz.org/doc/info/lang.html
+    pub fn write_multicircuit_as_dot<W: Write>(
+        multicircuit: &[ConstraintCircuitMonad<II>],
+        out: &mut W,
+    ) -> io::Result<()> {
+        writeln!(out, "digraph multicircuit {{")?;
+        let mut written_nodes = HashSet::new();
+        for (constraint_idx, constraint) in multicircuit.iter().enumerate() {
+            let circuit = constraint.circuit.borrow();
+            writeln!(
+                out,
+                "  constraint_{constraint_idx} [label=\"constraint {constraint_idx}\", shape=box];"
+            )?;
+            writeln!(out, "  constraint_{constraint_idx} -> node_{};", circuit.id)?;
+            Self::write_circuit_nodes_as_dot(&circuit, out, &mut written_nodes)?;
+        }
+        writeln!(out, "}}")
+    }
+
+    /// Internal helper function to recursively write all not-yet-written nodes of a circuit.
+    fn write_circuit_nodes_as_dot<W: Write>(
+        circuit: &ConstraintCircuit<II>,
+        out: &mut W,
+        written_nodes: &mut HashSet<usize>,
+    ) -> io::Result<()> {
+        if !written_nodes.insert(circuit.id) {
+            return Ok(());
+        }
+
+        let id = circuit.id;
+        match &circuit.expression {
+            BConstant(bfe) => writeln!(out, "  node_{id} [label=\"{bfe}\", shape=plain];")?,
+            XConstant(xfe) => writeln!(out, "  node_{id} [label=\"{xfe}\", shape=plain];")?,
+            Input(input) => writeln!(out, "  node_{id} [label=\"{input}\", shape=box];")?,
+            Challenge(challenge_idx) => writeln!(
+                out,
+                "  node_{id} [label=\"challenge {challenge_idx}\", shape=diamond];"
+            )?,
+            BinaryOperation(operation, lhs, rhs) => {
+                writeln!(out, "  node_{id} [label=\"{operation}\", shape=circle];")?;
+                writeln!(out, "  node_{id} -> node_{};", lhs.borrow().id)?;
+                writeln!(out, "  node_{id} -> node_{};", rhs.borrow().id)?;
+                Self::write_circuit_nodes_as_dot(&lhs.borrow(), out, written_nodes)?;
+                Self::write_circuit_nodes_as_dot(&rhs.borrow(), out, written_nodes)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the maximum degree of all circuits in the multicircuit.
     pub(crate) fn multicircuit_degree(multicircuit: &[ConstraintCircuitMonad<II>]) -> isize {
         multicircuit
@@ -1036,6 +1093,43 @@ impl<II: InputIndicator> ConstraintCircuitBuilder<II> {
         new_node
     }
 
+    /// The indices of all challenges referenced by any node created through this builder.
+    pub(crate) fn challenge_indices(&self) -> HashSet<usize> {
+        self.all_nodes
+            .borrow()
+            .values()
+            .filter_map(|node| match node.circuit.borrow().expression {
+                Challenge(challenge_index) => Some(challenge_index),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The number of unique nodes created through this builder. Since the builder never creates
+    /// a node that is structurally identical to an existing one, this is also the number of
+    /// structurally distinct (sub-)circuits.
+    pub fn node_count(&self) -> usize {
+        self.all_nodes.borrow().len()
+    }
+
+    /// The index pairs `(i, j)`, with `i < j`, of all constraints that are exact duplicates of
+    /// one another, _i.e._, that refer to the same node. Such duplicates do not change the
+    /// constrained relation but waste prover time, and usually indicate a copy-paste error.
+    pub fn duplicate_constraints(
+        constraints: &[ConstraintCircuitMonad<II>],
+    ) -> Vec<(usize, usize)> {
+        let node_id = |constraint: &ConstraintCircuitMonad<II>| constraint.circuit.borrow().id;
+        let mut duplicates = vec![];
+        for (i, constraint) in constraints.iter().enumerate() {
+            for (j, other_constraint) in constraints.iter().enumerate().skip(i + 1) {
+                if node_id(constraint) == node_id(other_constraint) {
+                    duplicates.push((i, j));
+                }
+            }
+        }
+        duplicates
+    }
+
     /// Replace all pointers to a given node (identified by `old_id`) by one
     /// to the new node.
     ///
@@ -1228,6 +1322,45 @@ mod tests {
         assert_eq!("(4·x² + 3·x + 2)", xfe_str);
     }
 
+    #[test]
+    fn structurally_identical_circuits_are_reported_as_duplicates() {
+        let builder = ConstraintCircuitBuilder::new();
+        let input = |i| builder.input(BaseRow(i));
+        let constraints = [
+            input(0) * input(1),
+            input(2) - builder.one(),
+            input(1) * input(0),
+            input(3),
+            input(2) - builder.one(),
+            input(0) * input(1),
+        ];
+
+        let duplicates = ConstraintCircuitBuilder::duplicate_constraints(&constraints);
+        assert_eq!(vec![(0, 2), (0, 5), (1, 4), (2, 5)], duplicates);
+
+        // 4 inputs, the constants 1 and -1, one product, and one difference
+        assert_eq!(8, builder.node_count());
+    }
+
+    #[test]
+    fn shared_subexpressions_appear_only_once_in_dot_output() {
+        let builder = ConstraintCircuitBuilder::new();
+        let shared = builder.input(BaseRow(3)) * builder.challenge(1_usize);
+        let first = shared.clone() + builder.b_constant(5);
+        let second = shared.clone() * builder.input(ExtRow(0));
+        let shared_id = shared.circuit.borrow().id;
+
+        let mut dot = vec![];
+        ConstraintCircuitMonad::write_multicircuit_as_dot(&[first, second], &mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+
+        assert!(dot.starts_with("digraph"));
+        assert_eq!(1, dot.matches(&format!("node_{shared_id} [")).count());
+        assert_eq!(2, dot.matches(&format!("-> node_{shared_id};")).count());
+        assert_eq!(1, dot.matches("label=\"base_row[3]\"").count());
+        assert!(dot.contains("constraint_0") && dot.contains("constraint_1"));
+    }
+
     #[proptest]
     fn constant_folding_can_deal_with_multiplication_by_one(
         #[strategy(arb())] c: ConstraintCircuitMonad<DualRowIndicator>,
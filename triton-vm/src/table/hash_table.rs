@@ -1851,7 +1851,7 @@ impl HashTable {
 pub(crate) mod tests {
     use std::collections::HashMap;
 
-    use crate::shared_tests::ProgramAndInput;
+    use crate::program::ProgramAndInput;
     use crate::stark::tests::master_tables_for_low_security_level;
     use crate::table::master_table::MasterTable;
     use crate::table::master_table::TableId;
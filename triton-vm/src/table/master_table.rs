@@ -17,10 +17,14 @@ use num_traits::One;
 use num_traits::Zero;
 use rand::distributions::Standard;
 use rand::prelude::Distribution;
-use rand::random;
+use rand::rngs::StdRng;
+use rand::thread_rng;
+use rand::Rng;
+use rand::SeedableRng;
 use strum::Display;
 use strum::EnumCount;
 use strum::EnumIter;
+use strum::IntoEnumIterator;
 use twenty_first::math::tip5::RATE;
 use twenty_first::math::traits::FiniteField;
 use twenty_first::prelude::*;
@@ -29,6 +33,7 @@ use twenty_first::util_types::algebraic_hasher;
 use crate::aet::AlgebraicExecutionTrace;
 use crate::arithmetic_domain::ArithmeticDomain;
 use crate::config::CacheDecision;
+use crate::error::NumRandomizersError;
 use crate::error::ProvingError;
 use crate::ndarray_helper::fast_zeros_column_major;
 use crate::ndarray_helper::horizontal_multi_slice_mut;
@@ -37,6 +42,8 @@ use crate::profiler::profiler;
 use crate::stark::NUM_RANDOMIZER_POLYNOMIALS;
 use crate::table::cascade_table::CascadeTable;
 use crate::table::challenges::Challenges;
+use crate::table::degree_lowering_table::DegreeLoweringBaseTableColumn;
+use crate::table::degree_lowering_table::DegreeLoweringExtTableColumn;
 use crate::table::degree_lowering_table::DegreeLoweringTable;
 use crate::table::extension_table::all_degrees_with_origin;
 use crate::table::extension_table::DegreeWithOrigin;
@@ -154,7 +161,20 @@ pub const EXT_DEGREE_LOWERING_TABLE_END: usize =
 const NUM_TABLES_WITHOUT_DEGREE_LOWERING: usize = TableId::COUNT - 1;
 
 /// A `TableId` uniquely determines one of Triton VM's tables.
-#[derive(Debug, Display, Copy, Clone, Eq, PartialEq, Hash, EnumCount, EnumIter, Arbitrary)]
+#[derive(
+    Debug,
+    Display,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    EnumCount,
+    EnumIter,
+    Arbitrary,
+)]
 pub enum TableId {
     Program,
     Processor,
@@ -168,6 +188,64 @@ pub enum TableId {
     DegreeLowering,
 }
 
+/// The name of a column in the [`MasterBaseTable`], qualified by the table the column
+/// belongs to. Displays like `Processor::IB0`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ColumnName {
+    pub table: TableId,
+    pub column: String,
+}
+
+impl std::fmt::Display for ColumnName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}::{}", self.table, self.column)
+    }
+}
+
+impl ColumnName {
+    fn names<C: IntoEnumIterator + ToString>(table: TableId) -> impl Iterator<Item = Self> {
+        C::iter().map(move |column| ColumnName {
+            table,
+            column: column.to_string(),
+        })
+    }
+
+    /// The names of all columns in the [`MasterBaseTable`], in the order of the master table.
+    pub fn all_base_columns() -> Vec<Self> {
+        Self::names::<ProgramBaseTableColumn>(TableId::Program)
+            .chain(Self::names::<ProcessorBaseTableColumn>(TableId::Processor))
+            .chain(Self::names::<OpStackBaseTableColumn>(TableId::OpStack))
+            .chain(Self::names::<RamBaseTableColumn>(TableId::Ram))
+            .chain(Self::names::<JumpStackBaseTableColumn>(TableId::JumpStack))
+            .chain(Self::names::<HashBaseTableColumn>(TableId::Hash))
+            .chain(Self::names::<CascadeBaseTableColumn>(TableId::Cascade))
+            .chain(Self::names::<LookupBaseTableColumn>(TableId::Lookup))
+            .chain(Self::names::<U32BaseTableColumn>(TableId::U32))
+            .chain(Self::names::<DegreeLoweringBaseTableColumn>(
+                TableId::DegreeLowering,
+            ))
+            .collect()
+    }
+
+    /// The names of all columns in the [`MasterExtTable`], in the order of the master table.
+    /// Does not include the columns holding the randomizer polynomials.
+    pub fn all_ext_columns() -> Vec<Self> {
+        Self::names::<ProgramExtTableColumn>(TableId::Program)
+            .chain(Self::names::<ProcessorExtTableColumn>(TableId::Processor))
+            .chain(Self::names::<OpStackExtTableColumn>(TableId::OpStack))
+            .chain(Self::names::<RamExtTableColumn>(TableId::Ram))
+            .chain(Self::names::<JumpStackExtTableColumn>(TableId::JumpStack))
+            .chain(Self::names::<HashExtTableColumn>(TableId::Hash))
+            .chain(Self::names::<CascadeExtTableColumn>(TableId::Cascade))
+            .chain(Self::names::<LookupExtTableColumn>(TableId::Lookup))
+            .chain(Self::names::<U32ExtTableColumn>(TableId::U32))
+            .chain(Self::names::<DegreeLoweringExtTableColumn>(
+                TableId::DegreeLowering,
+            ))
+            .collect()
+    }
+}
+
 /// A Master Table is, in some sense, a top-level table of Triton VM. It contains all the data
 /// but little logic beyond bookkeeping and presenting the data in useful ways. Conversely, the
 /// individual tables contain no data but all the respective logic. Master Tables are
@@ -269,11 +347,19 @@ where
 
     /// Set all rows _not_ part of the actual (padded) trace to random values.
     fn randomize_trace(&mut self) {
+        self.randomize_trace_with_rng(&mut thread_rng());
+    }
+
+    /// Like [`randomize_trace`](Self::randomize_trace), but samples the trace randomizers from
+    /// the given random number generator. Useful for reproducibility, for example, in tests.
+    ///
+    /// See also [`Stark::prove_with_rng`](crate::stark::Stark::prove_with_rng).
+    fn randomize_trace_with_rng<R: Rng>(&mut self, rng: &mut R) {
         let unit_distance = self.randomized_trace_domain().length / self.trace_domain().length;
         (1..unit_distance).for_each(|offset| {
-            self.randomized_trace_table_mut()
-                .slice_mut(s![offset..; unit_distance, ..])
-                .par_mapv_inplace(|_| random::<FF>())
+            let mut randomized_trace_table = self.randomized_trace_table_mut();
+            let randomizer_rows = randomized_trace_table.slice_mut(s![offset..; unit_distance, ..]);
+            fill_with_random_values(randomizer_rows, rng);
         });
     }
 
@@ -814,6 +900,66 @@ impl MasterBaseTable {
         master_base_table
     }
 
+    /// Change the number of trace randomizers, _i.e._, the number of randomized rows interleaved
+    /// with the trace. Defaults to the number passed to [`new`](Self::new). More randomizers
+    /// strengthen the zero-knowledge property at the cost of prover performance. When proving,
+    /// the number of trace randomizers is
+    /// [`Stark::num_trace_randomizers`](crate::stark::Stark::num_trace_randomizers).
+    ///
+    /// The trace is preserved, but any previously sampled trace randomizers, low-degree
+    /// extension, or interpolation polynomials are discarded. The quotient and FRI domains are
+    /// not changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the quotient domain or the FRI domain is too small for the resulting
+    /// [interpolant degree](interpolant_degree).
+    pub fn with_num_randomizers(
+        mut self,
+        num_trace_randomizers: usize,
+    ) -> Result<Self, NumRandomizersError> {
+        let padded_height = self.trace_domain.length;
+        let randomized_padded_trace_len =
+            randomized_padded_trace_len(padded_height, num_trace_randomizers);
+        let interpolant_degree = interpolant_degree(padded_height, num_trace_randomizers);
+        let max_degree = max_degree_with_origin(interpolant_degree, padded_height).degree;
+        if self.quotient_domain.length as isize <= max_degree {
+            return Err(NumRandomizersError::QuotientDomainTooSmall {
+                num_trace_randomizers,
+                domain_length: self.quotient_domain.length,
+            });
+        }
+        if self.fri_domain.length <= randomized_padded_trace_len {
+            return Err(NumRandomizersError::FriDomainTooSmall {
+                num_trace_randomizers,
+                domain_length: self.fri_domain.length,
+            });
+        }
+
+        let trace_table = self.trace_table().to_owned();
+        let unit_distance = randomized_padded_trace_len / padded_height;
+
+        let mut randomized_trace_table =
+            Array2::zeros([randomized_padded_trace_len, NUM_BASE_COLUMNS].f());
+        randomized_trace_table
+            .slice_mut(s![..; unit_distance, ..])
+            .assign(&trace_table);
+
+        self.num_trace_randomizers = num_trace_randomizers;
+        self.randomized_trace_domain =
+            ArithmeticDomain::of_length(randomized_padded_trace_len).unwrap();
+        self.randomized_trace_table = randomized_trace_table;
+        self.low_degree_extended_table = None;
+        self.interpolation_polynomials = None;
+        Ok(self)
+    }
+
+    /// The number of trace randomizers. See also
+    /// [`with_num_randomizers`](Self::with_num_randomizers).
+    pub fn num_randomizers(&self) -> usize {
+        self.num_trace_randomizers
+    }
+
     /// Pad the trace to the next power of two using the various, table-specific padding rules.
     /// All tables must have the same height for reasons of verifier efficiency.
     /// Furthermore, that height must be a power of two for reasons of prover efficiency.
@@ -859,6 +1005,73 @@ impl MasterBaseTable {
         profiler!(stop "fill degree-lowering table");
     }
 
+    /// The smallest and the largest value, in canonical representation, of every column in the
+    /// trace, _i.e._, excluding the randomizers. Helpful for catching anomalies like a column
+    /// that should be binary containing some large value.
+    pub fn column_stats(&self) -> Vec<(ColumnName, BFieldElement, BFieldElement)> {
+        let min_and_max = self
+            .trace_table()
+            .axis_iter(Axis(1))
+            .into_par_iter()
+            .map(|column| {
+                let min_max = column.iter().minmax_by_key(|element| element.value());
+                let (&min, &max) = min_max.into_option().unwrap();
+                (min, max)
+            })
+            .collect::<Vec<_>>();
+
+        ColumnName::all_base_columns()
+            .into_iter()
+            .zip_eq(min_and_max)
+            .map(|(name, (min, max))| (name, min, max))
+            .collect()
+    }
+
+    /// The trace, _i.e._, the table excluding the randomizers, as an Apache Arrow
+    /// [`RecordBatch`](arrow_array::RecordBatch). Every column is of type `UInt64`, holding the
+    /// canonical representation of the respective [`BFieldElement`]s, and is named like the
+    /// corresponding [`ColumnName`], _e.g._, `Processor::CLK`.
+    #[cfg(feature = "arrow")]
+    pub fn to_record_batch(&self) -> arrow_array::RecordBatch {
+        use std::sync::Arc;
+
+        use arrow_array::ArrayRef;
+        use arrow_array::UInt64Array;
+        use arrow_schema::DataType;
+        use arrow_schema::Field;
+        use arrow_schema::Schema;
+
+        let fields = ColumnName::all_base_columns()
+            .into_iter()
+            .map(|name| Field::new(name.to_string(), DataType::UInt64, false))
+            .collect_vec();
+        let columns = self
+            .trace_table()
+            .axis_iter(Axis(1))
+            .map(|column| UInt64Array::from_iter_values(column.iter().map(|bfe| bfe.value())))
+            .map(|column| Arc::new(column) as ArrayRef)
+            .collect_vec();
+
+        let schema = Arc::new(Schema::new(fields));
+        arrow_array::RecordBatch::try_new(schema, columns).unwrap()
+    }
+
+    /// Recompute the terminal of the server side of the clock jump difference lookup argument,
+    /// _i.e._, the Processor Table's, from the base trace and compare it against the sum of the
+    /// client sides' terminals, _i.e._, those of the Op Stack Table, the RAM Table, and the Jump
+    /// Stack Table. Helpful for debugging the cross-table argument.
+    pub fn clock_jump_difference_lookup_terminals_match(
+        &self,
+        master_ext_table: &MasterExtTable,
+        challenges: &Challenges,
+    ) -> bool {
+        let server_terminal = ProcessorTable::clock_jump_difference_lookup_server_terminal(
+            self.table(TableId::Processor),
+            challenges,
+        );
+        server_terminal == master_ext_table.clock_jump_difference_lookup_client_terminals_sum()
+    }
+
     fn all_pad_functions() -> [PadFunction; NUM_TABLES_WITHOUT_DEGREE_LOWERING] {
         [
             ProgramTable::pad_trace,
@@ -894,15 +1107,21 @@ impl MasterBaseTable {
     /// table. The `.extend()` for each table is specific to that table, but always involves
     /// adding some number of columns.
     pub fn extend(&self, challenges: &Challenges) -> MasterExtTable {
+        self.extend_with_rng(challenges, &mut thread_rng())
+    }
+
+    /// Like [`extend`](Self::extend), but samples the randomizer polynomials from the given
+    /// random number generator.
+    pub fn extend_with_rng<R: Rng>(&self, challenges: &Challenges, rng: &mut R) -> MasterExtTable {
         // randomizer polynomials
         let num_rows = self.randomized_trace_table().nrows();
         profiler!(start "initialize master table");
         let mut randomized_trace_extension_table =
             fast_zeros_column_major::<XFieldElement>(num_rows, NUM_EXT_COLUMNS);
 
-        randomized_trace_extension_table
-            .slice_mut(s![.., NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS..])
-            .par_mapv_inplace(|_| random::<XFieldElement>());
+        let randomizer_polynomials = randomized_trace_extension_table
+            .slice_mut(s![.., NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS..]);
+        fill_with_random_values(randomizer_polynomials, rng);
         profiler!(stop "initialize master table");
 
         let mut master_ext_table = MasterExtTable {
@@ -1066,6 +1285,72 @@ impl MasterExtTable {
             .slice_mut(s![..; unit_distance, column_indices])
     }
 
+    /// The sum of the terminals of all client sides of the clock jump difference lookup
+    /// argument, _i.e._, of the Op Stack Table, the RAM Table, and the Jump Stack Table.
+    pub fn clock_jump_difference_lookup_client_terminals_sum(&self) -> XFieldElement {
+        let terminal = |table_id, column_index| {
+            let table = self.table(table_id);
+            table[[table.nrows() - 1, column_index]]
+        };
+
+        let op_stack_cjdld = OpStackExtTableColumn::ClockJumpDifferenceLookupClientLogDerivative;
+        let ram_cjdld = RamExtTableColumn::ClockJumpDifferenceLookupClientLogDerivative;
+        let j_stack_cjdld = JumpStackExtTableColumn::ClockJumpDifferenceLookupClientLogDerivative;
+
+        terminal(TableId::OpStack, op_stack_cjdld.ext_table_index())
+            + terminal(TableId::Ram, ram_cjdld.ext_table_index())
+            + terminal(TableId::JumpStack, j_stack_cjdld.ext_table_index())
+    }
+
+    /// The trace, _i.e._, the table excluding the randomizers, as an Apache Arrow
+    /// [`RecordBatch`](arrow_array::RecordBatch). Every column is a `Struct` with the three
+    /// `UInt64` fields `c0`, `c1`, and `c2`, holding the canonical representation of the
+    /// respective [`XFieldElement`]'s coefficients. The columns are named like the corresponding
+    /// [`ColumnName`], _e.g._, `Processor::InputTableEvalArg`. The columns holding the randomizer
+    /// polynomials are not part of the trace and are therefore not exported.
+    #[cfg(feature = "arrow")]
+    pub fn to_record_batch(&self) -> arrow_array::RecordBatch {
+        use std::sync::Arc;
+
+        use arrow_array::ArrayRef;
+        use arrow_array::StructArray;
+        use arrow_array::UInt64Array;
+        use arrow_schema::DataType;
+        use arrow_schema::Field;
+        use arrow_schema::Fields;
+        use arrow_schema::Schema;
+        use twenty_first::prelude::x_field_element::EXTENSION_DEGREE;
+
+        let coefficient_fields = (0..EXTENSION_DEGREE)
+            .map(|i| Field::new(format!("c{i}"), DataType::UInt64, false))
+            .collect::<Fields>();
+        let fields = ColumnName::all_ext_columns()
+            .into_iter()
+            .map(|name| {
+                let data_type = DataType::Struct(coefficient_fields.clone());
+                Field::new(name.to_string(), data_type, false)
+            })
+            .collect_vec();
+
+        let to_struct_array = |column: ArrayView1<XFieldElement>| {
+            let coefficients = (0..EXTENSION_DEGREE)
+                .map(|i| column.iter().map(move |xfe| xfe.coefficients[i].value()))
+                .map(|coefficient| Arc::new(UInt64Array::from_iter_values(coefficient)) as ArrayRef)
+                .collect_vec();
+            let array = StructArray::new(coefficient_fields.clone(), coefficients, None);
+            Arc::new(array) as ArrayRef
+        };
+        let columns = self
+            .trace_table()
+            .slice(s![.., ..NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS])
+            .axis_iter(Axis(1))
+            .map(to_struct_array)
+            .collect_vec();
+
+        let schema = Arc::new(Schema::new(fields));
+        arrow_array::RecordBatch::try_new(schema, columns).unwrap()
+    }
+
     pub(crate) fn try_to_ext_row(row: Array1<XFieldElement>) -> Result<ExtensionRow, ProvingError> {
         let err = || ProvingError::TableRowConversionError {
             expected_len: NUM_EXT_COLUMNS,
@@ -1258,6 +1543,27 @@ pub fn all_quotients_combined(
     quotient_codeword
 }
 
+/// Set every element of the given table to a random value. To allow sampling in parallel, each
+/// column gets its own random number generator, seeded from the given one.
+fn fill_with_random_values<FF, R>(mut table: ArrayViewMut2<FF>, rng: &mut R)
+where
+    FF: Clone + Send + Sync,
+    R: Rng,
+    Standard: Distribution<FF>,
+{
+    let column_seeds = (0..table.ncols())
+        .map(|_| rng.gen::<<StdRng as SeedableRng>::Seed>())
+        .collect_vec();
+    table
+        .axis_iter_mut(Axis(1))
+        .into_par_iter()
+        .zip(column_seeds)
+        .for_each(|(mut column, seed)| {
+            let mut column_rng = StdRng::from_seed(seed);
+            column.mapv_inplace(|_| column_rng.gen());
+        });
+}
+
 /// Guaranteed to be a power of two.
 pub fn randomized_padded_trace_len(padded_height: usize, num_trace_randomizers: usize) -> usize {
     let total_table_length = padded_height + num_trace_randomizers;
@@ -1273,12 +1579,16 @@ mod tests {
     use fs_err as fs;
     use std::path::Path;
 
+    use assert2::assert;
+    use assert2::let_assert;
     use master_table::cross_table_argument::GrandCrossTableArg;
     use ndarray::s;
     use ndarray::Array2;
     use num_traits::Zero;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
     use strum::EnumCount;
     use strum::IntoEnumIterator;
     use strum::VariantNames;
@@ -1291,7 +1601,7 @@ mod tests {
     use crate::instruction::tests::InstructionBucket;
     use crate::instruction::Instruction;
     use crate::instruction::InstructionBit;
-    use crate::shared_tests::ProgramAndInput;
+    use crate::program::ProgramAndInput;
     use crate::stark::tests::*;
     use crate::table::degree_lowering_table::DegreeLoweringBaseTableColumn;
     use crate::table::degree_lowering_table::DegreeLoweringExtTableColumn;
@@ -1986,6 +2296,158 @@ mod tests {
         assert_eq!(0, not_trace_domain_element(EXT_U32_TABLE_START));
     }
 
+    #[test]
+    fn all_base_column_names_correspond_to_master_base_table_columns() {
+        let names = ColumnName::all_base_columns();
+        assert_eq!(NUM_BASE_COLUMNS, names.len());
+
+        let ib0 = ProcessorBaseTableColumn::IB0.master_base_table_index();
+        assert_eq!("Processor::IB0", names[ib0].to_string());
+    }
+
+    #[test]
+    fn all_ext_column_names_correspond_to_master_ext_table_columns() {
+        let names = ColumnName::all_ext_columns();
+        assert_eq!(NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS, names.len());
+
+        let input_table_eval_arg = ProcessorExtTableColumn::InputTableEvalArg;
+        let idx = input_table_eval_arg.master_ext_table_index();
+        assert_eq!("Processor::InputTableEvalArg", names[idx].to_string());
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn master_tables_round_trip_through_arrow_record_batches() {
+        use arrow_array::cast::AsArray;
+        use arrow_array::types::UInt64Type;
+
+        let program = ProgramAndInput::new(crate::example_programs::FIBONACCI_SEQUENCE.clone())
+            .with_input(bfe_array![10]);
+        let (_, _, master_base_table, master_ext_table, _) =
+            master_tables_for_low_security_level(program);
+
+        let base_batch = master_base_table.to_record_batch();
+        let base_trace = master_base_table.trace_table();
+        assert_eq!(NUM_BASE_COLUMNS, base_batch.num_columns());
+        assert_eq!(base_trace.nrows(), base_batch.num_rows());
+        for (name, column) in ColumnName::all_base_columns()
+            .iter()
+            .zip_eq(base_trace.columns())
+        {
+            let arrow_column = base_batch.column_by_name(&name.to_string()).unwrap();
+            let arrow_column = arrow_column.as_primitive::<UInt64Type>();
+            let round_tripped = arrow_column.values().iter().map(|&v| bfe!(v));
+            assert!(column.iter().copied().eq(round_tripped));
+        }
+
+        let ext_batch = master_ext_table.to_record_batch();
+        let ext_trace = master_ext_table.trace_table();
+        let ext_trace = ext_trace.slice(s![.., ..NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS]);
+        assert_eq!(
+            NUM_EXT_COLUMNS_WITHOUT_RANDOMIZER_POLYS,
+            ext_batch.num_columns()
+        );
+        assert_eq!(ext_trace.nrows(), ext_batch.num_rows());
+        for (name, column) in ColumnName::all_ext_columns()
+            .iter()
+            .zip_eq(ext_trace.columns())
+        {
+            let arrow_column = ext_batch.column_by_name(&name.to_string()).unwrap();
+            let coefficients = arrow_column
+                .as_struct()
+                .columns()
+                .iter()
+                .map(|c| c.as_primitive::<UInt64Type>().values().clone())
+                .collect_vec();
+            let round_tripped = (0..ext_batch.num_rows()).map(|row| {
+                let [c0, c1, c2] = [0, 1, 2].map(|i| bfe!(coefficients[i][row]));
+                xfe!([c0, c1, c2])
+            });
+            assert!(column.iter().copied().eq(round_tripped));
+        }
+    }
+
+    #[test]
+    fn instruction_bits_are_binary_according_to_column_stats() {
+        let program = ProgramAndInput::new(crate::example_programs::FIBONACCI_SEQUENCE.clone())
+            .with_input(bfe_array![100]);
+        let (_, _, mut master_base_table) = master_base_table_for_low_security_level(program);
+        master_base_table.pad();
+
+        let column_stats = master_base_table.column_stats();
+        assert_eq!(NUM_BASE_COLUMNS, column_stats.len());
+
+        let ib0 = ProcessorBaseTableColumn::IB0.master_base_table_index();
+        let (ref name, _, max) = column_stats[ib0];
+        assert_eq!("Processor::IB0", name.to_string());
+        assert!(max.value() <= 1);
+    }
+
+    #[test]
+    fn clock_jump_difference_lookup_server_terminal_equals_sum_of_client_terminals() {
+        let program = triton_program! {
+            push 5 push 42 write_mem 1 pop 1
+            call foo call foo
+            push 42 read_mem 1 pop 2 halt
+            foo: push 1 push 2 swap 1 pop 2 return
+        };
+        let (_, _, master_base_table, master_ext_table, challenges) =
+            master_tables_for_low_security_level(ProgramAndInput::new(program));
+
+        let processor_table = master_ext_table.table(TableId::Processor);
+        let processor_cjdld = ProcessorExtTableColumn::ClockJumpDifferenceLookupServerLogDerivative;
+        let processor_terminal = processor_table[[
+            processor_table.nrows() - 1,
+            processor_cjdld.ext_table_index(),
+        ]];
+        let recomputed_terminal = ProcessorTable::clock_jump_difference_lookup_server_terminal(
+            master_base_table.table(TableId::Processor),
+            &challenges,
+        );
+        assert_eq!(processor_terminal, recomputed_terminal);
+
+        let client_terminals_sum =
+            master_ext_table.clock_jump_difference_lookup_client_terminals_sum();
+        assert_eq!(recomputed_terminal, client_terminals_sum);
+        assert!(master_base_table
+            .clock_jump_difference_lookup_terminals_match(&master_ext_table, &challenges));
+    }
+
+    #[test]
+    fn same_rng_seed_and_number_of_randomizers_give_identical_merkle_roots() {
+        let program = ProgramAndInput::new(triton_program!(push 3 push 4 add pop 1 halt));
+        let (_, _, mut master_base_table) = master_base_table_for_low_security_level(program);
+        master_base_table.pad();
+
+        let num_randomizers = 2;
+        let commit = |seed| {
+            let mut table = master_base_table
+                .clone()
+                .with_num_randomizers(num_randomizers)
+                .unwrap();
+            assert!(num_randomizers == table.num_randomizers());
+            assert!(master_base_table.trace_table() == table.trace_table());
+
+            table.randomize_trace_with_rng(&mut StdRng::seed_from_u64(seed));
+            table.low_degree_extend_all_columns();
+            table.merkle_tree().root()
+        };
+
+        assert!(commit(42) == commit(42));
+        assert!(commit(42) != commit(43));
+    }
+
+    #[test]
+    fn too_many_randomizers_for_the_table_domains_give_appropriate_error() {
+        let program = ProgramAndInput::new(triton_program!(push 3 push 4 add pop 1 halt));
+        let (_, _, mut master_base_table) = master_base_table_for_low_security_level(program);
+        master_base_table.pad();
+
+        let num_randomizers = master_base_table.fri_domain().length;
+        let_assert!(Err(err) = master_base_table.with_num_randomizers(num_randomizers));
+        assert!(let NumRandomizersError::QuotientDomainTooSmall { .. } = err);
+    }
+
     #[proptest]
     fn test_sponge_with_pending_absorb(
         #[strategy(arb())] elements: Vec<BFieldElement>,
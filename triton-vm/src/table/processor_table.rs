@@ -1,4 +1,6 @@
 use std::cmp::max;
+use std::io;
+use std::io::Write;
 use std::ops::Mul;
 
 use itertools::izip;
@@ -8,6 +10,9 @@ use ndarray::*;
 use num_traits::ConstOne;
 use num_traits::One;
 use num_traits::Zero;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
 use strum::EnumCount;
 use strum::IntoEnumIterator;
 use twenty_first::math::traits::FiniteField;
@@ -15,9 +20,12 @@ use twenty_first::prelude::x_field_element::EXTENSION_DEGREE;
 use twenty_first::prelude::*;
 
 use crate::aet::AlgebraicExecutionTrace;
+use crate::error::OpStackElementError;
+use crate::error::TraceError;
 use crate::instruction::AnInstruction::*;
 use crate::instruction::Instruction;
 use crate::instruction::InstructionBit;
+use crate::instruction::StackEffect;
 use crate::instruction::ALL_INSTRUCTIONS;
 use crate::ndarray_helper::contiguous_column_slices;
 use crate::ndarray_helper::horizontal_multi_slice_mut;
@@ -32,10 +40,13 @@ use crate::table::constraint_circuit::DualRowIndicator::*;
 use crate::table::constraint_circuit::SingleRowIndicator::*;
 use crate::table::constraint_circuit::*;
 use crate::table::cross_table_argument::*;
+use crate::table::master_table::NUM_BASE_COLUMNS;
+use crate::table::master_table::NUM_EXT_COLUMNS;
 use crate::table::ram_table;
 use crate::table::table_column::ProcessorBaseTableColumn::*;
 use crate::table::table_column::ProcessorExtTableColumn::*;
 use crate::table::table_column::*;
+use crate::table::ConstraintCounts;
 
 pub const BASE_WIDTH: usize = ProcessorBaseTableColumn::COUNT;
 pub const EXT_WIDTH: usize = ProcessorExtTableColumn::COUNT;
@@ -53,8 +64,65 @@ impl ProcessorTable {
         clk_jump_diffs_jump_stack: &[BFieldElement],
     ) {
         let num_rows = aet.processor_trace.nrows();
-        let mut clk_jump_diff_multiplicities = Array1::zeros([num_rows]);
+        let clk_jump_diff_multiplicities = Self::clock_jump_difference_multiplicities(
+            num_rows,
+            clk_jump_diffs_op_stack,
+            clk_jump_diffs_ram,
+            clk_jump_diffs_jump_stack,
+        );
+
+        let mut processor_table = processor_table.slice_mut(s![0..num_rows, ..]);
+        processor_table.assign(&aet.processor_trace);
+        processor_table
+            .column_mut(ClockJumpDifferenceLookupMultiplicity.base_table_index())
+            .assign(&clk_jump_diff_multiplicities);
+    }
 
+    /// Like [`fill_trace`](Self::fill_trace), but the processor trace is supplied in batches of
+    /// consecutive rows instead of all at once. This way, the full processor trace never needs
+    /// to be materialized in addition to the processor table itself.
+    ///
+    /// The clock jump difference lookup multiplicities can be computed with
+    /// [`clock_jump_difference_multiplicities`](Self::clock_jump_difference_multiplicities).
+    /// Their number must equal the total number of rows across all batches.
+    pub fn fill_trace_chunked<I>(
+        processor_table: &mut ArrayViewMut2<BFieldElement>,
+        row_batches: I,
+        clk_jump_diff_multiplicities: &Array1<BFieldElement>,
+    ) where
+        I: IntoIterator<Item = Array2<BFieldElement>>,
+    {
+        let mut num_rows = 0;
+        for row_batch in row_batches {
+            let batch_end = num_rows + row_batch.nrows();
+            processor_table
+                .slice_mut(s![num_rows..batch_end, ..])
+                .assign(&row_batch);
+            num_rows = batch_end;
+        }
+
+        assert_eq!(
+            num_rows,
+            clk_jump_diff_multiplicities.len(),
+            "Number of clock jump difference multiplicities must match number of rows."
+        );
+        processor_table
+            .slice_mut(s![
+                0..num_rows,
+                ClockJumpDifferenceLookupMultiplicity.base_table_index()
+            ])
+            .assign(clk_jump_diff_multiplicities);
+    }
+
+    /// The number of times each clock cycle, in `0..num_rows`, is looked up as a clock jump
+    /// difference by any of the memory-like tables.
+    pub fn clock_jump_difference_multiplicities(
+        num_rows: usize,
+        clk_jump_diffs_op_stack: &[BFieldElement],
+        clk_jump_diffs_ram: &[BFieldElement],
+        clk_jump_diffs_jump_stack: &[BFieldElement],
+    ) -> Array1<BFieldElement> {
+        let mut clk_jump_diff_multiplicities = Array1::zeros([num_rows]);
         for clk_jump_diff in clk_jump_diffs_op_stack
             .iter()
             .chain(clk_jump_diffs_ram)
@@ -63,48 +131,153 @@ impl ProcessorTable {
             let clk = clk_jump_diff.value() as usize;
             clk_jump_diff_multiplicities[clk] += BFieldElement::ONE;
         }
+        clk_jump_diff_multiplicities
+    }
 
-        let mut processor_table = processor_table.slice_mut(s![0..num_rows, ..]);
-        processor_table.assign(&aet.processor_trace);
-        processor_table
-            .column_mut(ClockJumpDifferenceLookupMultiplicity.base_table_index())
-            .assign(&clk_jump_diff_multiplicities);
+    /// Check that the clock jump difference lookup multiplicities of the given Processor Table,
+    /// padded or not, agree with the clock jump differences of the memory-like tables. Here,
+    /// `processor_table_len` is the length of the Processor Table before padding. The
+    /// multiplicity of clock jump difference 1 must account for one additional lookup per padding
+    /// row; see [`pad_trace`](Self::pad_trace).
+    pub fn verify_clk_jump_diff_consistency(
+        processor_table: ArrayView2<BFieldElement>,
+        processor_table_len: usize,
+        clk_jump_diffs_op_stack: &[BFieldElement],
+        clk_jump_diffs_ram: &[BFieldElement],
+        clk_jump_diffs_jump_stack: &[BFieldElement],
+    ) -> Result<(), TraceError> {
+        let num_rows = processor_table.nrows();
+        let out_of_range_clk_jump_diff = clk_jump_diffs_op_stack
+            .iter()
+            .chain(clk_jump_diffs_ram)
+            .chain(clk_jump_diffs_jump_stack)
+            .find(|clk_jump_diff| clk_jump_diff.value() >= num_rows as u64);
+        if let Some(clk_jump_diff) = out_of_range_clk_jump_diff {
+            return Err(TraceError::ClockJumpDifferenceOutOfRange {
+                clock_jump_difference: clk_jump_diff.value(),
+                num_rows,
+            });
+        }
+
+        let mut expected_multiplicities = Self::clock_jump_difference_multiplicities(
+            num_rows,
+            clk_jump_diffs_op_stack,
+            clk_jump_diffs_ram,
+            clk_jump_diffs_jump_stack,
+        );
+        if num_rows > 1 {
+            let num_padding_rows = num_rows.saturating_sub(processor_table_len);
+            expected_multiplicities[1] += bfe!(num_padding_rows as u64);
+        }
+
+        let multiplicities =
+            processor_table.column(ClockJumpDifferenceLookupMultiplicity.base_table_index());
+        let mismatch = multiplicities
+            .iter()
+            .zip_eq(&expected_multiplicities)
+            .position(|(multiplicity, expected)| multiplicity != expected);
+        match mismatch {
+            Some(row) => Err(TraceError::InconsistentClockJumpDifferenceMultiplicity { row }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that the current instruction `CI` of every row equals the weighted sum of the
+    /// row's instruction bits. This is the trace-level counterpart of the consistency constraint
+    /// linking `CI` to its bit decomposition, and allows spotting a malformed trace without
+    /// having to go through proving.
+    pub fn verify_instruction_bits(
+        processor_trace: ArrayView2<BFieldElement>,
+    ) -> Result<(), TraceError> {
+        let instruction_bits = [IB0, IB1, IB2, IB3, IB4, IB5, IB6];
+        for (row_idx, row) in processor_trace.rows().into_iter().enumerate() {
+            let ib_composition = instruction_bits
+                .into_iter()
+                .enumerate()
+                .map(|(i, ib)| bfe!(1_u64 << i) * row[ib.base_table_index()])
+                .sum::<BFieldElement>();
+            if row[CI.base_table_index()] != ib_composition {
+                return Err(TraceError::InconsistentInstructionBits { row: row_idx });
+            }
+        }
+        Ok(())
     }
 
-    pub fn pad_trace(
+    pub fn pad_trace(processor_table: ArrayViewMut2<BFieldElement>, processor_table_len: usize) {
+        let target_len = processor_table.nrows();
+        Self::pad_trace_to(processor_table, processor_table_len, target_len);
+    }
+
+    /// Like [`pad_trace`](Self::pad_trace), but only pads the rows
+    /// `processor_table_len..target_len`. Rows from `target_len` onwards are left untouched.
+    pub fn pad_trace_to(
         mut processor_table: ArrayViewMut2<BFieldElement>,
         processor_table_len: usize,
+        target_len: usize,
     ) {
         assert!(
             processor_table_len > 0,
             "Processor Table must have at least one row."
         );
+        assert!(
+            processor_table_len <= target_len && target_len <= processor_table.nrows(),
+            "Target length {target_len} must be in range \
+            [{processor_table_len}, {}].",
+            processor_table.nrows()
+        );
         let mut padding_template = processor_table.row(processor_table_len - 1).to_owned();
         padding_template[IsPadding.base_table_index()] = bfe!(1);
         padding_template[ClockJumpDifferenceLookupMultiplicity.base_table_index()] = bfe!(0);
         processor_table
-            .slice_mut(s![processor_table_len.., ..])
+            .slice_mut(s![processor_table_len..target_len, ..])
             .axis_iter_mut(Axis(0))
             .into_par_iter()
             .for_each(|mut row| row.assign(&padding_template));
 
-        let clk_range = processor_table_len..processor_table.nrows();
+        let clk_range = processor_table_len..target_len;
         let clk_col = Array1::from_iter(clk_range.map(|a| bfe!(a as u64)));
         clk_col.move_into(
-            processor_table.slice_mut(s![processor_table_len.., CLK.base_table_index()]),
+            processor_table.slice_mut(s![processor_table_len..target_len, CLK.base_table_index()]),
         );
 
         // The Jump Stack Table does not have a padding indicator. Hence, clock jump differences are
         // being looked up in its padding sections. The clock jump differences in that section are
         // always 1. The lookup multiplicities of clock value 1 must be increased accordingly: one
         // per padding row.
-        let num_padding_rows = processor_table.nrows() - processor_table_len;
+        let num_padding_rows = target_len - processor_table_len;
         let num_padding_rows = bfe!(num_padding_rows as u64);
         let mut row_1 = processor_table.row_mut(1);
 
         row_1[ClockJumpDifferenceLookupMultiplicity.base_table_index()] += num_padding_rows;
     }
 
+    /// Write the given Processor Table, padded or not, as comma-separated values. The first line
+    /// is a header naming the columns; every following line corresponds to one row of the table.
+    /// Elements are written in their canonical representation.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if the table does not have the Processor
+    /// Table's width, without writing anything.
+    pub fn write_trace_csv<W: Write>(
+        processor_table: ArrayView2<BFieldElement>,
+        out: &mut W,
+    ) -> io::Result<()> {
+        if processor_table.ncols() != BASE_WIDTH {
+            let num_cols = processor_table.ncols();
+            let message = format!("expected {BASE_WIDTH} columns but got {num_cols}");
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+        }
+
+        writeln!(out, "{}", ProcessorBaseTableColumn::iter().join(","))?;
+        for row in processor_table.rows() {
+            writeln!(
+                out,
+                "{}",
+                row.iter().map(|element| element.value()).join(",")
+            )?;
+        }
+        Ok(())
+    }
+
     pub fn extend(
         base_table: ArrayView2<BFieldElement>,
         mut ext_table: ArrayViewMut2<XFieldElement>,
@@ -398,92 +571,116 @@ impl ProcessorTable {
         base_table: ArrayView2<BFieldElement>,
         challenges: &Challenges,
     ) -> Array2<XFieldElement> {
+        let compressed_rows_per_step = base_table
+            .rows()
+            .into_iter()
+            .tuple_windows()
+            .map(|(previous_row, current_row)| {
+                Self::u32_lookup_rows(previous_row, current_row, challenges)
+            })
+            .collect_vec();
+
         // collect elements to be inverted for more performant batch inversion
-        let mut to_invert = vec![];
-        for (previous_row, current_row) in base_table.rows().into_iter().tuple_windows() {
-            let previous_ci = previous_row[CI.base_table_index()];
-            if previous_ci == Instruction::Split.opcode_b() {
-                let compressed_row = current_row[ST0.base_table_index()] * challenges[U32LhsWeight]
-                    + current_row[ST1.base_table_index()] * challenges[U32RhsWeight]
-                    + previous_row[CI.base_table_index()] * challenges[U32CiWeight];
-                to_invert.push(challenges[U32Indeterminate] - compressed_row);
-            } else if previous_ci == Instruction::Lt.opcode_b()
-                || previous_ci == Instruction::And.opcode_b()
-                || previous_ci == Instruction::Pow.opcode_b()
-            {
-                let compressed_row = previous_row[ST0.base_table_index()]
-                    * challenges[U32LhsWeight]
-                    + previous_row[ST1.base_table_index()] * challenges[U32RhsWeight]
-                    + previous_row[CI.base_table_index()] * challenges[U32CiWeight]
-                    + current_row[ST0.base_table_index()] * challenges[U32ResultWeight];
-                to_invert.push(challenges[U32Indeterminate] - compressed_row);
-            } else if previous_ci == Instruction::Xor.opcode_b() {
+        let to_invert = compressed_rows_per_step
+            .iter()
+            .flatten()
+            .map(|&compressed_row| challenges[U32Indeterminate] - compressed_row)
+            .collect();
+        let mut inverses = XFieldElement::batch_inversion(to_invert).into_iter();
+
+        // populate column with inverses
+        let mut u32_table_running_sum_log_derivative = LookupArg::default_initial();
+        let mut extension_column = Vec::with_capacity(base_table.nrows());
+        extension_column.push(u32_table_running_sum_log_derivative);
+        for compressed_rows in compressed_rows_per_step {
+            for _ in compressed_rows {
+                u32_table_running_sum_log_derivative += inverses.next().unwrap();
+            }
+            extension_column.push(u32_table_running_sum_log_derivative);
+        }
+
+        Array2::from_shape_vec((base_table.nrows(), 1), extension_column).unwrap()
+    }
+
+    /// The compressed rows the Processor Table sends to the U32 Table when transitioning from
+    /// `previous_row` to `current_row`. Empty if the instruction executed in `previous_row` is
+    /// not a [u32 instruction](Instruction::is_u32_instruction).
+    fn u32_lookup_rows(
+        previous_row: ArrayView1<BFieldElement>,
+        current_row: ArrayView1<BFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<XFieldElement> {
+        let Some(instruction) = Self::instruction_from_row(previous_row) else {
+            return vec![];
+        };
+        if !instruction.is_u32_instruction() {
+            return vec![];
+        }
+
+        let previous_ci = previous_row[CI.base_table_index()];
+        let st0_prev = previous_row[ST0.base_table_index()];
+        let st1_prev = previous_row[ST1.base_table_index()];
+        let st0 = current_row[ST0.base_table_index()];
+        let st1 = current_row[ST1.base_table_index()];
+
+        match instruction {
+            Instruction::Split => {
+                let compressed_row = st0 * challenges[U32LhsWeight]
+                    + st1 * challenges[U32RhsWeight]
+                    + previous_ci * challenges[U32CiWeight];
+                vec![compressed_row]
+            }
+            Instruction::Lt | Instruction::And | Instruction::Pow => {
+                let compressed_row = st0_prev * challenges[U32LhsWeight]
+                    + st1_prev * challenges[U32RhsWeight]
+                    + previous_ci * challenges[U32CiWeight]
+                    + st0 * challenges[U32ResultWeight];
+                vec![compressed_row]
+            }
+            Instruction::Xor => {
                 // Triton VM uses the following equality to compute the results of both the
                 // `and` and `xor` instruction using the u32 coprocessor's `and` capability:
                 //     a ^ b = a + b - 2 · (a & b)
                 // <=> a & b = (a + b - a ^ b) / 2
-                let st0_prev = previous_row[ST0.base_table_index()];
-                let st1_prev = previous_row[ST1.base_table_index()];
-                let st0 = current_row[ST0.base_table_index()];
                 let from_xor_in_processor_to_and_in_u32_coprocessor =
                     (st0_prev + st1_prev - st0) / bfe!(2);
                 let compressed_row = st0_prev * challenges[U32LhsWeight]
                     + st1_prev * challenges[U32RhsWeight]
                     + Instruction::And.opcode_b() * challenges[U32CiWeight]
                     + from_xor_in_processor_to_and_in_u32_coprocessor * challenges[U32ResultWeight];
-                to_invert.push(challenges[U32Indeterminate] - compressed_row);
-            } else if previous_ci == Instruction::Log2Floor.opcode_b()
-                || previous_ci == Instruction::PopCount.opcode_b()
-            {
-                let compressed_row = previous_row[ST0.base_table_index()]
-                    * challenges[U32LhsWeight]
-                    + previous_row[CI.base_table_index()] * challenges[U32CiWeight]
-                    + current_row[ST0.base_table_index()] * challenges[U32ResultWeight];
-                to_invert.push(challenges[U32Indeterminate] - compressed_row);
-            } else if previous_ci == Instruction::DivMod.opcode_b() {
-                let compressed_row_for_lt_check = current_row[ST0.base_table_index()]
-                    * challenges[U32LhsWeight]
-                    + previous_row[ST1.base_table_index()] * challenges[U32RhsWeight]
+                vec![compressed_row]
+            }
+            Instruction::Log2Floor | Instruction::PopCount => {
+                let compressed_row = st0_prev * challenges[U32LhsWeight]
+                    + previous_ci * challenges[U32CiWeight]
+                    + st0 * challenges[U32ResultWeight];
+                vec![compressed_row]
+            }
+            Instruction::DivMod => {
+                let compressed_row_for_lt_check = st0 * challenges[U32LhsWeight]
+                    + st1_prev * challenges[U32RhsWeight]
                     + Instruction::Lt.opcode_b() * challenges[U32CiWeight]
                     + bfe!(1) * challenges[U32ResultWeight];
-                let compressed_row_for_range_check = previous_row[ST0.base_table_index()]
-                    * challenges[U32LhsWeight]
-                    + current_row[ST1.base_table_index()] * challenges[U32RhsWeight]
+                let compressed_row_for_range_check = st0_prev * challenges[U32LhsWeight]
+                    + st1 * challenges[U32RhsWeight]
                     + Instruction::Split.opcode_b() * challenges[U32CiWeight];
-                to_invert.push(challenges[U32Indeterminate] - compressed_row_for_lt_check);
-                to_invert.push(challenges[U32Indeterminate] - compressed_row_for_range_check);
-            } else if previous_ci == Instruction::MerkleStep.opcode_b() {
+                vec![compressed_row_for_lt_check, compressed_row_for_range_check]
+            }
+            Instruction::MerkleStep => {
                 let compressed_row = previous_row[ST5.base_table_index()]
                     * challenges[U32LhsWeight]
                     + current_row[ST5.base_table_index()] * challenges[U32RhsWeight]
                     + Instruction::Split.opcode_b() * challenges[U32CiWeight];
-                to_invert.push(challenges[U32Indeterminate] - compressed_row);
+                vec![compressed_row]
             }
-        }
-        let mut inverses = XFieldElement::batch_inversion(to_invert).into_iter();
-
-        // populate column with inverses
-        let mut u32_table_running_sum_log_derivative = LookupArg::default_initial();
-        let mut extension_column = Vec::with_capacity(base_table.nrows());
-        extension_column.push(u32_table_running_sum_log_derivative);
-        for (previous_row, _) in base_table.rows().into_iter().tuple_windows() {
-            let previous_ci = previous_row[CI.base_table_index()];
-            if Instruction::try_from(previous_ci)
-                .unwrap()
-                .is_u32_instruction()
-            {
-                u32_table_running_sum_log_derivative += inverses.next().unwrap();
-            }
-
-            // instruction `div_mod` requires a second inverse
-            if previous_ci == Instruction::DivMod.opcode_b() {
-                u32_table_running_sum_log_derivative += inverses.next().unwrap();
+            Instruction::AssertWithCode(_) | Instruction::AssertVectorWithCode(_) => {
+                let compressed_row = previous_row[NIA.base_table_index()]
+                    * challenges[U32LhsWeight]
+                    + Instruction::Split.opcode_b() * challenges[U32CiWeight];
+                vec![compressed_row]
             }
-
-            extension_column.push(u32_table_running_sum_log_derivative);
+            _ => unreachable!("instruction {instruction} is not a u32 instruction"),
         }
-
-        Array2::from_shape_vec((base_table.nrows(), 1), extension_column).unwrap()
     }
 
     fn extension_column_for_clock_jump_difference_lookup_argument(
@@ -515,6 +712,21 @@ impl ProcessorTable {
         Array2::from_shape_vec((base_table.nrows(), 1), extension_column).unwrap()
     }
 
+    /// The terminal of the server side of the clock jump difference lookup argument,
+    /// recomputed from the Processor Table's base columns.
+    pub fn clock_jump_difference_lookup_server_terminal(
+        base_table: ArrayView2<BFieldElement>,
+        challenges: &Challenges,
+    ) -> XFieldElement {
+        let extension_column = Self::extension_column_for_clock_jump_difference_lookup_argument(
+            base_table, challenges,
+        );
+        extension_column
+            .last()
+            .copied()
+            .unwrap_or_else(LookupArg::default_initial)
+    }
+
     fn factor_for_op_stack_table_running_product(
         previous_row: ArrayView1<BFieldElement>,
         current_row: ArrayView1<BFieldElement>,
@@ -532,14 +744,11 @@ impl ProcessorTable {
         };
 
         // shorter stack means relevant information is on top of stack, i.e., in stack registers
-        let row_with_shorter_stack = if previous_instruction.op_stack_size_influence() > 0 {
-            previous_row.view()
-        } else {
-            current_row.view()
+        let (row_with_shorter_stack, op_stack_delta) = match previous_instruction.stack_effect() {
+            StackEffect::Grow(delta) => (previous_row.view(), delta),
+            StackEffect::Shrink(delta) => (current_row.view(), delta),
+            StackEffect::Keep => return default_factor,
         };
-        let op_stack_delta = previous_instruction
-            .op_stack_size_influence()
-            .unsigned_abs() as usize;
 
         let mut factor = default_factor;
         for op_stack_pointer_offset in 0..op_stack_delta {
@@ -647,10 +856,7 @@ impl ProcessorTable {
         accesses
             .into_iter()
             .map(|(ramp, ramv)| {
-                clk * challenges[RamClkWeight]
-                    + instruction_type * challenges[RamInstructionTypeWeight]
-                    + ramp * challenges[RamPointerWeight]
-                    + ramv * challenges[RamValueWeight]
+                ram_table::ram_compressed_row(clk, instruction_type, ramp, ramv, challenges)
             })
             .map(|compressed_row| challenges[RamIndeterminate] - compressed_row)
             .reduce(|l, r| l * r)
@@ -677,7 +883,7 @@ impl ProcessorTable {
         let opcode = row[CI.base_table_index()];
         let instruction = Instruction::try_from(opcode).ok()?;
 
-        if instruction.arg().is_some() {
+        if instruction.has_arg() {
             let arg = row[NIA.base_table_index()];
             return instruction.change_arg(arg).ok();
         }
@@ -685,27 +891,48 @@ impl ProcessorTable {
         Some(instruction)
     }
 
-    fn op_stack_column_by_index(index: usize) -> ProcessorBaseTableColumn {
-        match index {
-            0 => ST0,
-            1 => ST1,
-            2 => ST2,
-            3 => ST3,
-            4 => ST4,
-            5 => ST5,
-            6 => ST6,
-            7 => ST7,
-            8 => ST8,
-            9 => ST9,
-            10 => ST10,
-            11 => ST11,
-            12 => ST12,
-            13 => ST13,
-            14 => ST14,
-            15 => ST15,
-            i => panic!("Op Stack column index must be in [0, 15], not {i}."),
+    /// The [`OpStackElement`] that register `NIA` of the given row refers to. Fails if the
+    /// register does not hold a valid op stack index, as might be the case in a malformed trace.
+    pub fn op_stack_element_from_nia(
+        row: ArrayView1<BFieldElement>,
+    ) -> Result<OpStackElement, OpStackElementError> {
+        OpStackElement::try_from(row[NIA.base_table_index()])
+    }
+
+    /// A human-readable representation of the instruction in the given row, including its
+    /// argument, if any. Never fails: malformed parts, as they might appear in a broken trace,
+    /// are rendered as, _e.g._, `swap <invalid: 16>`.
+    pub fn instruction_string_from_row(row: ArrayView1<BFieldElement>) -> String {
+        let opcode = row[CI.base_table_index()];
+        let Ok(instruction) = Instruction::try_from(opcode) else {
+            return format!("<invalid: {opcode}>");
+        };
+
+        if !instruction.has_arg() {
+            return instruction.to_string();
+        }
+
+        let nia = row[NIA.base_table_index()];
+        let maybe_instruction = match instruction {
+            Pick(_) => Self::op_stack_element_from_nia(row).ok().map(Pick),
+            Place(_) => Self::op_stack_element_from_nia(row).ok().map(Place),
+            Dup(_) => Self::op_stack_element_from_nia(row).ok().map(Dup),
+            Swap(_) => Self::op_stack_element_from_nia(row).ok().map(Swap),
+            _ => instruction.change_arg(nia).ok(),
+        };
+
+        match maybe_instruction {
+            Some(instruction) => instruction.to_string(),
+            None => format!("{} <invalid: {nia}>", instruction.name()),
         }
     }
+
+    fn op_stack_column_by_index(index: usize) -> ProcessorBaseTableColumn {
+        let Some(&column) = ProcessorBaseTableColumn::stack_registers().get(index) else {
+            panic!("Op Stack column index must be in [0, 15], not {index}.");
+        };
+        column
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -1139,9 +1366,7 @@ impl ExtProcessorTable {
             circuit_builder.input(NextBaseRow(col.master_base_table_index()))
         };
 
-        let stack = (0..OpStackElement::COUNT)
-            .map(ProcessorTable::op_stack_column_by_index)
-            .collect_vec();
+        let stack = ProcessorBaseTableColumn::stack_registers();
         let next_stack = stack.iter().map(|&st| next_row(st)).collect_vec();
         let curr_stack = stack.iter().map(|&st| curr_row(st)).collect_vec();
 
@@ -1402,7 +1627,7 @@ impl ExtProcessorTable {
 
     /// A polynomial that has no solutions when `ci` is `instruction`.
     /// The number of variables in the polynomial corresponds to two rows.
-    fn instruction_deselector_current_row(
+    pub fn instruction_deselector_current_row(
         circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
         instruction: Instruction,
     ) -> ConstraintCircuitMonad<DualRowIndicator> {
@@ -1556,9 +1781,93 @@ impl ExtProcessorTable {
         .concat()
     }
 
+    fn instruction_pick(
+        circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
+    ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
+        let stack_with_picked_i = |mut stack: Vec<_>, i| {
+            let picked_element = stack.remove(i);
+            stack.insert(0, picked_element);
+            stack
+        };
+        let next_stack_is_current_stack_with_correct_element_picked =
+            Self::next_stack_is_current_stack_rearranged_by_arg(
+                circuit_builder,
+                stack_with_picked_i,
+            );
+
+        [
+            vec![next_stack_is_current_stack_with_correct_element_picked],
+            Self::instruction_group_decompose_arg(circuit_builder),
+            Self::instruction_group_step_2(circuit_builder),
+            Self::instruction_group_no_ram(circuit_builder),
+            Self::instruction_group_no_io(circuit_builder),
+            Self::instruction_group_keep_op_stack_height(circuit_builder),
+        ]
+        .concat()
+    }
+
+    fn instruction_place(
+        circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
+    ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
+        let stack_with_placed_i = |mut stack: Vec<_>, i| {
+            let top_element = stack.remove(0);
+            stack.insert(i, top_element);
+            stack
+        };
+        let next_stack_is_current_stack_with_top_element_placed_correctly =
+            Self::next_stack_is_current_stack_rearranged_by_arg(
+                circuit_builder,
+                stack_with_placed_i,
+            );
+
+        [
+            vec![next_stack_is_current_stack_with_top_element_placed_correctly],
+            Self::instruction_group_decompose_arg(circuit_builder),
+            Self::instruction_group_step_2(circuit_builder),
+            Self::instruction_group_no_ram(circuit_builder),
+            Self::instruction_group_no_io(circuit_builder),
+            Self::instruction_group_keep_op_stack_height(circuit_builder),
+        ]
+        .concat()
+    }
+
     fn instruction_swap(
         circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
     ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
+        let stack_with_swapped_i = |mut stack: Vec<_>, i| {
+            stack.swap(0, i);
+            stack
+        };
+        let next_stack_is_current_stack_with_correct_element_swapped =
+            Self::next_stack_is_current_stack_rearranged_by_arg(
+                circuit_builder,
+                stack_with_swapped_i,
+            );
+
+        [
+            vec![next_stack_is_current_stack_with_correct_element_swapped],
+            Self::instruction_group_decompose_arg(circuit_builder),
+            Self::instruction_group_step_2(circuit_builder),
+            Self::instruction_group_no_ram(circuit_builder),
+            Self::instruction_group_no_io(circuit_builder),
+            Self::instruction_group_keep_op_stack_height(circuit_builder),
+        ]
+        .concat()
+    }
+
+    /// The next row's op stack equals the current row's op stack, rearranged according to the
+    /// instruction's argument. For each possible argument `i`, the `rearrangement` maps the
+    /// current row's op stack columns to the corresponding columns of the next row.
+    ///
+    /// Both stacks are compressed using challenges, combined with [indicator polynomials] for the
+    /// instruction's argument. The resulting constraint has the same degree for any
+    /// rearrangement.
+    ///
+    /// [indicator polynomials]: Self::indicator_polynomial
+    fn next_stack_is_current_stack_rearranged_by_arg(
+        circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
+        rearrangement: impl Fn(Vec<ProcessorBaseTableColumn>, usize) -> Vec<ProcessorBaseTableColumn>,
+    ) -> ConstraintCircuitMonad<DualRowIndicator> {
         let curr_row = |col: ProcessorBaseTableColumn| {
             circuit_builder.input(CurrentBaseRow(col.master_base_table_index()))
         };
@@ -1566,17 +1875,12 @@ impl ExtProcessorTable {
             circuit_builder.input(NextBaseRow(col.master_base_table_index()))
         };
 
-        let stack = (0..OpStackElement::COUNT)
-            .map(ProcessorTable::op_stack_column_by_index)
-            .collect_vec();
-        let stack_with_swapped_i = |i| {
-            let mut stack = stack.clone();
-            stack.swap(0, i);
-            stack.into_iter()
-        };
-
+        let stack = ProcessorBaseTableColumn::stack_registers();
         let next_stack = stack.iter().map(|&st| next_row(st)).collect_vec();
-        let curr_stack_with_swapped_i = |i| stack_with_swapped_i(i).map(curr_row).collect_vec();
+        let curr_stack_rearranged_by_i = |i| {
+            let rearranged_stack = rearrangement(stack.to_vec(), i);
+            rearranged_stack.into_iter().map(curr_row).collect_vec()
+        };
         let compress = |stack: Vec<_>| -> ConstraintCircuitMonad<_> {
             assert_eq!(OpStackElement::COUNT, stack.len());
             let weight = |i| circuit_builder.challenge(Self::stack_weight_by_index(i));
@@ -1584,23 +1888,13 @@ impl ExtProcessorTable {
             enumerated_stack.map(|(i, st)| weight(i) * st).sum()
         };
 
-        let next_stack_is_current_stack_with_swapped_i = |i| {
+        let next_stack_is_current_stack_rearranged_by_i = |i| {
             Self::indicator_polynomial(circuit_builder, i)
-                * (compress(next_stack.clone()) - compress(curr_stack_with_swapped_i(i)))
+                * (compress(next_stack.clone()) - compress(curr_stack_rearranged_by_i(i)))
         };
-        let next_stack_is_current_stack_with_correct_element_swapped = (0..OpStackElement::COUNT)
-            .map(next_stack_is_current_stack_with_swapped_i)
-            .sum();
-
-        [
-            vec![next_stack_is_current_stack_with_correct_element_swapped],
-            Self::instruction_group_decompose_arg(circuit_builder),
-            Self::instruction_group_step_2(circuit_builder),
-            Self::instruction_group_no_ram(circuit_builder),
-            Self::instruction_group_no_io(circuit_builder),
-            Self::instruction_group_keep_op_stack_height(circuit_builder),
-        ]
-        .concat()
+        (0..OpStackElement::COUNT)
+            .map(next_stack_is_current_stack_rearranged_by_i)
+            .sum()
     }
 
     fn instruction_nop(
@@ -1836,17 +2130,8 @@ impl ExtProcessorTable {
     fn instruction_assert(
         circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
     ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
-        let constant = |c: u32| circuit_builder.b_constant(c);
-        let curr_base_row = |col: ProcessorBaseTableColumn| {
-            circuit_builder.input(CurrentBaseRow(col.master_base_table_index()))
-        };
-
-        // The current top of the stack st0 is 1.
-        let st_0_is_1 = curr_base_row(ST0) - constant(1);
-
-        let specific_constraints = vec![st_0_is_1];
         [
-            specific_constraints,
+            Self::top_of_stack_is_1(circuit_builder),
             Self::instruction_group_step_1(circuit_builder),
             Self::instruction_group_shrink_op_stack(circuit_builder),
             Self::instruction_group_no_ram(circuit_builder),
@@ -1855,6 +2140,33 @@ impl ExtProcessorTable {
         .concat()
     }
 
+    /// The error code in `nia` is range-checked through the Lookup Argument with the U32 Table.
+    fn instruction_assert_with_code(
+        circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
+    ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
+        [
+            Self::top_of_stack_is_1(circuit_builder),
+            Self::instruction_group_step_2(circuit_builder),
+            Self::instruction_group_shrink_op_stack(circuit_builder),
+            Self::instruction_group_no_ram(circuit_builder),
+            Self::instruction_group_no_io(circuit_builder),
+        ]
+        .concat()
+    }
+
+    fn top_of_stack_is_1(
+        circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
+    ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
+        let constant = |c: u32| circuit_builder.b_constant(c);
+        let curr_base_row = |col: ProcessorBaseTableColumn| {
+            circuit_builder.input(CurrentBaseRow(col.master_base_table_index()))
+        };
+
+        // The current top of the stack st0 is 1.
+        let st_0_is_1 = curr_base_row(ST0) - constant(1);
+        vec![st_0_is_1]
+    }
+
     fn instruction_halt(
         circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
     ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
@@ -1973,26 +2285,45 @@ impl ExtProcessorTable {
 
     fn instruction_assert_vector(
         circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
+    ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
+        [
+            Self::top_two_digests_are_equal(circuit_builder),
+            Self::instruction_group_step_1(circuit_builder),
+            Self::constraints_for_shrinking_stack_by(circuit_builder, 5),
+            Self::instruction_group_no_ram(circuit_builder),
+            Self::instruction_group_no_io(circuit_builder),
+        ]
+        .concat()
+    }
+
+    /// The error code in `nia` is range-checked through the Lookup Argument with the U32 Table.
+    fn instruction_assert_vector_with_code(
+        circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
+    ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
+        [
+            Self::top_two_digests_are_equal(circuit_builder),
+            Self::instruction_group_step_2(circuit_builder),
+            Self::constraints_for_shrinking_stack_by(circuit_builder, 5),
+            Self::instruction_group_no_ram(circuit_builder),
+            Self::instruction_group_no_io(circuit_builder),
+        ]
+        .concat()
+    }
+
+    fn top_two_digests_are_equal(
+        circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
     ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
         let curr_base_row = |col: ProcessorBaseTableColumn| {
             circuit_builder.input(CurrentBaseRow(col.master_base_table_index()))
         };
 
-        let specific_constraints = vec![
+        vec![
             curr_base_row(ST5) - curr_base_row(ST0),
             curr_base_row(ST6) - curr_base_row(ST1),
             curr_base_row(ST7) - curr_base_row(ST2),
             curr_base_row(ST8) - curr_base_row(ST3),
             curr_base_row(ST9) - curr_base_row(ST4),
-        ];
-        [
-            specific_constraints,
-            Self::instruction_group_step_1(circuit_builder),
-            Self::constraints_for_shrinking_stack_by(circuit_builder, 5),
-            Self::instruction_group_no_ram(circuit_builder),
-            Self::instruction_group_no_io(circuit_builder),
         ]
-        .concat()
     }
 
     fn instruction_sponge_init(
@@ -2675,7 +3006,33 @@ impl ExtProcessorTable {
         .concat()
     }
 
-    fn transition_constraints_for_instruction(
+    /// The transition constraints specific to the given instruction. They only hold if the
+    /// instruction is the current instruction, _i.e._, they still need to be combined with the
+    /// [instruction's deselector](Self::instruction_deselector_current_row).
+    ///
+    /// Uses a fresh [`ConstraintCircuitBuilder`]. To combine the constraints with other
+    /// circuits, use [`Self::transition_constraints_for_instruction_with_builder`].
+    pub fn transition_constraints_for_instruction(
+        instruction: Instruction,
+    ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        Self::transition_constraints_for_instruction_with_builder(&circuit_builder, instruction)
+    }
+
+    /// Write the [transition constraints specific to the given
+    /// instruction](Self::transition_constraints_for_instruction) as a directed graph in
+    /// Graphviz DOT format. See [`ConstraintCircuitMonad::write_multicircuit_as_dot`].
+    pub fn write_transition_constraints_for_instruction_as_dot<W: Write>(
+        instruction: Instruction,
+        out: &mut W,
+    ) -> io::Result<()> {
+        let constraints = Self::transition_constraints_for_instruction(instruction);
+        ConstraintCircuitMonad::write_multicircuit_as_dot(&constraints, out)
+    }
+
+    /// Like [`Self::transition_constraints_for_instruction`], but uses the given
+    /// [`ConstraintCircuitBuilder`].
+    pub fn transition_constraints_for_instruction_with_builder(
         circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
         instruction: Instruction,
     ) -> Vec<ConstraintCircuitMonad<DualRowIndicator>> {
@@ -2683,6 +3040,8 @@ impl ExtProcessorTable {
             Pop(_) => ExtProcessorTable::instruction_pop(circuit_builder),
             Push(_) => ExtProcessorTable::instruction_push(circuit_builder),
             Divine(_) => ExtProcessorTable::instruction_divine(circuit_builder),
+            Pick(_) => ExtProcessorTable::instruction_pick(circuit_builder),
+            Place(_) => ExtProcessorTable::instruction_place(circuit_builder),
             Dup(_) => ExtProcessorTable::instruction_dup(circuit_builder),
             Swap(_) => ExtProcessorTable::instruction_swap(circuit_builder),
             Halt => ExtProcessorTable::instruction_halt(circuit_builder),
@@ -2693,10 +3052,14 @@ impl ExtProcessorTable {
             Recurse => ExtProcessorTable::instruction_recurse(circuit_builder),
             RecurseOrReturn => ExtProcessorTable::instruction_recurse_or_return(circuit_builder),
             Assert => ExtProcessorTable::instruction_assert(circuit_builder),
+            AssertWithCode(_) => ExtProcessorTable::instruction_assert_with_code(circuit_builder),
             ReadMem(_) => ExtProcessorTable::instruction_read_mem(circuit_builder),
             WriteMem(_) => ExtProcessorTable::instruction_write_mem(circuit_builder),
             Hash => ExtProcessorTable::instruction_hash(circuit_builder),
             AssertVector => ExtProcessorTable::instruction_assert_vector(circuit_builder),
+            AssertVectorWithCode(_) => {
+                ExtProcessorTable::instruction_assert_vector_with_code(circuit_builder)
+            }
             SpongeInit => ExtProcessorTable::instruction_sponge_init(circuit_builder),
             SpongeAbsorb => ExtProcessorTable::instruction_sponge_absorb(circuit_builder),
             SpongeAbsorbMem => ExtProcessorTable::instruction_sponge_absorb_mem(circuit_builder),
@@ -2726,6 +3089,99 @@ impl ExtProcessorTable {
         }
     }
 
+    /// The degrees of the [transition constraints](Self::transition_constraints_for_instruction)
+    /// of every instruction. The degrees do not account for the
+    /// [instruction's deselector](Self::instruction_deselector_current_row).
+    pub fn transition_constraint_degrees() -> Vec<(Instruction, Vec<usize>)> {
+        ALL_INSTRUCTIONS
+            .into_iter()
+            .map(|instruction| {
+                let constraints = Self::transition_constraints_for_instruction(instruction);
+                let degrees = constraints
+                    .into_iter()
+                    .map(|circuit| circuit.consume().degree())
+                    .map(|degree| {
+                        assert!(
+                            degree >= 0,
+                            "constraint of `{instruction}` has negative degree"
+                        );
+                        degree.unsigned_abs()
+                    })
+                    .collect();
+                (instruction, degrees)
+            })
+            .collect()
+    }
+
+    /// Cross-check the [op-stack related metadata](Instruction::verify_op_stack_metadata) of every
+    /// instruction, for every legal argument that influences the op stack, against the
+    /// instruction's [transition constraints](Self::transition_constraints_for_instruction). On top
+    /// of the metadata being consistent, the transition constraints must change the op stack
+    /// pointer by exactly the [op stack size influence](Instruction::op_stack_size_influence).
+    ///
+    /// Guards against drift between the instructions' metadata and their arithmetization. Returns
+    /// all instructions for which any of the checks fails.
+    pub fn verify_op_stack_invariants() -> Result<(), Vec<Instruction>> {
+        let inconsistent_instructions = Instruction::all_with_op_stack_influencing_args()
+            .into_iter()
+            .filter(|instruction| {
+                let influence = instruction.op_stack_size_influence();
+                !instruction.op_stack_metadata_is_consistent()
+                    || !Self::op_stack_pointer_changes_by(*instruction, influence)
+            })
+            .collect_vec();
+
+        match inconsistent_instructions.is_empty() {
+            true => Ok(()),
+            false => Err(inconsistent_instructions),
+        }
+    }
+
+    /// Whether the instruction's transition constraints determine the op stack pointer of the
+    /// next row to be the op stack pointer of the current row plus `delta`.
+    ///
+    /// Evaluates the constraints on a pseudo-random but fixed pair of rows, once with the next
+    /// row's op stack pointer set according to `delta`, and once each with that pointer being off
+    /// by one. Some constraint must vanish in the first case but not in the other two.
+    fn op_stack_pointer_changes_by(instruction: Instruction, delta: i32) -> bool {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut base_rows = Array2::from_shape_simple_fn([2, NUM_BASE_COLUMNS], || rng.gen());
+        let ext_rows = Array2::from_shape_simple_fn([2, NUM_EXT_COLUMNS], || rng.gen());
+        let challenges = rng.gen::<[XFieldElement; Challenges::COUNT]>();
+
+        // Some constraints depend on the argument, which is decomposed into helper variables.
+        let base_column = |column: ProcessorBaseTableColumn| column.master_base_table_index();
+        let arg = instruction.arg().unwrap_or_else(|| rng.gen());
+        base_rows[[0, base_column(NIA)]] = arg;
+        for (bit_index, helper_variable) in [HV0, HV1, HV2, HV3].into_iter().enumerate() {
+            let bit = (arg.value() >> bit_index) & 1;
+            base_rows[[0, base_column(helper_variable)]] = bfe!(bit);
+        }
+
+        let op_stack_pointer = base_column(OpStackPointer);
+        let expected_op_stack_pointer = base_rows[[0, op_stack_pointer]] + bfe!(delta);
+        let mut evaluate_constraints_with_next_op_stack_pointer = |next_op_stack_pointer| {
+            base_rows[[1, op_stack_pointer]] = next_op_stack_pointer;
+            Self::transition_constraints_for_instruction(instruction)
+                .into_iter()
+                .map(|constraint| {
+                    let constraint = constraint.consume();
+                    constraint.evaluate(base_rows.view(), ext_rows.view(), &challenges)
+                })
+                .collect_vec()
+        };
+
+        let honest = evaluate_constraints_with_next_op_stack_pointer(expected_op_stack_pointer);
+        let too_small =
+            evaluate_constraints_with_next_op_stack_pointer(expected_op_stack_pointer - bfe!(1));
+        let too_large =
+            evaluate_constraints_with_next_op_stack_pointer(expected_op_stack_pointer + bfe!(1));
+
+        izip!(honest, too_small, too_large).any(|(honest, too_small, too_large)| {
+            honest.is_zero() && !too_small.is_zero() && !too_large.is_zero()
+        })
+    }
+
     /// Constrains instruction argument `nia` such that 0 < nia <= 5.
     fn prohibit_any_illegal_number_of_words(
         circuit_builder: &ConstraintCircuitBuilder<DualRowIndicator>,
@@ -3000,7 +3456,7 @@ impl ExtProcessorTable {
             circuit_builder.input(NextBaseRow(col.master_base_table_index()))
         };
 
-        let stack = || (0..OpStackElement::COUNT).map(ProcessorTable::op_stack_column_by_index);
+        let stack = || ProcessorBaseTableColumn::stack_registers().into_iter();
         let new_stack = stack().dropping_back(n).map(next_row).collect_vec();
         let old_stack_with_top_n_removed = stack().skip(n).map(curr_row).collect_vec();
 
@@ -3036,7 +3492,7 @@ impl ExtProcessorTable {
             circuit_builder.input(NextBaseRow(col.master_base_table_index()))
         };
 
-        let stack = || (0..OpStackElement::COUNT).map(ProcessorTable::op_stack_column_by_index);
+        let stack = || ProcessorBaseTableColumn::stack_registers().into_iter();
         let new_stack = stack().skip(n).map(next_row).collect_vec();
         let old_stack_with_top_n_added = stack().map(curr_row).dropping_back(n).collect_vec();
 
@@ -3673,6 +4129,14 @@ impl ExtProcessorTable {
             Self::instruction_deselector_current_row(circuit_builder, Instruction::PopCount);
         let merkle_step_deselector =
             Self::instruction_deselector_current_row(circuit_builder, Instruction::MerkleStep);
+        let assert_with_code_deselector = Self::instruction_deselector_current_row(
+            circuit_builder,
+            Instruction::AssertWithCode(0),
+        );
+        let assert_vector_with_code_deselector = Self::instruction_deselector_current_row(
+            circuit_builder,
+            Instruction::AssertVectorWithCode(0),
+        );
 
         let running_sum = curr_ext_row(U32LookupClientLogDerivative);
         let running_sum_next = next_ext_row(U32LookupClientLogDerivative);
@@ -3710,6 +4174,9 @@ impl ExtProcessorTable {
             - challenge(U32LhsWeight) * curr_base_row(ST5)
             - challenge(U32RhsWeight) * next_base_row(ST5)
             - challenge(U32CiWeight) * constant(Instruction::Split.opcode());
+        let error_code_range_check_factor = challenge(U32Indeterminate)
+            - challenge(U32LhsWeight) * curr_base_row(NIA)
+            - challenge(U32CiWeight) * constant(Instruction::Split.opcode());
 
         let running_sum_absorbs_split_factor =
             (running_sum_next.clone() - running_sum.clone()) * split_factor - one();
@@ -3722,6 +4189,9 @@ impl ExtProcessorTable {
         let running_sum_absorbs_merkle_step_factor =
             (running_sum_next.clone() - running_sum.clone()) * merkle_step_range_check_factor
                 - one();
+        let running_sum_absorbs_error_code_factor =
+            (running_sum_next.clone() - running_sum.clone()) * error_code_range_check_factor
+                - one();
 
         let split_summand = split_deselector * running_sum_absorbs_split_factor;
         let lt_summand = lt_deselector * running_sum_absorbs_binop_factor.clone();
@@ -3737,6 +4207,8 @@ impl ExtProcessorTable {
                 - div_mod_factor_for_range_check);
         let pop_count_summand = pop_count_deselector * running_sum_absorbs_unop_factor;
         let merkle_walk_summand = merkle_step_deselector * running_sum_absorbs_merkle_step_factor;
+        let error_code_summand = (assert_with_code_deselector + assert_vector_with_code_deselector)
+            * running_sum_absorbs_error_code_factor;
         let no_update_summand = (one() - curr_base_row(IB2)) * (running_sum_next - running_sum);
 
         split_summand
@@ -3748,6 +4220,7 @@ impl ExtProcessorTable {
             + div_mod_summand
             + pop_count_summand
             + merkle_walk_summand
+            + error_code_summand
             + no_update_summand
     }
 
@@ -3793,8 +4266,9 @@ impl ExtProcessorTable {
             vec![clk_increases_by_1, is_padding_is_0_or_does_not_change];
 
         // instruction-specific constraints
-        let transition_constraints_for_instruction =
-            |instr| Self::transition_constraints_for_instruction(circuit_builder, instr);
+        let transition_constraints_for_instruction = |instr| {
+            Self::transition_constraints_for_instruction_with_builder(circuit_builder, instr)
+        };
         let all_instructions_and_their_transition_constraints =
             ALL_INSTRUCTIONS.map(|instr| (instr, transition_constraints_for_instruction(instr)));
         let deselected_transition_constraints =
@@ -3828,6 +4302,31 @@ impl ExtProcessorTable {
         .concat()
     }
 
+    /// Evaluate all [transition constraints](Self::transition_constraints) on the given pair of
+    /// consecutive rows of the master tables. Returns the index and the non-zero residual of
+    /// every violated constraint.
+    pub fn find_violated_transition_constraints(
+        current_base_row: ArrayView1<BFieldElement>,
+        current_ext_row: ArrayView1<XFieldElement>,
+        next_base_row: ArrayView1<BFieldElement>,
+        next_ext_row: ArrayView1<XFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<(usize, XFieldElement)> {
+        let base_rows = stack![Axis(0), current_base_row, next_base_row];
+        let ext_rows = stack![Axis(0), current_ext_row, next_ext_row];
+
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        Self::transition_constraints(&circuit_builder)
+            .into_iter()
+            .map(|constraint| {
+                let constraint = constraint.consume();
+                constraint.evaluate(base_rows.view(), ext_rows.view(), &challenges.challenges)
+            })
+            .enumerate()
+            .filter(|(_, residual)| !residual.is_zero())
+            .collect()
+    }
+
     pub fn terminal_constraints(
         circuit_builder: &ConstraintCircuitBuilder<SingleRowIndicator>,
     ) -> Vec<ConstraintCircuitMonad<SingleRowIndicator>> {
@@ -3841,6 +4340,18 @@ impl ExtProcessorTable {
 
         vec![last_ci_is_halt]
     }
+
+    /// The number of constraints of the Processor Table, by constraint type. Any change to
+    /// these numbers changes the AIR, rendering proofs and verifiers of different versions
+    /// incompatible.
+    pub fn constraint_counts() -> ConstraintCounts {
+        ConstraintCounts {
+            initial: Self::initial_constraints(&ConstraintCircuitBuilder::new()).len(),
+            consistency: Self::consistency_constraints(&ConstraintCircuitBuilder::new()).len(),
+            transition: Self::transition_constraints(&ConstraintCircuitBuilder::new()).len(),
+            terminal: Self::terminal_constraints(&ConstraintCircuitBuilder::new()).len(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -3848,12 +4359,17 @@ pub(crate) mod tests {
     use std::collections::HashMap;
 
     use assert2::assert;
+    use assert2::let_assert;
+    use colored::Colorize;
     use ndarray::Array2;
     use proptest::collection::vec;
     use proptest::prop_assert_eq;
     use proptest_arbitrary_interop::arb;
+    use rand::random;
+    use rand::rngs::StdRng;
     use rand::thread_rng;
     use rand::Rng;
+    use rand_core::SeedableRng;
     use strum::IntoEnumIterator;
     use test_strategy::proptest;
 
@@ -3861,13 +4377,23 @@ pub(crate) mod tests {
     use crate::instruction::Instruction;
     use crate::op_stack::NumberOfWords::*;
     use crate::op_stack::OpStackElement;
+    use crate::prelude::Claim;
     use crate::prelude::PublicInput;
     use crate::program::Program;
-    use crate::shared_tests::ProgramAndInput;
+    use crate::program::ProgramAndInput;
+    use crate::stark::tests::master_base_table_for_low_security_level;
     use crate::stark::tests::master_tables_for_low_security_level;
+    use crate::table::jump_stack_table;
+    use crate::table::jump_stack_table::JumpStackTable;
     use crate::table::master_table::*;
+    use crate::table::op_stack_table;
+    use crate::table::op_stack_table::OpStackTable;
+    use crate::table::ram_table::RamTable;
     use crate::triton_asm;
+    use crate::triton_instr;
     use crate::triton_program;
+    use crate::vm::tests::test_program_for_call_recurse_return;
+    use crate::vm::tests::test_program_for_recurse_or_return;
     use crate::vm::VMState;
     use crate::vm::NUM_HELPER_VARIABLE_REGISTERS;
     use crate::NonDeterminism;
@@ -3882,6 +4408,328 @@ pub(crate) mod tests {
         println!("\n{}", err.vm_state);
     }
 
+    #[test]
+    fn program_hash_is_the_digest_expected_by_the_initial_constraints() {
+        let program = triton_program!(push 1 push 2 add pop 1 halt);
+        let (_, claim, master_base_table, master_ext_table, challenges) =
+            master_tables_for_low_security_level(ProgramAndInput::new(program.clone()));
+        assert!(program.hash() == claim.program_digest);
+
+        let base_rows = master_base_table.trace_table();
+        let ext_rows = master_ext_table.trace_table();
+        let first_row = base_rows.row(0);
+        let stack_digest = [ST11, ST12, ST13, ST14, ST15]
+            .map(|register| first_row[register.master_base_table_index()]);
+        assert!(program.hash() == Digest::new(stack_digest));
+
+        let num_failing_initial_constraints = |challenges: &Challenges| {
+            let builder = ConstraintCircuitBuilder::new();
+            ExtProcessorTable::initial_constraints(&builder)
+                .into_iter()
+                .map(|constraint| constraint.consume())
+                .filter(|constraint| {
+                    let base_row = base_rows.slice(s![..1, ..]);
+                    let ext_row = ext_rows.slice(s![..1, ..]);
+                    !constraint
+                        .evaluate(base_row, ext_row, &challenges.challenges)
+                        .is_zero()
+                })
+                .count()
+        };
+        assert!(0 == num_failing_initial_constraints(&challenges));
+
+        let other_program = triton_program!(push 1 push 2 add pop 1 push 0 halt);
+        let other_claim = Claim {
+            program_digest: other_program.hash(),
+            ..claim
+        };
+        let other_challenges = Challenges::placeholder(&other_claim);
+        assert!(1 == num_failing_initial_constraints(&other_challenges));
+    }
+
+    /// Fails if the number of constraints changes. If the change is deliberate, update the
+    /// expected numbers.
+    #[test]
+    fn constraint_counts_are_as_expected() {
+        let expected = ConstraintCounts {
+            initial: 29,
+            consistency: 10,
+            transition: 41,
+            terminal: 1,
+        };
+        assert!(expected == ExtProcessorTable::constraint_counts());
+    }
+
+    #[test]
+    fn no_instruction_has_duplicate_transition_constraints() {
+        for instruction in ALL_INSTRUCTIONS {
+            let constraints =
+                ExtProcessorTable::transition_constraints_for_instruction(instruction);
+            let duplicates = ConstraintCircuitBuilder::duplicate_constraints(&constraints);
+            assert!(duplicates.is_empty(), "{instruction}: {duplicates:?}");
+        }
+    }
+
+    #[test]
+    fn exactly_the_u32_instructions_send_rows_to_the_u32_table() {
+        let challenges = Challenges::placeholder(&Claim::default());
+        for instruction in ALL_INSTRUCTIONS {
+            let mut previous_row = Array1::from_iter(random::<[BFieldElement; BASE_WIDTH]>());
+            let current_row = Array1::from_iter(random::<[BFieldElement; BASE_WIDTH]>());
+            previous_row[CI.base_table_index()] = instruction.opcode_b();
+            previous_row[NIA.base_table_index()] = instruction.arg().unwrap_or_default();
+
+            let compressed_rows = ProcessorTable::u32_lookup_rows(
+                previous_row.view(),
+                current_row.view(),
+                &challenges,
+            );
+            let expected_num_rows = match instruction {
+                Instruction::DivMod => 2,
+                _ if instruction.is_u32_instruction() => 1,
+                _ => 0,
+            };
+            assert!(expected_num_rows == compressed_rows.len(), "{instruction}");
+        }
+    }
+
+    #[test]
+    fn instruction_bits_of_honest_trace_are_consistent() {
+        let program = triton_program!(push 3 push 4 add pick 0 place 0 pop 1 halt);
+        let (aet, _) = program
+            .trace_execution(PublicInput::default(), NonDeterminism::default())
+            .unwrap();
+        let verdict = ProcessorTable::verify_instruction_bits(aet.processor_trace.view());
+        assert!(let Ok(()) = verdict);
+    }
+
+    #[test]
+    fn tampered_instruction_bit_is_detected() {
+        let program = triton_program!(push 3 push 4 add pop 1 halt);
+        let (aet, _) = program
+            .trace_execution(PublicInput::default(), NonDeterminism::default())
+            .unwrap();
+
+        let tampered_row = 2;
+        let mut processor_trace = aet.processor_trace;
+        let ib1 = &mut processor_trace[[tampered_row, IB1.base_table_index()]];
+        *ib1 = bfe!(1) - *ib1;
+
+        let verdict = ProcessorTable::verify_instruction_bits(processor_trace.view());
+        let_assert!(Err(TraceError::InconsistentInstructionBits { row }) = verdict);
+        assert!(tampered_row == row);
+    }
+
+    #[test]
+    fn clk_jump_diff_multiplicities_of_padded_honest_trace_are_consistent() {
+        let program = triton_program! {
+            push 5 push 42 write_mem 1 pop 1
+            call foo call foo
+            push 42 read_mem 1 pop 2 halt
+            foo: push 1 push 2 swap 1 pop 2 return
+        };
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        let padded_height = aet.padded_height();
+        let num_rows = aet.processor_trace.nrows();
+
+        let mut op_stack_table = Array2::zeros([padded_height, op_stack_table::BASE_WIDTH]);
+        let mut ram_table = Array2::zeros([padded_height, ram_table::BASE_WIDTH]);
+        let mut jump_stack_table = Array2::zeros([padded_height, jump_stack_table::BASE_WIDTH]);
+        let clk_jump_diffs_op_stack =
+            OpStackTable::fill_trace(&mut op_stack_table.view_mut(), &aet);
+        let clk_jump_diffs_ram = RamTable::fill_trace(&mut ram_table.view_mut(), &aet);
+        let clk_jump_diffs_jump_stack =
+            JumpStackTable::fill_trace(&mut jump_stack_table.view_mut(), &aet);
+
+        let mut processor_table = Array2::zeros([padded_height, BASE_WIDTH]);
+        ProcessorTable::fill_trace(
+            &mut processor_table.view_mut(),
+            &aet,
+            &clk_jump_diffs_op_stack,
+            &clk_jump_diffs_ram,
+            &clk_jump_diffs_jump_stack,
+        );
+        ProcessorTable::pad_trace(processor_table.view_mut(), num_rows);
+
+        let verify = |processor_table: &Array2<_>| {
+            ProcessorTable::verify_clk_jump_diff_consistency(
+                processor_table.view(),
+                num_rows,
+                &clk_jump_diffs_op_stack,
+                &clk_jump_diffs_ram,
+                &clk_jump_diffs_jump_stack,
+            )
+        };
+        assert!(let Ok(()) = verify(&processor_table));
+
+        let multiplicity_column = ClockJumpDifferenceLookupMultiplicity.base_table_index();
+        processor_table[[1, multiplicity_column]] += bfe!(1);
+        let_assert!(
+            Err(TraceError::InconsistentClockJumpDifferenceMultiplicity { row }) =
+                verify(&processor_table)
+        );
+        assert!(1 == row);
+    }
+
+    #[test]
+    fn out_of_range_clk_jump_diff_is_reported_instead_of_panicking() {
+        let num_rows = 4;
+        let processor_table = Array2::zeros([num_rows, BASE_WIDTH]);
+        for clock_jump_difference in [num_rows as u64, BFieldElement::MAX] {
+            let verdict = ProcessorTable::verify_clk_jump_diff_consistency(
+                processor_table.view(),
+                num_rows,
+                &[],
+                &[bfe!(clock_jump_difference)],
+                &[],
+            );
+            let_assert!(Err(err) = verdict);
+            let_assert!(
+                TraceError::ClockJumpDifferenceOutOfRange {
+                    clock_jump_difference: reported_difference,
+                    num_rows: reported_num_rows,
+                } = err
+            );
+            assert!(clock_jump_difference == reported_difference);
+            assert!(num_rows == reported_num_rows);
+        }
+    }
+
+    #[test]
+    fn filling_trace_in_chunks_gives_same_table_as_filling_trace_in_one_go() {
+        let program = triton_program! {
+            push 5 push 42 write_mem 1 pop 1
+            call foo call foo
+            push 42 read_mem 1 pop 2 halt
+            foo: push 1 push 2 swap 1 pop 2 return
+        };
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        let padded_height = aet.padded_height();
+        let num_rows = aet.processor_trace.nrows();
+
+        let mut op_stack_table = Array2::zeros([padded_height, op_stack_table::BASE_WIDTH]);
+        let mut ram_table = Array2::zeros([padded_height, ram_table::BASE_WIDTH]);
+        let mut jump_stack_table = Array2::zeros([padded_height, jump_stack_table::BASE_WIDTH]);
+        let clk_jump_diffs_op_stack =
+            OpStackTable::fill_trace(&mut op_stack_table.view_mut(), &aet);
+        let clk_jump_diffs_ram = RamTable::fill_trace(&mut ram_table.view_mut(), &aet);
+        let clk_jump_diffs_jump_stack =
+            JumpStackTable::fill_trace(&mut jump_stack_table.view_mut(), &aet);
+
+        let mut one_go_table = Array2::zeros([padded_height, BASE_WIDTH]);
+        ProcessorTable::fill_trace(
+            &mut one_go_table.view_mut(),
+            &aet,
+            &clk_jump_diffs_op_stack,
+            &clk_jump_diffs_ram,
+            &clk_jump_diffs_jump_stack,
+        );
+        ProcessorTable::pad_trace(one_go_table.view_mut(), num_rows);
+
+        let clk_jump_diff_multiplicities = ProcessorTable::clock_jump_difference_multiplicities(
+            num_rows,
+            &clk_jump_diffs_op_stack,
+            &clk_jump_diffs_ram,
+            &clk_jump_diffs_jump_stack,
+        );
+        let row_batches = aet
+            .processor_trace
+            .axis_chunks_iter(Axis(0), 3)
+            .map(|batch| batch.to_owned());
+        let mut chunked_table = Array2::zeros([padded_height, BASE_WIDTH]);
+        ProcessorTable::fill_trace_chunked(
+            &mut chunked_table.view_mut(),
+            row_batches,
+            &clk_jump_diff_multiplicities,
+        );
+        ProcessorTable::pad_trace(chunked_table.view_mut(), num_rows);
+
+        assert!(one_go_table == chunked_table);
+    }
+
+    #[test]
+    fn padding_trace_to_target_length_leaves_remaining_rows_untouched() {
+        let program = triton_program!(push 3 push 4 add pop 1 halt);
+        let (aet, _) = program.trace_execution([].into(), [].into()).unwrap();
+        let num_rows = aet.processor_trace.nrows();
+        let num_padding_rows = 5;
+        let target_len = num_rows + num_padding_rows;
+
+        let untouched_value = bfe!(42);
+        let mut processor_table = Array2::from_elem([target_len + 3, BASE_WIDTH], untouched_value);
+        processor_table
+            .slice_mut(s![..num_rows, ..])
+            .assign(&aet.processor_trace);
+        let multiplicity_index = ClockJumpDifferenceLookupMultiplicity.base_table_index();
+        let row_1_multiplicity = processor_table[[1, multiplicity_index]];
+
+        ProcessorTable::pad_trace_to(processor_table.view_mut(), num_rows, target_len);
+
+        let num_padding_rows = bfe!(num_padding_rows as u64);
+        assert!(row_1_multiplicity + num_padding_rows == processor_table[[1, multiplicity_index]]);
+        for (clk, row) in processor_table
+            .rows()
+            .into_iter()
+            .enumerate()
+            .skip(num_rows)
+        {
+            if clk < target_len {
+                assert!(bfe!(clk as u64) == row[CLK.base_table_index()]);
+                assert!(bfe!(1) == row[IsPadding.base_table_index()]);
+            } else {
+                assert!(row.iter().all(|&element| element == untouched_value));
+            }
+        }
+    }
+
+    #[test]
+    fn instruction_with_malformed_argument_is_rendered_without_panicking() {
+        let mut row = Array1::zeros(BASE_WIDTH);
+        row[CI.base_table_index()] = Instruction::Swap(OpStackElement::ST0).opcode_b();
+        row[NIA.base_table_index()] = bfe!(16);
+        assert!(ProcessorTable::op_stack_element_from_nia(row.view()).is_err());
+        let instruction = ProcessorTable::instruction_string_from_row(row.view());
+        assert!("swap <invalid: 16>" == instruction);
+
+        row[NIA.base_table_index()] = bfe!(15);
+        let instruction = ProcessorTable::instruction_string_from_row(row.view());
+        assert!("swap 15" == instruction);
+    }
+
+    #[test]
+    fn processor_table_survives_round_trip_through_csv() {
+        let program = ProgramAndInput::new(crate::example_programs::FIBONACCI_SEQUENCE.clone())
+            .with_input(bfe_array![10]);
+        let (_, _, master_base_table) = master_base_table_for_low_security_level(program);
+        let processor_table = master_base_table.table(TableId::Processor);
+
+        let mut csv = vec![];
+        ProcessorTable::write_trace_csv(processor_table, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        let mut lines = csv.lines();
+        let header = lines.next().unwrap().split(',').collect_vec();
+        assert!(BASE_WIDTH == header.len());
+        assert!("CLK" == header[CLK.base_table_index()]);
+        assert!("IB0" == header[IB0.base_table_index()]);
+
+        let rows = lines
+            .map(|line| line.split(',').map(|e| bfe!(e.parse::<u64>().unwrap())))
+            .flat_map(|row| row.collect_vec())
+            .collect_vec();
+        let parsed_table = Array2::from_shape_vec(processor_table.raw_dim(), rows).unwrap();
+        assert!(processor_table == parsed_table);
+    }
+
+    #[test]
+    fn writing_table_of_wrong_width_as_csv_is_an_error() {
+        let table = Array2::zeros([2, BASE_WIDTH - 1]);
+        let mut csv = vec![];
+        let_assert!(Err(err) = ProcessorTable::write_trace_csv(table.view(), &mut csv));
+        assert!(io::ErrorKind::InvalidInput == err.kind());
+        assert!(csv.is_empty());
+    }
+
     #[derive(Debug, Clone)]
     struct TestRows {
         pub challenges: Challenges,
@@ -3924,11 +4772,8 @@ pub(crate) mod tests {
         debug_info: TestRowsDebugInfo,
     ) {
         let instruction = debug_info.instruction;
-        let circuit_builder = ConstraintCircuitBuilder::new();
-        let transition_constraints = ExtProcessorTable::transition_constraints_for_instruction(
-            &circuit_builder,
-            instruction,
-        );
+        let transition_constraints =
+            ExtProcessorTable::transition_constraints_for_instruction(instruction);
 
         for (case_idx, rows) in test_rows.iter().enumerate() {
             let curr_row = rows.consecutive_master_base_table_rows.slice(s![0, ..]);
@@ -3955,6 +4800,9 @@ pub(crate) mod tests {
                     rows.consecutive_ext_base_table_rows.view(),
                     &rows.challenges.challenges,
                 );
+                if !evaluation_result.is_zero() {
+                    print_columns_referenced_by_constraint(constraint, rows);
+                }
                 assert!(
                     evaluation_result.is_zero(),
                     "For case {case_idx}, transition constraint polynomial with \
@@ -3964,6 +4812,57 @@ pub(crate) mod tests {
         }
     }
 
+    /// Print the current and next values of all columns the given constraint refers to.
+    /// Columns whose value changes between the two rows are highlighted: for a failing
+    /// constraint, they are the likely culprits.
+    fn print_columns_referenced_by_constraint(
+        constraint: &ConstraintCircuitMonad<DualRowIndicator>,
+        rows: &TestRows,
+    ) {
+        let base_rows = rows.consecutive_master_base_table_rows.view();
+        let ext_rows = rows.consecutive_ext_base_table_rows.view();
+        let circuit = constraint.consume();
+        let referenced_inputs = ConstraintCircuitMonad::all_nodes_in_multicircuit(&[circuit])
+            .into_iter()
+            .filter_map(|node| match node.expression {
+                CircuitExpression::Input(input) => Some(input),
+                _ => None,
+            });
+
+        let mut referenced_base_columns = vec![];
+        let mut referenced_ext_columns = vec![];
+        for input in referenced_inputs {
+            match input {
+                CurrentBaseRow(index) | NextBaseRow(index) => referenced_base_columns.push(index),
+                CurrentExtRow(index) | NextExtRow(index) => referenced_ext_columns.push(index),
+            }
+        }
+
+        println!("Columns referenced by the failing constraint:");
+        for index in referenced_base_columns.into_iter().unique().sorted() {
+            let column = ProcessorBaseTableColumn::iter()
+                .find(|column| column.master_base_table_index() == index)
+                .map_or_else(|| format!("base column {index}"), |c| c.to_string());
+            let (curr, next) = (base_rows[[0, index]], base_rows[[1, index]]);
+            print_column_diff(&column, curr, next);
+        }
+        for index in referenced_ext_columns.into_iter().unique().sorted() {
+            let column = ProcessorExtTableColumn::iter()
+                .find(|column| column.master_ext_table_index() == index)
+                .map_or_else(|| format!("ext column {index}"), |c| c.to_string());
+            let (curr, next) = (ext_rows[[0, index]], ext_rows[[1, index]]);
+            print_column_diff(&column, curr, next);
+        }
+    }
+
+    fn print_column_diff<FF: FiniteField>(column: &str, curr: FF, next: FF) {
+        let diff = format!("{column}: {curr} → {next}");
+        match curr == next {
+            true => println!("  {diff}"),
+            false => println!("  {}", diff.red().bold()),
+        }
+    }
+
     #[proptest(cases = 20)]
     fn transition_constraints_for_instruction_pop_n(#[strategy(arb())] n: NumberOfWords) {
         let program = triton_program!(push 1 push 2 push 3 push 4 push 5 pop {n} halt);
@@ -4034,6 +4933,40 @@ pub(crate) mod tests {
         assert_constraints_for_rows_with_debug_info(&test_rows, debug_info);
     }
 
+    #[test]
+    fn transition_constraints_for_instruction_pick() {
+        let push_distinct_elements = (0..OpStackElement::COUNT as u64)
+            .map(|i| triton_instr!(push i))
+            .collect_vec();
+        let test_rows = (0..OpStackElement::COUNT)
+            .map(|i| triton_program!({&push_distinct_elements} pick {i} halt))
+            .map(|program| test_row_from_program(program, OpStackElement::COUNT))
+            .collect_vec();
+        let debug_info = TestRowsDebugInfo {
+            instruction: Pick(OpStackElement::ST0),
+            debug_cols_curr_row: vec![ST0, ST1, ST2],
+            debug_cols_next_row: vec![ST0, ST1, ST2],
+        };
+        assert_constraints_for_rows_with_debug_info(&test_rows, debug_info);
+    }
+
+    #[test]
+    fn transition_constraints_for_instruction_place() {
+        let push_distinct_elements = (0..OpStackElement::COUNT as u64)
+            .map(|i| triton_instr!(push i))
+            .collect_vec();
+        let test_rows = (0..OpStackElement::COUNT)
+            .map(|i| triton_program!({&push_distinct_elements} place {i} halt))
+            .map(|program| test_row_from_program(program, OpStackElement::COUNT))
+            .collect_vec();
+        let debug_info = TestRowsDebugInfo {
+            instruction: Place(OpStackElement::ST0),
+            debug_cols_curr_row: vec![ST0, ST1, ST2],
+            debug_cols_next_row: vec![ST0, ST1, ST2],
+        };
+        assert_constraints_for_rows_with_debug_info(&test_rows, debug_info);
+    }
+
     #[test]
     fn transition_constraints_for_instruction_swap() {
         let test_rows = (0..OpStackElement::COUNT)
@@ -4640,26 +5573,199 @@ pub(crate) mod tests {
         println!();
         println!("| Instruction         | #polys | max deg | Degrees");
         println!("|:--------------------|-------:|--------:|:------------");
-        let circuit_builder = ConstraintCircuitBuilder::new();
-        for instruction in ALL_INSTRUCTIONS {
-            let constraints = ExtProcessorTable::transition_constraints_for_instruction(
-                &circuit_builder,
-                instruction,
-            );
-            let degrees = constraints
-                .iter()
-                .map(|circuit| circuit.clone().consume().degree())
-                .collect_vec();
+        for (instruction, degrees) in ExtProcessorTable::transition_constraint_degrees() {
             let max_degree = degrees.iter().max().unwrap_or(&0);
             let degrees_str = degrees.iter().join(", ");
             println!(
                 "| {:<19} | {:>6} | {max_degree:>7} | [{degrees_str}]",
                 format!("{instruction}"),
-                constraints.len(),
+                degrees.len(),
+            );
+        }
+    }
+
+    #[test]
+    fn transition_constraints_of_all_instructions_can_be_written_as_dot() {
+        for instruction in ALL_INSTRUCTIONS {
+            let mut dot = vec![];
+            let_assert!(
+                Ok(()) = ExtProcessorTable::write_transition_constraints_for_instruction_as_dot(
+                    instruction,
+                    &mut dot
+                )
             );
+            let_assert!(Ok(dot) = String::from_utf8(dot));
+            assert!(dot.starts_with("digraph"), "{instruction}");
+            assert!(dot.trim_end().ends_with('}'), "{instruction}");
+        }
+    }
+
+    #[test]
+    fn op_stack_invariants_hold_for_all_instructions() {
+        assert!(let Ok(()) = ExtProcessorTable::verify_op_stack_invariants());
+    }
+
+    #[test]
+    fn op_stack_pointer_check_detects_wrong_op_stack_size_influence() {
+        for instruction in [Push(bfe!(0)), Pop(N3), Hash, Nop] {
+            let influence = instruction.op_stack_size_influence();
+            let changes_by =
+                |delta| ExtProcessorTable::op_stack_pointer_changes_by(instruction, delta);
+            assert!(changes_by(influence), "{instruction}");
+            assert!(!changes_by(influence + 1), "{instruction}");
+            assert!(!changes_by(influence - 2), "{instruction}");
+        }
+    }
+
+    #[test]
+    fn transition_constraint_degrees_cover_all_instructions() {
+        let all_degrees = ExtProcessorTable::transition_constraint_degrees();
+        assert!(ALL_INSTRUCTIONS.len() == all_degrees.len());
+
+        for (instruction, degrees) in all_degrees {
+            let num_constraints =
+                ExtProcessorTable::transition_constraints_for_instruction(instruction).len();
+            assert!(num_constraints == degrees.len(), "{instruction}");
+        }
+    }
+
+    /// A program that executes the given instruction at least once, together with the input it
+    /// needs to run to completion.
+    fn program_executing_instruction(instruction: Instruction) -> ProgramAndInput {
+        match instruction {
+            Call(_) | Return | Recurse => test_program_for_call_recurse_return(),
+            RecurseOrReturn => test_program_for_recurse_or_return(),
+            _ => {
+                let push_instructions = triton_asm![push 1; 10];
+                let program =
+                    triton_program!(sponge_init {&push_instructions} {instruction} nop halt);
+                let non_determinism =
+                    NonDeterminism::from(bfe_array![1; 5]).with_digests([Digest::default()]);
+                ProgramAndInput::new(program)
+                    .with_input(bfe_array![1; 5])
+                    .with_non_determinism(non_determinism)
+            }
+        }
+    }
+
+    /// Starting from an honest pair of consecutive rows in which the current row executes the
+    /// given instruction, tamper with the next row and assert that at least one of the
+    /// instruction's [transition constraints][constraints] detects the tampering.
+    ///
+    /// The tampered register and the offset are derived from the `seed`, making failures
+    /// reproducible. Only registers that every instruction must constrain are tampered with,
+    /// namely the instruction pointer, the jump stack pointer, and the op stack pointer.
+    ///
+    /// [constraints]: ExtProcessorTable::transition_constraints_for_instruction
+    pub(crate) fn assert_tampered_transition_violates_constraints_of_instruction(
+        instruction: Instruction,
+        seed: u64,
+    ) {
+        let (_, _, master_base_table, master_ext_table, challenges) =
+            master_tables_for_low_security_level(program_executing_instruction(instruction));
+        let base_table = master_base_table.trace_table();
+        let_assert!(
+            Some(row_idx) = base_table.rows().into_iter().position(|row| {
+                row[CI.master_base_table_index()] == instruction.opcode_b()
+                    && row[IsPadding.master_base_table_index()].is_zero()
+            })
+        );
+        let mut base_rows = base_table.slice(s![row_idx..=row_idx + 1, ..]).to_owned();
+        let mut ext_rows = master_ext_table
+            .trace_table()
+            .slice(s![row_idx..=row_idx + 1, ..])
+            .to_owned();
+
+        // Instruction `halt` is always followed by a padding row, for which its transition
+        // constraints are disabled. Build the row `halt` would transition into otherwise.
+        if instruction == Halt {
+            let current_base_row = base_rows.row(0).to_owned();
+            base_rows.row_mut(1).assign(&current_base_row);
+            base_rows[[1, IP.master_base_table_index()]] += bfe!(1);
+            let current_ext_row = ext_rows.row(0).to_owned();
+            ext_rows.row_mut(1).assign(&current_ext_row);
+        }
+
+        let transition_constraints =
+            ExtProcessorTable::transition_constraints_for_instruction(instruction);
+        let num_violated_constraints = |base_rows: &Array2<BFieldElement>| {
+            transition_constraints
+                .iter()
+                .map(|constraint| {
+                    constraint.clone().consume().evaluate(
+                        base_rows.view(),
+                        ext_rows.view(),
+                        &challenges.challenges,
+                    )
+                })
+                .filter(|residual| !residual.is_zero())
+                .count()
+        };
+        assert!(
+            0 == num_violated_constraints(&base_rows),
+            "honest transition of {instruction} must satisfy all its constraints"
+        );
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let tampered_column = [IP, JSP, OpStackPointer][rng.gen_range(0..3)];
+        let offset = bfe!(rng.gen_range(1..=BFieldElement::MAX));
+        base_rows[[1, tampered_column.master_base_table_index()]] += offset;
+        assert!(
+            0 < num_violated_constraints(&base_rows),
+            "tampering with next row's {tampered_column} by {offset} must violate a transition \
+            constraint of {instruction} (seed {seed})"
+        );
+    }
+
+    #[proptest(cases = 3)]
+    fn tampered_transition_violates_constraints_of_every_instruction(seed: u64) {
+        for instruction in ALL_INSTRUCTIONS {
+            assert_tampered_transition_violates_constraints_of_instruction(instruction, seed);
         }
     }
 
+    #[test]
+    fn honest_row_pair_violates_no_transition_constraint() {
+        let program = triton_program!(push 3 push 4 add pop 1 halt);
+        let test_rows = test_row_from_program(program, 2);
+        let base_rows = test_rows.consecutive_master_base_table_rows;
+        let ext_rows = test_rows.consecutive_ext_base_table_rows;
+
+        let violated_constraints = ExtProcessorTable::find_violated_transition_constraints(
+            base_rows.row(0),
+            ext_rows.row(0),
+            base_rows.row(1),
+            ext_rows.row(1),
+            &test_rows.challenges,
+        );
+        assert!(violated_constraints.is_empty());
+    }
+
+    #[test]
+    fn skipped_clock_cycle_violates_transition_constraint_clk_increases_by_1() {
+        let program = triton_program!(push 3 push 4 add pop 1 halt);
+        let test_rows = test_row_from_program(program, 2);
+        let mut base_rows = test_rows.consecutive_master_base_table_rows;
+        let ext_rows = test_rows.consecutive_ext_base_table_rows;
+        base_rows[[1, CLK.master_base_table_index()]] += bfe!(1);
+
+        let violated_constraints = ExtProcessorTable::find_violated_transition_constraints(
+            base_rows.row(0),
+            ext_rows.row(0),
+            base_rows.row(1),
+            ext_rows.row(1),
+            &test_rows.challenges,
+        );
+
+        let clk_increases_by_1_index = 0;
+        let_assert!(
+            Some(&(_, residual)) = violated_constraints
+                .iter()
+                .find(|(index, _)| *index == clk_increases_by_1_index)
+        );
+        assert!(xfe!(1) == residual);
+    }
+
     #[test]
     fn opcode_decomposition_for_skiz_is_unique() {
         let max_value_of_skiz_constraint_for_nia_decomposition =
@@ -4755,6 +5861,21 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn stack_registers_correspond_to_op_stack_elements() {
+        let stack_registers = ProcessorBaseTableColumn::stack_registers();
+        for (stack_element, stack_register) in
+            OpStackElement::all().into_iter().zip_eq(stack_registers)
+        {
+            assert!(format!("ST{stack_element}") == stack_register.to_string());
+            let index = stack_element.into();
+            assert_eq!(
+                stack_register,
+                ProcessorTable::op_stack_column_by_index(index)
+            );
+        }
+    }
+
     #[test]
     fn can_get_op_stack_column_for_in_range_index() {
         for index in 0..OpStackElement::COUNT {
@@ -4800,6 +5921,47 @@ pub(crate) mod tests {
         );
     }
 
+    #[proptest]
+    fn factor_for_op_stack_table_running_product_agrees_with_op_stack_size_influence(
+        #[strategy(vec(arb(), BASE_WIDTH))] previous_row: Vec<BFieldElement>,
+        #[strategy(vec(arb(), BASE_WIDTH))] current_row: Vec<BFieldElement>,
+        #[strategy(arb())] challenges: Challenges,
+        #[strategy(arb())] instruction: Instruction,
+    ) {
+        let mut previous_row = Array1::from(previous_row);
+        let mut current_row = Array1::from(current_row);
+        previous_row[CI.base_table_index()] = instruction.opcode_b();
+        previous_row[NIA.base_table_index()] = instruction.arg().unwrap_or_default();
+        current_row[IsPadding.base_table_index()] = bfe!(0);
+
+        let row_with_shorter_stack = if instruction.grows_op_stack() {
+            previous_row.view()
+        } else {
+            current_row.view()
+        };
+        let op_stack_delta = instruction.op_stack_size_influence().unsigned_abs() as usize;
+        let expected_factor = (0..op_stack_delta)
+            .map(|offset| {
+                let stack_element = ProcessorTable::op_stack_column_by_index(15 - offset);
+                let op_stack_pointer = row_with_shorter_stack[OpStackPointer.base_table_index()];
+                let compressed_row = previous_row[CLK.base_table_index()]
+                    * challenges[OpStackClkWeight]
+                    + previous_row[IB1.base_table_index()] * challenges[OpStackIb1Weight]
+                    + (op_stack_pointer + bfe!(offset as u64)) * challenges[OpStackPointerWeight]
+                    + row_with_shorter_stack[stack_element.base_table_index()]
+                        * challenges[OpStackFirstUnderflowElementWeight];
+                challenges[OpStackIndeterminate] - compressed_row
+            })
+            .fold(xfe!(1), |acc, factor| acc * factor);
+
+        let factor = ProcessorTable::factor_for_op_stack_table_running_product(
+            previous_row.view(),
+            current_row.view(),
+            &challenges,
+        );
+        prop_assert_eq!(expected_factor, factor);
+    }
+
     #[proptest]
     fn constructing_factor_for_ram_table_running_product_never_panics(
         #[strategy(vec(arb(), BASE_WIDTH))] previous_row: Vec<BFieldElement>,
@@ -4815,6 +5977,36 @@ pub(crate) mod tests {
         );
     }
 
+    #[proptest]
+    fn factor_for_ram_table_running_product_uses_compressed_rows(
+        #[strategy(vec(arb(), BASE_WIDTH))] previous_row: Vec<BFieldElement>,
+        #[strategy(vec(arb(), BASE_WIDTH))] current_row: Vec<BFieldElement>,
+        #[strategy(arb())] challenges: Challenges,
+    ) {
+        let mut previous_row = Array1::from(previous_row);
+        let mut current_row = Array1::from(current_row);
+        previous_row[CI.base_table_index()] = Instruction::WriteMem(N1).opcode_b();
+        previous_row[NIA.base_table_index()] = N1.into();
+        current_row[IsPadding.base_table_index()] = bfe!(0);
+
+        let_assert!(
+            Some(factor) = ProcessorTable::factor_for_ram_table_running_product(
+                previous_row.view(),
+                current_row.view(),
+                &challenges,
+            )
+        );
+
+        let compressed_row = ram_table::ram_compressed_row(
+            previous_row[CLK.base_table_index()],
+            ram_table::INSTRUCTION_TYPE_WRITE,
+            previous_row[ST0.base_table_index()],
+            previous_row[ST1.base_table_index()],
+            &challenges,
+        );
+        prop_assert_eq!(challenges[RamIndeterminate] - compressed_row, factor);
+    }
+
     #[proptest]
     fn xx_product_is_accurate(
         #[strategy(arb())] a: XFieldElement,
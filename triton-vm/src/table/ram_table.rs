@@ -36,6 +36,23 @@ pub const INSTRUCTION_TYPE_WRITE: BFieldElement = BFieldElement::new(0);
 pub const INSTRUCTION_TYPE_READ: BFieldElement = BFieldElement::new(1);
 pub const PADDING_INDICATOR: BFieldElement = BFieldElement::new(2);
 
+/// Compress a RAM access into a single [extension field element](XFieldElement) for the
+/// Permutation Argument between the [Processor Table](crate::table::processor_table) and the
+/// [RAM Table](RamTable). Both tables use this function, guaranteeing that their compressions
+/// agree.
+pub(crate) fn ram_compressed_row(
+    clk: BFieldElement,
+    instruction_type: BFieldElement,
+    ram_pointer: BFieldElement,
+    ram_value: BFieldElement,
+    challenges: &Challenges,
+) -> XFieldElement {
+    clk * challenges[RamClkWeight]
+        + instruction_type * challenges[RamInstructionTypeWeight]
+        + ram_pointer * challenges[RamPointerWeight]
+        + ram_value * challenges[RamValueWeight]
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
 pub struct RamTableCall {
     pub clk: u32,
@@ -354,10 +371,13 @@ impl RamTable {
             let clk = row[CLK.base_table_index()];
             let current_ram_pointer = row[RamPointer.base_table_index()];
             let ram_value = row[RamValue.base_table_index()];
-            let compressed_row = clk * challenges[RamClkWeight]
-                + instruction_type * challenges[RamInstructionTypeWeight]
-                + current_ram_pointer * challenges[RamPointerWeight]
-                + ram_value * challenges[RamValueWeight];
+            let compressed_row = ram_compressed_row(
+                clk,
+                instruction_type,
+                current_ram_pointer,
+                ram_value,
+                challenges,
+            );
             running_product_for_perm_arg *= challenges[RamIndeterminate] - compressed_row;
             extension_column.push(running_product_for_perm_arg);
         }
@@ -637,6 +657,8 @@ impl ExtRamTable {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use assert2::let_assert;
+    use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use test_strategy::proptest;
@@ -650,6 +672,35 @@ pub(crate) mod tests {
         ram_table_call.to_table_row();
     }
 
+    #[proptest]
+    fn running_product_of_permutation_argument_uses_compressed_rows(
+        #[strategy(vec(arb(), 1..20))] ram_table_calls: Vec<RamTableCall>,
+        #[strategy(arb())] challenges: Challenges,
+    ) {
+        let rows = ram_table_calls
+            .iter()
+            .map(|call| call.to_table_row())
+            .collect_vec();
+        let row_views = rows.iter().map(|row| row.view()).collect_vec();
+        let_assert!(Ok(base_table) = ndarray::stack(Axis(0), &row_views));
+
+        let running_product =
+            RamTable::extension_column_running_product_perm_arg(base_table.view(), &challenges);
+
+        let mut expected_running_product = PermArg::default_initial();
+        for (row, running_product) in base_table.rows().into_iter().zip_eq(running_product) {
+            let compressed_row = ram_compressed_row(
+                row[CLK.base_table_index()],
+                row[InstructionType.base_table_index()],
+                row[RamPointer.base_table_index()],
+                row[RamValue.base_table_index()],
+                &challenges,
+            );
+            expected_running_product *= challenges[RamIndeterminate] - compressed_row;
+            prop_assert_eq!(expected_running_product, running_product);
+        }
+    }
+
     #[test]
     fn bezout_coefficient_polynomials_of_empty_ram_table_are_default() {
         let (a, b) = RamTable::bezout_coefficient_polynomials_coefficients(&[]);
@@ -7,6 +7,7 @@ use strum::Display;
 use strum::EnumCount;
 use strum::EnumIter;
 
+use crate::op_stack::OpStackElement;
 use crate::table::degree_lowering_table::DegreeLoweringBaseTableColumn;
 use crate::table::degree_lowering_table::DegreeLoweringExtTableColumn;
 use crate::table::master_table::CASCADE_TABLE_START;
@@ -144,6 +145,18 @@ pub enum ProcessorBaseTableColumn {
     ClockJumpDifferenceLookupMultiplicity,
 }
 
+impl ProcessorBaseTableColumn {
+    /// The columns holding the op-stack registers, in order of their
+    /// [index](OpStackElement::index).
+    pub const fn stack_registers() -> [Self; OpStackElement::COUNT] {
+        use ProcessorBaseTableColumn::*;
+
+        [
+            ST0, ST1, ST2, ST3, ST4, ST5, ST6, ST7, ST8, ST9, ST10, ST11, ST12, ST13, ST14, ST15,
+        ]
+    }
+}
+
 #[repr(usize)]
 #[derive(Debug, Display, Copy, Clone, Eq, PartialEq, Hash, EnumCount, EnumIter)]
 pub enum ProcessorExtTableColumn {
@@ -1,12 +1,13 @@
 use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::fmt::Arguments;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::fmt::Write;
 use std::ops::Range;
 
 use arbitrary::Arbitrary;
-use itertools::Itertools;
 use ndarray::Array1;
 use num_traits::ConstZero;
 use num_traits::One;
@@ -143,8 +144,8 @@ impl VMState {
         let ram_read = |address| self.ram.get(&address).copied().unwrap_or_else(|| bfe!(0));
 
         match current_instruction {
-            Pop(_) | Divine(_) | Dup(_) | Swap(_) | ReadMem(_) | WriteMem(_) | ReadIo(_)
-            | WriteIo(_) => {
+            Pop(_) | Divine(_) | Pick(_) | Place(_) | Dup(_) | Swap(_) | ReadMem(_)
+            | WriteMem(_) | ReadIo(_) | WriteIo(_) => {
                 let arg = current_instruction.arg().unwrap().value();
                 hvs[..4].copy_from_slice(&decompose_arg(arg));
             }
@@ -227,6 +228,8 @@ impl VMState {
             Pop(n) => self.pop(n)?,
             Push(field_element) => self.push(field_element),
             Divine(n) => self.divine(n)?,
+            Pick(stack_element) => self.pick(stack_element),
+            Place(stack_element) => self.place(stack_element),
             Dup(stack_element) => self.dup(stack_element),
             Swap(stack_element) => self.swap(stack_element),
             Halt => self.halt(),
@@ -237,6 +240,7 @@ impl VMState {
             Recurse => self.recurse()?,
             RecurseOrReturn => self.recurse_or_return()?,
             Assert => self.assert()?,
+            AssertWithCode(code) => self.assert_with_code(code)?,
             ReadMem(n) => self.read_mem(n)?,
             WriteMem(n) => self.write_mem(n)?,
             Hash => self.hash()?,
@@ -245,6 +249,7 @@ impl VMState {
             SpongeAbsorbMem => self.sponge_absorb_mem()?,
             SpongeSqueeze => self.sponge_squeeze()?,
             AssertVector => self.assert_vector()?,
+            AssertVectorWithCode(code) => self.assert_vector_with_code(code)?,
             Add => self.add()?,
             AddI(field_element) => self.addi(field_element),
             Mul => self.mul()?,
@@ -338,6 +343,24 @@ impl VMState {
         Ok(vec![])
     }
 
+    fn pick(&mut self, stack_register: OpStackElement) -> Vec<CoProcessorCall> {
+        let top_of_stack = self.op_stack.len() - 1;
+        let picked_element_index = top_of_stack - usize::from(stack_register);
+        self.op_stack.stack[picked_element_index..].rotate_left(1);
+
+        self.instruction_pointer += 2;
+        vec![]
+    }
+
+    fn place(&mut self, stack_register: OpStackElement) -> Vec<CoProcessorCall> {
+        let top_of_stack = self.op_stack.len() - 1;
+        let place_index = top_of_stack - usize::from(stack_register);
+        self.op_stack.stack[place_index..].rotate_right(1);
+
+        self.instruction_pointer += 2;
+        vec![]
+    }
+
     fn dup(&mut self, stack_register: OpStackElement) -> Vec<CoProcessorCall> {
         let element = self.op_stack[stack_register];
         self.op_stack.push(element);
@@ -407,13 +430,29 @@ impl VMState {
     }
 
     fn assert(&mut self) -> Result<Vec<CoProcessorCall>> {
+        self.pop_asserted_one(0)?;
+        self.instruction_pointer += 1;
+        Ok(vec![])
+    }
+
+    fn assert_with_code(&mut self, code: u32) -> Result<Vec<CoProcessorCall>> {
+        self.pop_asserted_one(code)?;
+        self.instruction_pointer += 2;
+        Ok(vec![Self::error_code_range_check(code)])
+    }
+
+    fn pop_asserted_one(&mut self, code: u32) -> Result<()> {
         if !self.op_stack[ST0].is_one() {
-            return Err(AssertionFailed);
+            return Err(AssertionFailed { code });
         }
         let _ = self.op_stack.pop()?;
+        Ok(())
+    }
 
-        self.instruction_pointer += 1;
-        Ok(vec![])
+    /// The error code of instructions `assert` and `assert_vector`, if any, is range-checked by
+    /// the U32 Table.
+    fn error_code_range_check(code: u32) -> CoProcessorCall {
+        U32Call(U32TableEntry::new(Split, code, 0_u32))
     }
 
     fn halt(&mut self) -> Vec<CoProcessorCall> {
@@ -565,14 +604,25 @@ impl VMState {
     }
 
     fn assert_vector(&mut self) -> Result<Vec<CoProcessorCall>> {
-        for i in 0..Digest::LEN {
-            if self.op_stack[i] != self.op_stack[i + Digest::LEN] {
-                return Err(VectorAssertionFailed(i));
+        self.pop_asserted_vector(0)?;
+        self.instruction_pointer += 1;
+        Ok(vec![])
+    }
+
+    fn assert_vector_with_code(&mut self, code: u32) -> Result<Vec<CoProcessorCall>> {
+        self.pop_asserted_vector(code)?;
+        self.instruction_pointer += 2;
+        Ok(vec![Self::error_code_range_check(code)])
+    }
+
+    fn pop_asserted_vector(&mut self, code: u32) -> Result<()> {
+        for index in 0..Digest::LEN {
+            if self.op_stack[index] != self.op_stack[index + Digest::LEN] {
+                return Err(VectorAssertionFailed { index, code });
             }
         }
         self.op_stack.pop_multiple::<{ Digest::LEN }>()?;
-        self.instruction_pointer += 1;
-        Ok(vec![])
+        Ok(())
     }
 
     fn add(&mut self) -> Result<Vec<CoProcessorCall>> {
@@ -988,6 +1038,23 @@ impl VMState {
         maybe_next_instruction.ok_or(InstructionPointerOverflow)
     }
 
+    /// The number of elements on the op stack, including the op-stack underflow memory.
+    /// Equivalent to the value of register `OpStackPointer`.
+    pub fn op_stack_depth(&self) -> usize {
+        self.op_stack.len()
+    }
+
+    /// The `index`-th element from the top of the op stack, where index 0 refers to the top.
+    /// Unlike indexing into the [`OpStack`], this is not limited to the op-stack registers but
+    /// also reaches into the op-stack underflow memory.
+    ///
+    /// Returns `None` if `index` is not smaller than the [op stack's depth][depth].
+    ///
+    /// [depth]: Self::op_stack_depth
+    pub fn peek_stack(&self, index: usize) -> Option<BFieldElement> {
+        self.op_stack.stack.iter().rev().nth(index).copied()
+    }
+
     fn jump_stack_pop(&mut self) -> Result<(BFieldElement, BFieldElement)> {
         self.jump_stack.pop().ok_or(JumpStackIsEmpty)
     }
@@ -1027,20 +1094,21 @@ impl Display for VMState {
         let register_width = 20;
         let buffer_width = total_width - tab_width - clk_width - 7;
 
-        let print_row = |f: &mut Formatter, s: String| writeln!(f, "│ {s: <total_width$} │");
-        let print_blank_row = |f: &mut Formatter| print_row(f, String::new());
+        let print_row = |f: &mut Formatter, args: Arguments| {
+            writeln!(f, "│ {} │", LeftAligned(args, total_width))
+        };
+        let print_blank_row = |f: &mut Formatter| print_row(f, format_args!(""));
 
         let row = self.to_processor_row();
 
         let register = |reg: ProcessorBaseTableColumn| {
-            let reg_string = format!("{}", row[reg.base_table_index()]);
-            format!("{reg_string:>register_width$}")
+            RightAligned(row[reg.base_table_index()], register_width)
         };
-        let multi_register = |regs: [_; 4]| regs.map(register).join(" | ");
+        let multi_register = |regs: [_; 4]| Joined(regs.map(register));
 
         writeln!(f)?;
         writeln!(f, " ╭─{:─<tab_width$}─╮", "")?;
-        writeln!(f, " │ {: <tab_width$} │", format!("{instruction}"))?;
+        writeln!(f, " │ {} │", LeftAligned(instruction, tab_width))?;
         writeln!(
             f,
             "╭┴─{:─<tab_width$}─┴─{:─<buffer_width$}─┬─{:─>clk_width$}─╮",
@@ -1054,17 +1122,19 @@ impl Display for VMState {
         let jso = register(ProcCol::JSO);
         let jsd = register(ProcCol::JSD);
         let osp = register(ProcCol::OpStackPointer);
-        let clk = row[ProcCol::CLK.base_table_index()].to_string();
-        let clk = clk.trim_start_matches('0');
+        let clk = RightAligned(
+            WithoutLeadingZeros(row[ProcCol::CLK.base_table_index()]),
+            clk_width,
+        );
 
-        let first_line = format!("ip:   {ip} ╷ ci:   {ci} ╷ nia: {nia} │ {clk: >clk_width$}");
+        let first_line = format_args!("ip:   {ip} ╷ ci:   {ci} ╷ nia: {nia} │ {clk}");
         print_row(f, first_line)?;
         writeln!(
             f,
             "│ jsp:  {jsp} │ jso:  {jso} │ jsd: {jsd} ╰─{:─>clk_width$}─┤",
             "",
         )?;
-        print_row(f, format!("osp:  {osp} ╵"))?;
+        print_row(f, format_args!("osp:  {osp} ╵"))?;
         print_blank_row(f)?;
 
         let st_00_03 = multi_register([ProcCol::ST0, ProcCol::ST1, ProcCol::ST2, ProcCol::ST3]);
@@ -1072,47 +1142,41 @@ impl Display for VMState {
         let st_08_11 = multi_register([ProcCol::ST8, ProcCol::ST9, ProcCol::ST10, ProcCol::ST11]);
         let st_12_15 = multi_register([ProcCol::ST12, ProcCol::ST13, ProcCol::ST14, ProcCol::ST15]);
 
-        print_row(f, format!("st0-3:    [ {st_00_03} ]"))?;
-        print_row(f, format!("st4-7:    [ {st_04_07} ]"))?;
-        print_row(f, format!("st8-11:   [ {st_08_11} ]"))?;
-        print_row(f, format!("st12-15:  [ {st_12_15} ]"))?;
+        print_row(f, format_args!("st0-3:    [ {st_00_03} ]"))?;
+        print_row(f, format_args!("st4-7:    [ {st_04_07} ]"))?;
+        print_row(f, format_args!("st8-11:   [ {st_08_11} ]"))?;
+        print_row(f, format_args!("st12-15:  [ {st_12_15} ]"))?;
         print_blank_row(f)?;
 
-        let hv_00_03_line = format!(
-            "hv0-3:    [ {} ]",
-            multi_register([ProcCol::HV0, ProcCol::HV1, ProcCol::HV2, ProcCol::HV3])
-        );
-        let hv_04_05_line = format!(
-            "hv4-5:    [ {} | {} ]",
-            register(ProcCol::HV4),
-            register(ProcCol::HV5),
+        let hv_00_03 = multi_register([ProcCol::HV0, ProcCol::HV1, ProcCol::HV2, ProcCol::HV3]);
+        let hv_04_05 = Joined([register(ProcCol::HV4), register(ProcCol::HV5)]);
+        print_row(f, format_args!("hv0-3:    [ {hv_00_03} ]"))?;
+        print_row(f, format_args!("hv4-5:    [ {hv_04_05} ]"))?;
+
+        let ib_registers = Joined(
+            [
+                ProcCol::IB6,
+                ProcCol::IB5,
+                ProcCol::IB4,
+                ProcCol::IB3,
+                ProcCol::IB2,
+                ProcCol::IB1,
+                ProcCol::IB0,
+            ]
+            .map(|reg| row[reg.base_table_index()]),
         );
-        print_row(f, hv_00_03_line)?;
-        print_row(f, hv_04_05_line)?;
-
-        let ib_registers = [
-            ProcCol::IB6,
-            ProcCol::IB5,
-            ProcCol::IB4,
-            ProcCol::IB3,
-            ProcCol::IB2,
-            ProcCol::IB1,
-            ProcCol::IB0,
-        ]
-        .map(|reg| row[reg.base_table_index()])
-        .map(|bfe| format!("{bfe:>2}"))
-        .join(" | ");
-        print_row(f, format!("ib6-0:    [ {ib_registers} ]",))?;
+        print_row(f, format_args!("ib6-0:    [ {ib_registers} ]"))?;
 
         let Some(ref sponge) = self.sponge else {
             return writeln!(f, "╰─{:─<total_width$}─╯", "");
         };
 
-        let sponge_state_register = |i: usize| sponge.state[i].value();
         let sponge_state_slice = |idxs: Range<usize>| {
-            idxs.map(sponge_state_register)
-                .map(|ss| format!("{ss:>register_width$}"))
-                .join(" | ")
+            let mut slice = [RightAligned(0, register_width); 4];
+            for (padded, i) in slice.iter_mut().zip(idxs) {
+                padded.0 = sponge.state[i].value();
+            }
+            Joined(slice)
         };
 
         let sponge_state_00_03 = sponge_state_slice(0..4);
@@ -1121,14 +1185,107 @@ impl Display for VMState {
         let sponge_state_12_15 = sponge_state_slice(12..16);
 
         writeln!(f, "├─{:─<total_width$}─┤", "")?;
-        print_row(f, format!("sp0-3:    [ {sponge_state_00_03} ]"))?;
-        print_row(f, format!("sp4-7:    [ {sponge_state_04_07} ]"))?;
-        print_row(f, format!("sp8-11:   [ {sponge_state_08_11} ]"))?;
-        print_row(f, format!("sp12-15:  [ {sponge_state_12_15} ]"))?;
+        print_row(f, format_args!("sp0-3:    [ {sponge_state_00_03} ]"))?;
+        print_row(f, format_args!("sp4-7:    [ {sponge_state_04_07} ]"))?;
+        print_row(f, format_args!("sp8-11:   [ {sponge_state_08_11} ]"))?;
+        print_row(f, format_args!("sp12-15:  [ {sponge_state_12_15} ]"))?;
         writeln!(f, "╰─{:─<total_width$}─╯", "")
     }
 }
 
+/// Counts the [`char`]s written to it, discarding the written data itself.
+#[derive(Debug, Default, Copy, Clone)]
+struct CharCounter(usize);
+
+impl Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> FmtResult {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// The number of [`char`]s in the [`Display`] representation of the given item.
+/// Unlike `item.to_string().chars().count()`, this does not allocate.
+fn display_width(item: &impl Display) -> usize {
+    let mut counter = CharCounter::default();
+    let _ = write!(counter, "{item}");
+    counter.0
+}
+
+/// Pads the [`Display`] representation of the wrapped item with spaces on the right until it
+/// reaches the given width. Works with any `Display` implementation, including those ignoring
+/// [`Formatter::width`].
+#[derive(Debug, Copy, Clone)]
+struct LeftAligned<T>(T, usize);
+
+impl<T: Display> Display for LeftAligned<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Self(item, width) = self;
+        let padding = width.saturating_sub(display_width(item));
+        write!(f, "{item}{:padding$}", "")
+    }
+}
+
+/// Pads the [`Display`] representation of the wrapped item with spaces on the left until it
+/// reaches the given width. Works with any `Display` implementation, including those ignoring
+/// [`Formatter::width`].
+#[derive(Debug, Copy, Clone)]
+struct RightAligned<T>(T, usize);
+
+impl<T: Display> Display for RightAligned<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let Self(item, width) = self;
+        let padding = width.saturating_sub(display_width(item));
+        write!(f, "{:padding$}{item}", "")
+    }
+}
+
+/// Displays all wrapped items, separated by `" | "`.
+#[derive(Debug, Copy, Clone)]
+struct Joined<T, const N: usize>([T; N]);
+
+impl<T: Display, const N: usize> Display for Joined<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for (i, item) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " | ")?;
+            }
+            write!(f, "{item}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays the wrapped item with all leading `'0'`s removed.
+#[derive(Debug, Copy, Clone)]
+struct WithoutLeadingZeros<T>(T);
+
+impl<T: Display> Display for WithoutLeadingZeros<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut writer = LeadingZeroSkipper {
+            inner: f,
+            is_skipping: true,
+        };
+        write!(writer, "{}", self.0)
+    }
+}
+
+struct LeadingZeroSkipper<'a, 'f> {
+    inner: &'a mut Formatter<'f>,
+    is_skipping: bool,
+}
+
+impl Write for LeadingZeroSkipper<'_, '_> {
+    fn write_str(&mut self, s: &str) -> FmtResult {
+        if !self.is_skipping {
+            return self.inner.write_str(s);
+        }
+        let s = s.trim_start_matches('0');
+        self.is_skipping = s.is_empty();
+        self.inner.write_str(s)
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use std::ops::BitAnd;
@@ -1137,6 +1294,7 @@ pub(crate) mod tests {
     use assert2::assert;
     use assert2::let_assert;
     use itertools::izip;
+    use itertools::Itertools;
     use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
@@ -1149,9 +1307,9 @@ pub(crate) mod tests {
     use twenty_first::math::other::random_elements;
 
     use crate::example_programs::*;
+    use crate::program::ProgramAndInput;
     use crate::shared_tests::prove_and_verify;
     use crate::shared_tests::LeavedMerkleTreeTestData;
-    use crate::shared_tests::ProgramAndInput;
     use crate::shared_tests::DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS;
     use crate::triton_asm;
     use crate::triton_instr;
@@ -1395,6 +1553,18 @@ pub(crate) mod tests {
         ))
     }
 
+    pub(crate) fn test_program_for_asserts_with_error_codes() -> ProgramAndInput {
+        ProgramAndInput::new(triton_program!(
+            push 1 assert 7
+            push 1 push 2 push 3 push 4 push 5
+            push 1 push 2 push 3 push 4 push 5
+            assert_vector 42
+            push 1 assert 0
+            push 1 assert 4294967295
+            halt
+        ))
+    }
+
     pub(crate) fn test_program_for_sponge_instructions() -> ProgramAndInput {
         let push_10_zeros = triton_asm![push 0; 10];
         ProgramAndInput::new(triton_program!(
@@ -2048,7 +2218,7 @@ pub(crate) mod tests {
         );
         let program_and_input = ProgramAndInput::new(program);
         let_assert!(Err(err) = program_and_input.run());
-        let_assert!(AssertionFailed = err.source);
+        let_assert!(AssertionFailed { .. } = err.source);
     }
 
     pub(crate) fn test_program_for_split() -> ProgramAndInput {
@@ -2261,6 +2431,99 @@ pub(crate) mod tests {
         println!("{last_processor_row}");
     }
 
+    #[test]
+    fn peeking_into_op_stack_reaches_underflow_memory() {
+        let program = triton_program!(push 1 push 2 push 3 push 4 push 5 halt);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        let_assert!(Ok(()) = vm_state.run());
+
+        let expected_depth = OpStackElement::COUNT + 5;
+        assert!(expected_depth == vm_state.op_stack_depth());
+        for (index, expected) in (1..=5).rev().enumerate() {
+            assert!(Some(bfe!(expected)) == vm_state.peek_stack(index));
+        }
+
+        let program_digest = program.hash().values();
+        for (index, digest_element) in program_digest.into_iter().enumerate() {
+            let stack_index = expected_depth - Digest::LEN + index;
+            assert!(Some(digest_element) == vm_state.peek_stack(stack_index));
+        }
+        assert!(None == vm_state.peek_stack(expected_depth));
+    }
+
+    fn vm_state_after_push_2_push_minus_1_add() -> VMState {
+        let program = triton_program!(push 2 push -1 add assert halt);
+        let mut vm_state = VMState::new(&program, [].into(), [].into());
+        for _ in 0..3 {
+            let_assert!(Ok(_) = vm_state.step());
+        }
+        vm_state
+    }
+
+    #[test]
+    fn display_of_vm_state_matches_snapshot() {
+        let expected = r"
+ ╭────────────────────────────────────────────────────────╮
+ │ assert                                                 │
+╭┴────────────────────────────────────────────────────────┴───────────────────────────┬───────────────────╮
+│ ip:                      5 ╷ ci:                     10 ╷ nia:                    0 │                 3 │
+│ jsp:                     0 │ jso:                     0 │ jsd:                    0 ╰───────────────────┤
+│ osp:                    17 ╵                                                                            │
+│                                                                                                         │
+│ st0-3:    [                    1 |                    0 |                    0 |                    0 ] │
+│ st4-7:    [                    0 |                    0 |                    0 |                    0 ] │
+│ st8-11:   [                    0 |                    0 |                    0 |                    0 ] │
+│ st12-15:  [ 03446302186868708751 | 09982093984857817696 | 02635510442930299280 | 01401985024703060743 ] │
+│                                                                                                         │
+│ hv0-3:    [                    0 |                    0 |                    0 |                    0 ] │
+│ hv4-5:    [                    0 |                    0 ]                                               │
+│ ib6-0:    [ 0 | 0 | 0 | 1 | 0 | 1 | 0 ]                                                                 │
+╰─────────────────────────────────────────────────────────────────────────────────────────────────────────╯
+";
+        let vm_state = vm_state_after_push_2_push_minus_1_add();
+        assert!(expected == vm_state.to_string());
+    }
+
+    #[test]
+    fn display_of_vm_state_with_initialized_sponge_matches_snapshot() {
+        let expected = r"
+ ╭────────────────────────────────────────────────────────╮
+ │ assert                                                 │
+╭┴────────────────────────────────────────────────────────┴───────────────────────────┬───────────────────╮
+│ ip:                      5 ╷ ci:                     10 ╷ nia:                    0 │                 3 │
+│ jsp:                     0 │ jso:                     0 │ jsd:                    0 ╰───────────────────┤
+│ osp:                    17 ╵                                                                            │
+│                                                                                                         │
+│ st0-3:    [                    1 |                    0 |                    0 |                    0 ] │
+│ st4-7:    [                    0 |                    0 |                    0 |                    0 ] │
+│ st8-11:   [                    0 |                    0 |                    0 |                    0 ] │
+│ st12-15:  [ 03446302186868708751 | 09982093984857817696 | 02635510442930299280 | 01401985024703060743 ] │
+│                                                                                                         │
+│ hv0-3:    [                    0 |                    0 |                    0 |                    0 ] │
+│ hv4-5:    [                    0 |                    0 ]                                               │
+│ ib6-0:    [ 0 | 0 | 0 | 1 | 0 | 1 | 0 ]                                                                 │
+├─────────────────────────────────────────────────────────────────────────────────────────────────────────┤
+│ sp0-3:    [                    0 |                    0 |                    0 |                    0 ] │
+│ sp4-7:    [                    0 |                    0 |                    0 |                    0 ] │
+│ sp8-11:   [                    0 |                    0 |                    0 |                    0 ] │
+│ sp12-15:  [                    0 |                    0 |                    0 |                    0 ] │
+╰─────────────────────────────────────────────────────────────────────────────────────────────────────────╯
+";
+        let mut vm_state = vm_state_after_push_2_push_minus_1_add();
+        vm_state.sponge = Some(Tip5::init());
+        assert!(expected == vm_state.to_string());
+    }
+
+    #[test]
+    fn display_helpers_pad_by_number_of_chars() {
+        assert!("╷  " == LeftAligned("╷", 3).to_string());
+        assert!("  ╷" == RightAligned("╷", 3).to_string());
+        assert!("too long" == RightAligned("too long", 3).to_string());
+        assert!("1 | 2 | 3" == Joined([1, 2, 3]).to_string());
+        assert!("" == WithoutLeadingZeros(bfe!(0)).to_string());
+        assert!("1002" == WithoutLeadingZeros("001002").to_string());
+    }
+
     #[test]
     fn run_tvm_basic_ram_read_write() {
         let program = triton_program!(
@@ -2406,6 +2669,38 @@ pub(crate) mod tests {
         assert!(bfe!(2) == standard_out[0]);
     }
 
+    #[test]
+    fn pick_and_place_move_stack_elements_as_expected() {
+        let program = triton_program!(
+            push 4 push 3 push 2 push 1 push 0  // _ 4 3 2 1 0
+            pick 3                              // _ 4 2 1 0 3
+            place 4                             // _ 3 4 2 1 0
+            write_io 5 halt
+        );
+        let_assert!(Ok(standard_out) = program.run([].into(), [].into()));
+        assert!(bfe_vec![0, 1, 2, 4, 3] == standard_out);
+    }
+
+    #[proptest]
+    fn place_undoes_pick(
+        #[strategy(arb())] st: OpStackElement,
+        #[strategy(arb())] elements: [BFieldElement; OpStackElement::COUNT],
+    ) {
+        let push_elements = elements.map(|element| triton_instr!(push element));
+        let program = triton_program!({&push_elements} pick {st} place {st} halt);
+        let mut state = VMState::new(&program, [].into(), [].into());
+        let_assert!(Ok(()) = state.run());
+
+        let reference_program = triton_program!({&push_elements} halt);
+        let mut reference_state = VMState::new(&reference_program, [].into(), [].into());
+        let_assert!(Ok(()) = reference_state.run());
+
+        // the programs' digests, which are at the bottom of the op stack, differ
+        let pushed_elements =
+            |state: &VMState| state.op_stack.stack[OpStackElement::COUNT..].to_vec();
+        assert!(pushed_elements(&reference_state) == pushed_elements(&state));
+    }
+
     #[test]
     fn swap_st0_is_like_no_op() {
         let program = triton_program!(push 42 swap 0 write_io 1 halt);
@@ -2514,7 +2809,7 @@ pub(crate) mod tests {
         let bad_std_in = PublicInput::from(bad_sudoku.map(|b| bfe!(b)));
         let secret_in = NonDeterminism::default();
         let_assert!(Err(err) = program.trace_execution(bad_std_in, secret_in));
-        let_assert!(AssertionFailed = err.source);
+        let_assert!(AssertionFailed { .. } = err.source);
     }
 
     fn instruction_does_not_change_vm_state_when_crashing_vm(